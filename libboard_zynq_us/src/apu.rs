@@ -0,0 +1,175 @@
+//! A53 core control: reset vectors, per-core resets and power-down requests
+//!
+//! Secondary cores are parked by the boot code; to run one from a new address, hold it in reset,
+//! set its reset vector and release it. See UG1085 Ch. 3 and UG1087 APU, CRF_APB RST_FPD_APU
+//! and PMU_GLOBAL PWR_STATE.
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+
+use libregister::{RegisterR, RegisterRW};
+
+use crate::reset::Reset;
+use crate::slcr::{apu, pmu_global};
+
+pub const NUM_CORES: usize = 4;
+
+/// Reset vectors are 40-bit and word aligned
+const RVBAR_MASK: u64 = 0xFF_FFFF_FFFC;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Core {
+    Core0 = 0,
+    Core1 = 1,
+    Core2 = 2,
+    Core3 = 3,
+}
+
+pub const ALL_CORES: [Core; NUM_CORES] = [Core::Core0, Core::Core1, Core::Core2, Core::Core3];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoreState {
+    /// Held in warm or power-on reset
+    Reset,
+    /// Out of reset but powered off by the PMU
+    PoweredDown,
+    /// Powers down on the next WFI
+    PowerDownRequested,
+    Running,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoreError {
+    /// A core can't hold or restart itself
+    CurrentCore,
+}
+
+impl Core {
+    /// Core this code runs on (MPIDR_EL1 Aff0)
+    #[cfg(target_arch = "aarch64")]
+    pub fn current() -> Self {
+        let mpidr: u64;
+        unsafe { asm!("mrs {}, MPIDR_EL1", out(reg) mpidr) };
+        ALL_CORES[(mpidr & 0b11) as usize]
+    }
+
+    // host builds (unit tests) run as core 0
+    #[cfg(not(target_arch = "aarch64"))]
+    pub fn current() -> Self {
+        Core::Core0
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn bit(self) -> u8 {
+        1 << self.index()
+    }
+
+    fn reset(self) -> Reset {
+        match self {
+            Core::Core0 => Reset::Apu0,
+            Core::Core1 => Reset::Apu1,
+            Core::Core2 => Reset::Apu2,
+            Core::Core3 => Reset::Apu3,
+        }
+    }
+
+    fn por(self) -> Reset {
+        match self {
+            Core::Core0 => Reset::Apu0Por,
+            Core::Core1 => Reset::Apu1Por,
+            Core::Core2 => Reset::Apu2Por,
+            Core::Core3 => Reset::Apu3Por,
+        }
+    }
+
+    /// Address the core starts executing from when released from reset
+    pub fn reset_vector(self) -> u64 {
+        let rvbar = &apu::RegisterBlock::apu().rvbaraddr[self.index()];
+        u64::from(rvbar.hi.read()) << 32 | u64::from(rvbar.lo.read())
+    }
+
+    /// Set the reset vector; it only takes effect on the core's next reset
+    pub fn set_reset_vector(self, addr: u64) {
+        assert_eq!(
+            addr & !RVBAR_MASK,
+            0,
+            "unaligned or out of range reset vector"
+        );
+        let rvbar = &mut apu::RegisterBlock::apu().rvbaraddr[self.index()];
+        unsafe {
+            rvbar.lo.write(addr as u32);
+            rvbar.hi.write((addr >> 32) as u32);
+        }
+    }
+
+    /// Whether the core comes out of reset in AArch64 (rather than AArch32)
+    pub fn set_aarch64(self, aarch64: bool) {
+        let bit = self.bit();
+        apu::RegisterBlock::apu().config_0.modify(|r, w| {
+            let cores = r.aa64naa32();
+            w.aa64naa32(if aarch64 { cores | bit } else { cores & !bit })
+        });
+    }
+
+    /// Hold the core in warm reset
+    pub fn hold(self) -> Result<(), CoreError> {
+        if self == Core::current() {
+            return Err(CoreError::CurrentCore);
+        }
+        self.reset().assert();
+        Ok(())
+    }
+
+    /// Release the core from warm and power-on reset
+    pub fn release(self) {
+        self.por().deassert();
+        self.reset().deassert();
+    }
+
+    /// Restart the core at `entry` in AArch64 state
+    pub fn start(self, entry: u64) -> Result<(), CoreError> {
+        self.hold()?;
+        self.set_reset_vector(entry);
+        self.set_aarch64(true);
+        self.request_power_down(false);
+        self.release();
+        Ok(())
+    }
+
+    /// Request the core to power down on its next WFI, or withdraw the request
+    pub fn request_power_down(self, power_down: bool) {
+        let bit = self.bit();
+        apu::RegisterBlock::apu().pwrctl.modify(|r, w| {
+            let cores = r.cpupwrdwnreq();
+            w.cpupwrdwnreq(if power_down {
+                cores | bit
+            } else {
+                cores & !bit
+            })
+        });
+    }
+
+    /// Whether a debugger prevents the core from powering down
+    pub fn debug_no_power_down(self) -> bool {
+        apu::RegisterBlock::apu().pwrstat.read().dbgnopwrdwn() & self.bit() != 0
+    }
+
+    pub fn state(self) -> CoreState {
+        let powered = pmu_global::RegisterBlock::pmu_global()
+            .pwr_state
+            .read()
+            .acpu();
+        let power_down_req = apu::RegisterBlock::apu().pwrctl.read().cpupwrdwnreq();
+        if self.reset().is_asserted() || self.por().is_asserted() {
+            CoreState::Reset
+        } else if powered & self.bit() == 0 {
+            CoreState::PoweredDown
+        } else if power_down_req & self.bit() != 0 {
+            CoreState::PowerDownRequested
+        } else {
+            CoreState::Running
+        }
+    }
+}
@@ -50,6 +50,16 @@ register_at!(RegisterBlock, 0xFD39_0000, s_axi_hp1_fpd);
 register_at!(RegisterBlock, 0xFD3A_0000, s_axi_hp2_fpd);
 register_at!(RegisterBlock, 0xFD3B_0000, s_axi_hp3_fpd);
 register_at!(RegisterBlock, 0xFF9B_0000, s_axi_lpd);
+assert_layout!(RegisterBlock, 0xFD36_0000, {
+    rdchan_ctrl => 0xFD36_0000,
+    rdqos => 0xFD36_0008,
+    rddebug => 0xFD36_0010,
+    wrchan_ctrl => 0xFD36_0014,
+    wrqos => 0xFD36_001C,
+    i_sts => 0xFD36_0E00,
+    i_mask => 0xFD36_0E0C,
+    safety_chk => 0xFD36_0F0C,
+});
 
 register!(rdchan_ctrl, RdchanCtrl, RW, u32);
 // Pause the issuing of new read commands to the PS-side.
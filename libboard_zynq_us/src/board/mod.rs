@@ -0,0 +1,93 @@
+//! Board support
+//!
+//! Everything that depends on how the PS is wired up on a particular carrier lives behind the
+//! [Board] trait. Drivers use [Target], the board selected by the `target_*` feature.
+use crate::clocks::source::RefClocks;
+use crate::ddr::lpddr4::Lpddr4Device;
+use crate::ddr::phy::LaneVref;
+use crate::mio::{MioConfig, MioError};
+
+mod ultra96;
+mod zcu102;
+mod zcu104;
+mod zcu111;
+pub use ultra96::Ultra96;
+pub use zcu102::Zcu102;
+pub use zcu104::Zcu104;
+pub use zcu111::Zcu111;
+
+#[cfg(any(
+    all(feature = "target_zcu111", feature = "target_zcu102"),
+    all(feature = "target_zcu111", feature = "target_zcu104"),
+    all(feature = "target_zcu111", feature = "target_ultra96"),
+    all(feature = "target_zcu102", feature = "target_zcu104"),
+    all(feature = "target_zcu102", feature = "target_ultra96"),
+    all(feature = "target_zcu104", feature = "target_ultra96"),
+))]
+compile_error!("Only one target_* feature may be enabled");
+
+#[cfg(not(any(
+    feature = "target_zcu111",
+    feature = "target_zcu102",
+    feature = "target_zcu104",
+    feature = "target_ultra96"
+)))]
+compile_error!("A target_* feature must be enabled");
+
+#[cfg(feature = "target_zcu111")]
+pub type Target = Zcu111;
+#[cfg(feature = "target_zcu102")]
+pub type Target = Zcu102;
+#[cfg(feature = "target_zcu104")]
+pub type Target = Zcu104;
+#[cfg(feature = "target_ultra96")]
+pub type Target = Ultra96;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UartPort {
+    Uart0,
+    Uart1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum I2cBus {
+    I2c0,
+    I2c1,
+}
+
+/// Where the SPD EEPROM of the PS DDR DIMM sits on the I2C tree
+#[derive(Debug, Clone, Copy)]
+pub struct SpdLocation {
+    pub bus: I2cBus,
+    /// Address of the I2C mux in front of the DIMM
+    pub mux_addr: u16,
+    /// Mux channel select bits for the DIMM
+    pub mux_sel: u8,
+    /// EEPROM address
+    pub addr: u16,
+}
+
+/// PS DDR memory: a DIMM described by its SPD EEPROM, or soldered devices without one
+#[derive(Debug, Clone, Copy)]
+pub enum Dram {
+    Spd(SpdLocation),
+    Lpddr4(Lpddr4Device),
+}
+
+pub trait Board {
+    const NAME: &'static str;
+    /// PLL reference inputs
+    const REF_CLOCKS: RefClocks;
+    /// UART used for `print!`/`println!` and the logger
+    const CONSOLE_UART: UartPort;
+    const DRAM: Dram;
+    /// PS DDR data rate in transfers/s (twice the DDR clock)
+    const DDR_FREQ: u32;
+    /// PS DDR VREF generator settings of byte lanes DX0-8
+    const DDR_LANE_VREF: [LaneVref; 9];
+    /// Max PCAP clock, depends on Vccint of the part (DS926 Table 26)
+    const PCAP_MAX_FREQ: u32;
+
+    /// MIO pin assignment
+    fn mio_config() -> Result<MioConfig, MioError>;
+}
@@ -0,0 +1,63 @@
+//! Ultra96 (XCZU3EG-1)
+use super::{Board, Dram, UartPort};
+use crate::clocks::source::RefClocks;
+use crate::ddr::phy::LaneVref;
+use crate::ddr::{lpddr4::Lpddr4Device, spd::Rtt};
+use crate::mio::{Mio, MioConfig, MioError};
+
+pub struct Ultra96;
+
+impl Board for Ultra96 {
+    const NAME: &'static str = "Ultra96";
+    const REF_CLOCKS: RefClocks = RefClocks {
+        ps_ref_clk: 33_333_000,
+        video_ref_clk: None,
+        alt_ref_clk: None,
+        aux_ref_clk: None,
+        gt_ref_clk: None,
+    };
+    // UART0 goes to the Bluetooth module, UART1 to the JTAG/UART adapter
+    const CONSOLE_UART: UartPort = UartPort::Uart1;
+    // Micron MT53B512M32D2NP-062 WT, 2 GB LPDDR4 on both channels
+    const DRAM: Dram = Dram::Lpddr4(Lpddr4Device {
+        channel_capacity_megabits: 8192,
+        row_addr_bits: 16,
+        ranks: 1,
+        channels: 2,
+        t_ck_min_ps: 625,
+        dq_odt: Rtt::Disabled,
+        ca_odt: Rtt::Disabled,
+    });
+    // LPDDR4-1066, as set up by the FSBL
+    const DDR_FREQ: u32 = 1_066_666_666;
+    // no ECC byte
+    const DDR_LANE_VREF: [LaneVref; 9] = [
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::OFF,
+    ];
+    // Vccint = 0.85 V
+    const PCAP_MAX_FREQ: u32 = 200_000_000;
+
+    fn mio_config() -> Result<MioConfig, MioError> {
+        let mut config = MioConfig::new();
+        config
+            .pin(0, Mio::Uart1Tx)?
+            .pin(1, Mio::Uart1Rx)?
+            .pin(2, Mio::Uart0Rx)?
+            .pin(3, Mio::Uart0Tx)?
+            .pin(4, Mio::I2c1Scl)?
+            .pin(5, Mio::I2c1Sda)?
+            .group(Mio::DpAux)?
+            .group(Mio::Sd1)?
+            .group(Mio::Usb0)?
+            .group(Mio::Usb1)?;
+        Ok(config)
+    }
+}
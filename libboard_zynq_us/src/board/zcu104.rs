@@ -0,0 +1,69 @@
+//! ZCU104 (XCZU7EV-2), UG1267
+use super::{Board, Dram, I2cBus, SpdLocation, UartPort};
+use crate::clocks::source::RefClocks;
+use crate::ddr::phy::LaneVref;
+use crate::mio::{Mio, MioConfig, MioError};
+
+pub struct Zcu104;
+
+impl Board for Zcu104 {
+    const NAME: &'static str = "ZCU104";
+    const REF_CLOCKS: RefClocks = RefClocks {
+        ps_ref_clk: 33_333_000,
+        video_ref_clk: None,
+        alt_ref_clk: None,
+        aux_ref_clk: None,
+        gt_ref_clk: None,
+    };
+    const CONSOLE_UART: UartPort = UartPort::Uart0;
+    // DDR4 SODIMM behind I2C1 mux U34, channel 7
+    const DRAM: Dram = Dram::Spd(SpdLocation {
+        bus: I2cBus::I2c1,
+        mux_addr: 0x74,
+        mux_sel: 0x80,
+        addr: 0x51,
+    });
+    // single-rank DDR4 SODIMM
+    const DDR_FREQ: u32 = 2_133_333_333;
+    // no ECC byte
+    const DDR_LANE_VREF: [LaneVref; 9] = [
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        LaneVref::OFF,
+    ];
+    // Vccint = 0.85 V
+    const PCAP_MAX_FREQ: u32 = 200_000_000;
+
+    fn mio_config() -> Result<MioConfig, MioError> {
+        let mut config = MioConfig::new();
+        config
+            .group(Mio::QspiLower)?
+            .group(Mio::QspiFeedbackClk)?
+            .group(Mio::QspiUpper)?
+            .pin(16, Mio::I2c1Scl)?
+            .pin(17, Mio::I2c1Sda)?
+            .pin(18, Mio::Uart0Rx)?
+            .pin(19, Mio::Uart0Tx)?
+            .pin(26, Mio::PmuIn)?
+            .group(Mio::DpAux)?
+            .pin(31, Mio::PmuIn)?
+            .pin(32, Mio::PmuOut)?
+            .pin(33, Mio::PmuOut)?
+            .pin(34, Mio::PmuOut)?
+            .pin(35, Mio::PmuOut)?
+            .pin(36, Mio::PmuOut)?
+            .pin(37, Mio::PmuOut)?
+            .pin(45, Mio::Sd1Cd)?
+            .group(Mio::Sd1)?
+            .group(Mio::Usb0)?
+            .group(Mio::Gem3)?
+            .group(Mio::Mdio3)?;
+        Ok(config)
+    }
+}
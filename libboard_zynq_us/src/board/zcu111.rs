@@ -0,0 +1,85 @@
+//! ZCU111 (XCZU28DR-2E), UG1271
+use super::{Board, Dram, I2cBus, SpdLocation, UartPort};
+use crate::clocks::source::RefClocks;
+use crate::ddr::phy::LaneVref;
+use crate::mio::{Mio, MioConfig, MioError};
+
+pub struct Zcu111;
+
+const PARTIAL_VREF: LaneVref = LaneVref {
+    ref_sen: true,
+    ref_ien: 0x1,
+};
+
+impl Board for Zcu111 {
+    const NAME: &'static str = "ZCU111";
+    // MIO pins for ALT_REF_CLK and VIDEO_REF_CLK are used for other functions
+    const REF_CLOCKS: RefClocks = RefClocks {
+        ps_ref_clk: 33_333_000,
+        video_ref_clk: None,
+        alt_ref_clk: None,
+        aux_ref_clk: None,
+        gt_ref_clk: None,
+    };
+    const CONSOLE_UART: UartPort = UartPort::Uart0;
+    // Mux is the same for both I2C buses
+    const DRAM: Dram = Dram::Spd(SpdLocation {
+        bus: I2cBus::I2c1,
+        mux_addr: 0x75,
+        mux_sel: 0x08,
+        addr: 0x51,
+    });
+    // Micron MTA4ATF51264HZ-2G6E1, DDR4, max data rate 2666 MHz
+    // But US+ only supports up to 2133 MHz for a single-rank DIMM
+    const DDR_FREQ: u32 = 2_133_333_333;
+    // FSBL: only some of the IOs of DX2-4 and DX6 use the internal VREF, no ECC byte
+    const DDR_LANE_VREF: [LaneVref; 9] = [
+        LaneVref::INTERNAL,
+        LaneVref::INTERNAL,
+        PARTIAL_VREF,
+        PARTIAL_VREF,
+        PARTIAL_VREF,
+        LaneVref::INTERNAL,
+        PARTIAL_VREF,
+        LaneVref::INTERNAL,
+        LaneVref::OFF,
+    ];
+    // Vccint = 0.85 V
+    const PCAP_MAX_FREQ: u32 = 200_000_000;
+
+    fn mio_config() -> Result<MioConfig, MioError> {
+        let mut config = MioConfig::new();
+        config
+            .group(Mio::QspiLower)?
+            // TODO: UG1271 says 6 is NC
+            .group(Mio::QspiFeedbackClk)?
+            .group(Mio::QspiUpper)?
+            .pin(13, Mio::Gpio)?
+            .pin(14, Mio::I2c0Scl)?
+            .pin(15, Mio::I2c0Sda)?
+            .pin(16, Mio::I2c1Scl)?
+            .pin(17, Mio::I2c1Sda)?
+            .pin(18, Mio::Uart0Rx)?
+            .pin(19, Mio::Uart0Tx)?
+            .pin(22, Mio::Gpio)?
+            .pin(23, Mio::Gpio)?
+            // TODO: FSBL has 26 as GPIO but it's wired to PMU in
+            .pin(26, Mio::Gpio)?
+            .group(Mio::DpAux)?
+            // TODO: pins 34-37 are just skipped in the FSBL
+            .pin(32, Mio::PmuOut)?
+            .pin(33, Mio::PmuOut)?
+            .pin(34, Mio::PmuOut)?
+            .pin(35, Mio::PmuOut)?
+            .pin(36, Mio::PmuOut)?
+            .pin(37, Mio::PmuOut)?
+            .pin(38, Mio::Gpio)?
+            .group(Mio::Sd1Data8)?
+            .pin(45, Mio::Sd1Cd)?
+            .group(Mio::Sd1)?
+            .group(Mio::Usb0)?
+            .group(Mio::Gem3)?
+            .group(Mio::Mdio3)?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,284 @@
+//! Reference-counted peripheral clock gating
+//!
+//! [Clocks::init](super::Clocks::init) starts every peripheral clock. Drivers hold a
+//! [ClockGate] for the clocks they use, and [gate_unused] can then be called to stop everything
+//! else. Once the last handle for a clock is dropped, that clock is stopped again.
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use libregister::RegisterRW;
+
+use crate::slcr::{common::Unlocked, crf_apb, crl_apb};
+use crate::sync::BakeryLock;
+
+/// Peripheral clocks with a CLKACT bit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GatedClock {
+    // LPD (CRL_APB)
+    /// Shared by USB0 and USB1
+    Usb3Dual,
+    Gem0,
+    Gem1,
+    Gem2,
+    Gem3,
+    /// Shared by all GEMs
+    GemTsu,
+    Usb0Bus,
+    Usb1Bus,
+    Qspi,
+    Sdio0,
+    Sdio1,
+    Uart0,
+    Uart1,
+    Spi0,
+    Spi1,
+    Can0,
+    Can1,
+    Pcap,
+    Nand,
+    LpdDma,
+    Pl0,
+    Pl1,
+    Pl2,
+    Pl3,
+    I2c0,
+    I2c1,
+    Timestamp,
+    // FPD (CRF_APB)
+    Gpu,
+    Sata,
+    Pcie,
+    FpdDma,
+    DpDma,
+    DpVideo,
+    DpAudio,
+    DpSys,
+}
+
+const NUM_GATED_CLOCKS: usize = GatedClock::DpSys as usize + 1;
+
+const ALL_GATED_CLOCKS: [GatedClock; NUM_GATED_CLOCKS] = [
+    GatedClock::Usb3Dual,
+    GatedClock::Gem0,
+    GatedClock::Gem1,
+    GatedClock::Gem2,
+    GatedClock::Gem3,
+    GatedClock::GemTsu,
+    GatedClock::Usb0Bus,
+    GatedClock::Usb1Bus,
+    GatedClock::Qspi,
+    GatedClock::Sdio0,
+    GatedClock::Sdio1,
+    GatedClock::Uart0,
+    GatedClock::Uart1,
+    GatedClock::Spi0,
+    GatedClock::Spi1,
+    GatedClock::Can0,
+    GatedClock::Can1,
+    GatedClock::Pcap,
+    GatedClock::Nand,
+    GatedClock::LpdDma,
+    GatedClock::Pl0,
+    GatedClock::Pl1,
+    GatedClock::Pl2,
+    GatedClock::Pl3,
+    GatedClock::I2c0,
+    GatedClock::I2c1,
+    GatedClock::Timestamp,
+    GatedClock::Gpu,
+    GatedClock::Sata,
+    GatedClock::Pcie,
+    GatedClock::FpdDma,
+    GatedClock::DpDma,
+    GatedClock::DpVideo,
+    GatedClock::DpAudio,
+    GatedClock::DpSys,
+];
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU8 = AtomicU8::new(0);
+// Number of live ClockGates per clock, indexed by GatedClock
+static REF_COUNTS: [AtomicU8; NUM_GATED_CLOCKS] = [ZERO; NUM_GATED_CLOCKS];
+// Held while a count changes together with its CLKACT bits
+static REF_COUNTS_LOCK: BakeryLock = BakeryLock::new();
+
+impl GatedClock {
+    /// Write the CLKACT bit(s) for this clock
+    pub fn set_active(self, active: bool) {
+        match self {
+            GatedClock::Usb3Dual => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.usb3_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Gem0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb
+                    .gem0_clk_ctrl
+                    .modify(|_, w| w.rx_clkact(active).clkact(active))
+            }),
+            GatedClock::Gem1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb
+                    .gem1_clk_ctrl
+                    .modify(|_, w| w.rx_clkact(active).clkact(active))
+            }),
+            GatedClock::Gem2 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb
+                    .gem2_clk_ctrl
+                    .modify(|_, w| w.rx_clkact(active).clkact(active))
+            }),
+            GatedClock::Gem3 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb
+                    .gem3_clk_ctrl
+                    .modify(|_, w| w.rx_clkact(active).clkact(active))
+            }),
+            GatedClock::GemTsu => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.gem_tsu_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Usb0Bus => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.usb0_bus_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Usb1Bus => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.usb1_bus_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Qspi => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.qspi_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Sdio0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.sdio0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Sdio1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.sdio1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Uart0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.uart0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Uart1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.uart1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Spi0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.spi0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Spi1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.spi1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Can0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.can0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Can1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.can1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pcap => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.pcap_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Nand => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.nand_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::LpdDma => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.lpd_dma_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pl0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.pl0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pl1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.pl1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pl2 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.pl2_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pl3 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.pl3_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::I2c0 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.i2c0_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::I2c1 => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.i2c1_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Timestamp => crl_apb::RegisterBlock::unlocked(|crl_apb| {
+                crl_apb.timestamp_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Gpu => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb
+                    .gpu_clk_ctrl
+                    .modify(|_, w| w.pp1_clkact(active).pp0_clkact(active).clkact(active))
+            }),
+            GatedClock::Sata => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.sata_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::Pcie => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.pcie_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::FpdDma => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.fpd_dma_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::DpDma => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.dp_dma_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::DpVideo => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.dp_video_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::DpAudio => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.dp_audio_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+            GatedClock::DpSys => crf_apb::RegisterBlock::unlocked(|crf_apb| {
+                crf_apb.dp_sys_clk_ctrl.modify(|_, w| w.clkact(active))
+            }),
+        }
+    }
+
+    /// Number of [ClockGate]s currently held for this clock
+    pub fn ref_count(self) -> u8 {
+        REF_COUNTS[self as usize].load(Ordering::Relaxed)
+    }
+}
+
+/// Handle that keeps a peripheral clock running
+///
+/// The clock is started when the first handle for it is acquired and stopped when the last
+/// handle is dropped.
+pub struct ClockGate {
+    clock: GatedClock,
+}
+
+impl ClockGate {
+    pub fn acquire(clock: GatedClock) -> Self {
+        REF_COUNTS_LOCK.lock(|| {
+            let ref_count = &REF_COUNTS[clock as usize];
+            let count = ref_count.load(Ordering::Relaxed);
+            let count = count
+                .checked_add(1)
+                .expect("too many ClockGates for one clock");
+            ref_count.store(count, Ordering::Relaxed);
+            if count == 1 {
+                clock.set_active(true);
+            }
+        });
+        ClockGate { clock }
+    }
+
+    pub fn clock(&self) -> GatedClock {
+        self.clock
+    }
+}
+
+impl Drop for ClockGate {
+    fn drop(&mut self) {
+        REF_COUNTS_LOCK.lock(|| {
+            let ref_count = &REF_COUNTS[self.clock as usize];
+            // every ClockGate holds one count, so this can't underflow
+            let count = ref_count.load(Ordering::Relaxed) - 1;
+            ref_count.store(count, Ordering::Relaxed);
+            if count == 0 {
+                self.clock.set_active(false);
+            }
+        })
+    }
+}
+
+/// Stop every peripheral clock that isn't held by a [ClockGate]
+pub fn gate_unused() {
+    REF_COUNTS_LOCK.lock(|| {
+        for clock in ALL_GATED_CLOCKS.iter() {
+            if clock.ref_count() == 0 {
+                clock.set_active(false);
+            }
+        }
+    })
+}
@@ -4,14 +4,18 @@
 // Original authors: Astro, Harry Ho, pca006132
 // Modifications made for different clock sources, PLL configuration, and SLCRs
 
+use super::board::{Board, Target};
 use super::slcr::{
-    common::Unlocked,
+    common::{PllSource, Unlocked},
     crf_apb::{self, ApuClkSource},
-    crl_apb::{self, IoClkSource, RpuClkSource},
-    iou_slcr,
+    crl_apb::{self, ChkrClkASource, IoClkSource, RpuClkSource},
+    iou_slcr::{self, TtcClkSource},
 };
 use libregister::{RegisterR, RegisterW};
 
+pub mod gate;
+pub mod monitor;
+pub mod pl;
 pub mod source;
 use source::*;
 
@@ -32,10 +36,30 @@ const LPD_LSBUS_MAX_FREQ: u32 = 100_000_000;
 #[allow(unused)]
 const XDOMAIN_MAX_FREQ: u32 = 533_000_000;
 // max PCAP freq is dependent on Vccint, see DS926 Table 26
-// for ZCU111 (ZU28DR -2E) Vccint = 0.85 V
 #[allow(unused)]
-#[cfg(feature = "target_zcu111")]
-const PCAP_MAX_FREQ: u32 = 200_000_000;
+const PCAP_MAX_FREQ: u32 = Target::PCAP_MAX_FREQ;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClockError {
+    /// Requested PLL output frequency (Hz) is outside the DS926 range
+    OutputOutOfRange(u32),
+    /// Resulting VCO frequency (Hz) is outside the DS926 range
+    VcoOutOfRange(u32),
+    /// No UG1085 lock parameters for the given feedback divider
+    NoLockParams(u32),
+    /// Selected PLL reference input isn't available on this board
+    RefClkUnavailable(PllSource),
+    /// Named PLL failed to lock after all reset attempts
+    LockTimeout(&'static str),
+    /// Clock monitor measured a PLL (Hz) outside tolerance of its configured frequency
+    Drift(ChkrClkASource, u32),
+    /// No divisor pair gets close to the requested clock frequency (Hz)
+    DivisorOutOfRange(u32),
+    /// Throttle counter of the given PL clock didn't finish
+    ThrottleTimeout(u8),
+    /// Throttle counters can't emit 0 pulses
+    NoPulses,
+}
 
 #[derive(Debug, Clone)]
 pub struct Clocks {
@@ -53,12 +77,13 @@ pub struct Clocks {
 
 impl Clocks {
     /// Initialize PLLs and component clock sources
-    pub fn init() {
-        init_plls();
+    pub fn init() -> Result<(), ClockError> {
+        init_plls()?;
         Self::init_xdomain_clocks();
         Self::init_lpd_clocks();
         Self::init_fpd_clocks();
         Self::init_misc_clocks();
+        Ok(())
     }
 
     pub fn get() -> Self {
@@ -314,9 +339,13 @@ impl Clocks {
             );
 
             // Use APB interconnect clock
-            iou_slcr
-                .iou_ttc_apb_clk
-                .write(iou_slcr::IouTtcApbClk::zeroed());
+            iou_slcr.iou_ttc_apb_clk.write(
+                iou_slcr::IouTtcApbClk::zeroed()
+                    .ttc3_sel(TtcClkSource::LpdLsbus)
+                    .ttc2_sel(TtcClkSource::LpdLsbus)
+                    .ttc1_sel(TtcClkSource::LpdLsbus)
+                    .ttc0_sel(TtcClkSource::LpdLsbus),
+            );
         });
 
         // TODO: system watchdog timers
@@ -327,14 +356,48 @@ impl Clocks {
         match srcsel {
             crl_apb::IoClkSource::IoPll => self.io,
             crl_apb::IoClkSource::RpuPll => self.rpu,
-            crl_apb::IoClkSource::DdrPllToLpd => {
-                let fpd_regs = crf_apb::RegisterBlock::crf_apb();
-                let divisor = u32::from(fpd_regs.ddr_pll_to_lpd_ctrl.read().divisor0());
-                self.ddr / divisor
-            }
+            crl_apb::IoClkSource::DdrPllToLpd => self.ddr_pll_to_lpd(),
+        }
+    }
+
+    /// Get source frequency from [crate::slcr::crl_apb::RpuClkSource]
+    fn rpu_clk_source_freq(&self, srcsel: crl_apb::RpuClkSource) -> u32 {
+        match srcsel {
+            crl_apb::RpuClkSource::RpuPll => self.rpu,
+            crl_apb::RpuClkSource::IoPll => self.io,
+            crl_apb::RpuClkSource::DdrPllToLpd => self.ddr_pll_to_lpd(),
         }
     }
 
+    fn ddr_pll_to_lpd(&self) -> u32 {
+        let fpd_regs = crf_apb::RegisterBlock::crf_apb();
+        let divisor = u32::from(fpd_regs.ddr_pll_to_lpd_ctrl.read().divisor0());
+        self.ddr / divisor
+    }
+
+    /// DDR controller clock; the DRAM clock runs at twice this
+    pub fn ddr_ctl_clk(&self) -> u32 {
+        let ctrl = crf_apb::RegisterBlock::crf_apb().ddr_clk_ctrl.read();
+        let source_freq = match ctrl.srcsel() {
+            1 => self.video,
+            _ => self.ddr,
+        };
+        // a divisor of 0 divides by 1
+        source_freq / u32::from(ctrl.divisor0()).max(1)
+    }
+
+    /// RPU core clock
+    pub fn rpu_clk(&self) -> u32 {
+        let ctrl = crl_apb::RegisterBlock::crl_apb().rpu_clk_ctrl.read();
+        self.rpu_clk_source_freq(ctrl.srcsel()) / u32::from(ctrl.divisor0())
+    }
+
+    /// LPD APB clock, e.g. for the TTCs and GPIO
+    pub fn lpd_lsbus_clk(&self) -> u32 {
+        let ctrl = crl_apb::RegisterBlock::crl_apb().lpd_lsbus_clk_ctrl.read();
+        self.rpu_clk_source_freq(ctrl.srcsel()) / u32::from(ctrl.divisor0())
+    }
+
     fn uart_ref_clk(&self, uart_clk_ctrl: crl_apb::uart_clk_ctrl::Read) -> u32 {
         let source_freq = self.io_clk_source_freq(uart_clk_ctrl.srcsel());
         source_freq / (u32::from(uart_clk_ctrl.divisor0()) * u32::from(uart_clk_ctrl.divisor1()))
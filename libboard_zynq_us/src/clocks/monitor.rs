@@ -0,0 +1,228 @@
+//! Clock monitor (CHKR) driver
+//!
+//! Each of the eight CRL_APB clock checkers counts cycles of a monitored PLL (clock A) over a
+//! window of CLKB_CNT reference clock (clock B) cycles and flags counts outside the configured
+//! thresholds, see UG1085 Ch. 37 and UG1087 CRL_APB CHKRn_*.
+use libregister::{RegisterR, RegisterRW, RegisterW};
+
+use super::{source::REF_CLOCKS, ClockError, Clocks};
+use crate::slcr::{
+    common::{PllSource, Unlocked},
+    crl_apb::{self, ChkrClkASource, ChkrClkBSource},
+};
+
+pub const NUM_CHECKERS: usize = 8;
+
+// 1 ms at 33.333 MHz PS_REF_CLK
+const DEFAULT_WINDOW: u32 = 33_333;
+/// Tolerance used by [verify_clocks]
+pub const DEFAULT_TOLERANCE_PPM: u32 = 1_000;
+
+pub struct ClockMonitor {
+    index: usize,
+    reference: ChkrClkBSource,
+    ref_freq: u32,
+    window: u32,
+}
+
+impl ClockMonitor {
+    /// Use checker `index` with PS_REF_CLK as the reference
+    pub fn new(index: usize) -> Self {
+        assert!(index < NUM_CHECKERS);
+        ClockMonitor {
+            index,
+            reference: ChkrClkBSource::PsRefClk,
+            ref_freq: REF_CLOCKS.ps_ref_clk,
+            window: DEFAULT_WINDOW,
+        }
+    }
+
+    /// Measure against another reference input instead of PS_REF_CLK
+    pub fn with_reference(mut self, reference: ChkrClkBSource) -> Result<Self, ClockError> {
+        let source = match reference {
+            ChkrClkBSource::PsRefClk => PllSource::PsRefClk,
+            ChkrClkBSource::AltRefClk => PllSource::AltRefClk,
+        };
+        self.ref_freq = REF_CLOCKS
+            .freq(source)
+            .ok_or(ClockError::RefClkUnavailable(source))?;
+        self.reference = reference;
+        Ok(self)
+    }
+
+    /// Set the measurement window in reference clock cycles
+    pub fn with_window(mut self, window: u32) -> Self {
+        assert!(window > 0);
+        self.window = window;
+        self
+    }
+
+    fn bit(&self) -> u8 {
+        1 << self.index
+    }
+
+    /// Clock A count over one window for a clock running at `freq`
+    fn expected_count(&self, freq: u32) -> u32 {
+        (u64::from(freq) * u64::from(self.window) / u64::from(self.ref_freq)) as u32
+    }
+
+    /// Count bounds for `freq` +/- `tolerance_ppm`
+    fn bounds(&self, freq: u32, tolerance_ppm: u32) -> (u32, u32) {
+        let count = u64::from(self.expected_count(freq));
+        let margin = (count * u64::from(tolerance_ppm)).div_ceil(1_000_000);
+        let lower = count.saturating_sub(margin) as u32;
+        let upper = (count + margin).min(u64::from(u32::MAX)) as u32;
+        (lower, upper)
+    }
+
+    fn configure(&self, clock: ChkrClkASource, lower: u32, upper: u32) {
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            let chkr = &mut crl_apb.chkr[self.index];
+            chkr.ctrl.modify(|_, w| w.enable(false));
+            unsafe {
+                chkr.clkb_cnt.write(self.window);
+                chkr.clka_lower.write(lower);
+                chkr.clka_upper.write(upper);
+            }
+            let bit = self.bit();
+            crl_apb.clkmon_status.write(
+                crl_apb::ClkmonStatus::zeroed()
+                    .cnta_overflow(bit)
+                    .clka_max(bit)
+                    .clka_min(bit),
+            );
+            crl_apb.chkr[self.index].ctrl.write(
+                crl_apb::ChkrCtrl::zeroed()
+                    .clka_mux_ctrl(clock)
+                    .clkb_mux_ctrl(self.reference)
+                    .enable(true),
+            );
+        });
+    }
+
+    /// Run one window and return (below lower, above upper)
+    fn single_shot(&self, clock: ChkrClkASource, lower: u32, upper: u32) -> (bool, bool) {
+        self.configure(clock, lower, upper);
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            crl_apb.chkr[self.index]
+                .ctrl
+                .modify(|_, w| w.start_single_shot(true))
+        });
+
+        // No completion flag: wait out the window (twice over) with every spin taking at least
+        // one APU cycle, which is never faster than the APU PLL output
+        let apu = u64::from(Clocks::get().apu.max(self.ref_freq));
+        let cycles = 2 * u64::from(self.window) * apu / u64::from(self.ref_freq);
+        for _ in 0..cycles {
+            core::hint::spin_loop();
+        }
+
+        let crl_apb = crl_apb::RegisterBlock::crl_apb();
+        let status = crl_apb.clkmon_status.read();
+        let bit = self.bit();
+        let below = status.clka_min() & bit != 0;
+        let above = (status.clka_max() | status.cnta_overflow()) & bit != 0;
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            crl_apb.chkr[self.index].ctrl.modify(|_, w| w.enable(false))
+        });
+        (below, above)
+    }
+
+    /// Check whether `clock` is within `tolerance_ppm` of `freq`
+    pub fn check(&self, clock: ChkrClkASource, freq: u32, tolerance_ppm: u32) -> bool {
+        let (lower, upper) = self.bounds(freq, tolerance_ppm);
+        self.single_shot(clock, lower, upper) == (false, false)
+    }
+
+    /// Measure the frequency of `clock` in Hz
+    ///
+    /// The checkers only report threshold violations, so the count is found by bisecting the
+    /// upper threshold over repeated windows.
+    pub fn measure(&self, clock: ChkrClkASource) -> u32 {
+        let (mut lo, mut hi) = (0u32, u32::MAX);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (_, above) = self.single_shot(clock, 0, mid);
+            if above {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        (u64::from(lo) * u64::from(self.ref_freq) / u64::from(self.window)) as u32
+    }
+
+    /// Continuously monitor `clock` and raise the CRL_APB clock monitor interrupt when it
+    /// drifts more than `tolerance_ppm` away from `freq`
+    pub fn monitor(&self, clock: ChkrClkASource, freq: u32, tolerance_ppm: u32) {
+        let (lower, upper) = self.bounds(freq, tolerance_ppm);
+        self.configure(clock, lower, upper);
+        let bit = self.bit();
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            crl_apb.clkmon_enable.write(
+                crl_apb::ClkmonEnable::zeroed()
+                    .cnta_overflow(bit)
+                    .clka_max(bit)
+                    .clka_min(bit),
+            );
+            crl_apb.chkr[self.index]
+                .ctrl
+                .modify(|_, w| w.start_continuous(true));
+        });
+    }
+
+    /// Stop monitoring and mask this checker's interrupts
+    pub fn stop(&self) {
+        let bit = self.bit();
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            crl_apb.chkr[self.index]
+                .ctrl
+                .modify(|_, w| w.start_continuous(false).enable(false));
+            crl_apb.clkmon_disable.write(
+                crl_apb::ClkmonDisable::zeroed()
+                    .cnta_overflow(bit)
+                    .clka_max(bit)
+                    .clka_min(bit),
+            );
+        });
+    }
+
+    /// Whether a drift was flagged since the last [clear](Self::clear)
+    pub fn drifted(&self) -> bool {
+        let status = crl_apb::RegisterBlock::crl_apb().clkmon_status.read();
+        (status.clka_min() | status.clka_max() | status.cnta_overflow()) & self.bit() != 0
+    }
+
+    /// Clear this checker's status bits (and thereby its interrupt)
+    pub fn clear(&self) {
+        let bit = self.bit();
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            crl_apb.clkmon_status.write(
+                crl_apb::ClkmonStatus::zeroed()
+                    .cnta_overflow(bit)
+                    .clka_max(bit)
+                    .clka_min(bit),
+            )
+        });
+    }
+}
+
+/// Measure every PLL against PS_REF_CLK and compare with the frequencies reported by
+/// [Clocks::get]. Intended to run after [Clocks::init].
+pub fn verify_clocks() -> Result<(), ClockError> {
+    let clocks = Clocks::get();
+    let monitor = ClockMonitor::new(0);
+    let plls = [
+        (ChkrClkASource::ApuPll, clocks.apu),
+        (ChkrClkASource::DdrPll, clocks.ddr),
+        (ChkrClkASource::VideoPll, clocks.video),
+        (ChkrClkASource::IoPll, clocks.io),
+        (ChkrClkASource::RpuPll, clocks.rpu),
+    ];
+    for &(pll, freq) in plls.iter() {
+        if !monitor.check(pll, freq, DEFAULT_TOLERANCE_PPM) {
+            return Err(ClockError::Drift(pll, monitor.measure(pll)));
+        }
+    }
+    Ok(())
+}
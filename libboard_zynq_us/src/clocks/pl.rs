@@ -0,0 +1,182 @@
+//! PL fabric clocks (PL0_REF_CLK to PL3_REF_CLK) and their throttle counters
+use libregister::{RegisterR, RegisterRW, RegisterW};
+
+use super::{
+    gate::{ClockGate, GatedClock},
+    ClockError, Clocks,
+};
+use crate::slcr::{
+    common::Unlocked,
+    crl_apb::{self, IoClkSource},
+};
+
+// divisor fields are 6 bits wide
+const PL_DIVISOR_MAX: u32 = 63;
+
+pub struct PlClock {
+    index: usize,
+    gate: Option<ClockGate>,
+}
+
+impl PlClock {
+    pub fn pl0() -> Self {
+        Self::new(0)
+    }
+
+    pub fn pl1() -> Self {
+        Self::new(1)
+    }
+
+    pub fn pl2() -> Self {
+        Self::new(2)
+    }
+
+    pub fn pl3() -> Self {
+        Self::new(3)
+    }
+
+    fn new(index: usize) -> Self {
+        PlClock { index, gate: None }
+    }
+
+    fn clk_ctrl<'a>(&self, crl_apb: &'a mut crl_apb::RegisterBlock) -> &'a mut crl_apb::PlClkCtrl {
+        match self.index {
+            0 => &mut crl_apb.pl0_clk_ctrl,
+            1 => &mut crl_apb.pl1_clk_ctrl,
+            2 => &mut crl_apb.pl2_clk_ctrl,
+            _ => &mut crl_apb.pl3_clk_ctrl,
+        }
+    }
+
+    fn thr_regs<'a>(
+        &self,
+        crl_apb: &'a mut crl_apb::RegisterBlock,
+    ) -> (&'a mut crl_apb::PlThrCtrl, &'a mut crl_apb::PlThrCnt) {
+        match self.index {
+            0 => (&mut crl_apb.pl0_thr_ctrl, &mut crl_apb.pl0_thr_cnt),
+            1 => (&mut crl_apb.pl1_thr_ctrl, &mut crl_apb.pl1_thr_cnt),
+            2 => (&mut crl_apb.pl2_thr_ctrl, &mut crl_apb.pl2_thr_cnt),
+            _ => (&mut crl_apb.pl3_thr_ctrl, &mut crl_apb.pl3_thr_cnt),
+        }
+    }
+
+    fn gated_clock(&self) -> GatedClock {
+        match self.index {
+            0 => GatedClock::Pl0,
+            1 => GatedClock::Pl1,
+            2 => GatedClock::Pl2,
+            _ => GatedClock::Pl3,
+        }
+    }
+
+    /// Current output frequency in Hz
+    pub fn freq(&self) -> u32 {
+        let ctrl = self.clk_ctrl(crl_apb::RegisterBlock::crl_apb()).read();
+        let source_freq = Clocks::get().io_clk_source_freq(ctrl.srcsel());
+        // a divisor of 0 (the reset value) divides by 1
+        let divisor0 = u32::from(ctrl.divisor0()).max(1);
+        let divisor1 = u32::from(ctrl.divisor1()).max(1);
+        source_freq / (divisor0 * divisor1)
+    }
+
+    /// Set the clock as close as possible to `target_freq` (Hz) from `source`,
+    /// returning the resulting frequency. Doesn't change whether the clock is running.
+    pub fn set_freq(&mut self, source: IoClkSource, target_freq: u32) -> Result<u32, ClockError> {
+        let source_freq = Clocks::get().io_clk_source_freq(source);
+        if target_freq == 0 || target_freq > source_freq {
+            return Err(ClockError::DivisorOutOfRange(target_freq));
+        }
+        let mut best: Option<(u32, u32, u32)> = None;
+        for divisor0 in 1..=PL_DIVISOR_MAX {
+            for divisor1 in 1..=PL_DIVISOR_MAX {
+                let freq = source_freq / (divisor0 * divisor1);
+                let error = freq.abs_diff(target_freq);
+                if best.map_or(true, |(_, _, best_error)| error < best_error) {
+                    best = Some((divisor0, divisor1, error));
+                }
+            }
+        }
+        // a frequency below source_freq / 63^2 can't be reached
+        let (divisor0, divisor1, _) = best.unwrap();
+        if source_freq / (divisor0 * divisor1) > 2 * target_freq {
+            return Err(ClockError::DivisorOutOfRange(target_freq));
+        }
+
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            self.clk_ctrl(crl_apb).modify(|_, w| {
+                w.divisor1(divisor1 as u8)
+                    .divisor0(divisor0 as u8)
+                    .srcsel(source)
+            })
+        });
+        Ok(source_freq / (divisor0 * divisor1))
+    }
+
+    /// Keep the clock running while this handle holds it
+    pub fn enable(&mut self) {
+        if self.gate.is_none() {
+            self.gate = Some(ClockGate::acquire(self.gated_clock()));
+        }
+    }
+
+    /// Release the clock; it stops unless held elsewhere
+    pub fn disable(&mut self) {
+        self.gate = None;
+    }
+
+    /// Emit exactly `count` clock pulses and stop, for single-stepping PL logic
+    ///
+    /// The clock must be enabled. Blocks until the throttle counter has finished.
+    pub fn pulse(&mut self, count: u16) -> Result<(), ClockError> {
+        if count == 0 {
+            return Err(ClockError::NoPulses);
+        }
+        let freq = self.freq();
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            let (thr_ctrl, thr_cnt) = self.thr_regs(crl_apb);
+            thr_cnt.write(crl_apb::PlThrCnt::zeroed().last_cnt(count));
+            thr_ctrl.write(crl_apb::PlThrCtrl::zeroed().cnt_rst(true));
+            thr_ctrl.write(crl_apb::PlThrCtrl::zeroed());
+            thr_ctrl.write(crl_apb::PlThrCtrl::zeroed().cpu_start(true));
+        });
+
+        // every poll takes at least one APU cycle; allow twice the expected duration
+        let apu = u64::from(Clocks::get().apu);
+        let timeout = 2 * u64::from(count) * apu / u64::from(freq.max(1)) + 1;
+        let mut polls = 0;
+        let mut result = Ok(());
+        while self.throttle_running() {
+            polls += 1;
+            if polls > timeout {
+                result = Err(ClockError::ThrottleTimeout(self.index as u8));
+                break;
+            }
+        }
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            self.thr_regs(crl_apb).0.write(crl_apb::PlThrCtrl::zeroed());
+        });
+        result
+    }
+
+    fn throttle_running(&self) -> bool {
+        let (thr_ctrl, _) = self.thr_regs(crl_apb::RegisterBlock::crl_apb());
+        thr_ctrl.read().running()
+    }
+
+    /// Pulses emitted by the throttle counter since it was last started
+    pub fn pulses_emitted(&self) -> u16 {
+        let (thr_ctrl, _) = self.thr_regs(crl_apb::RegisterBlock::crl_apb());
+        thr_ctrl.read().curr_val()
+    }
+
+    /// Return to free-running output after [pulse](Self::pulse)
+    pub fn free_run(&mut self) {
+        crl_apb::RegisterBlock::unlocked(|crl_apb| {
+            let (thr_ctrl, thr_cnt) = self.thr_regs(crl_apb);
+            // LAST_CNT = 0: no pulse limit
+            thr_cnt.write(crl_apb::PlThrCnt::zeroed());
+            thr_ctrl.write(crl_apb::PlThrCtrl::zeroed().cnt_rst(true));
+            thr_ctrl.write(crl_apb::PlThrCtrl::zeroed());
+        });
+    }
+}
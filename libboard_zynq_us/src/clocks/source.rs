@@ -4,27 +4,60 @@
 // Original authors: Astro, Harry Ho, Sebastien Bourdeauducq
 // Modifications made for different clock sources, FDIV params, PLL configuration, and SLCRs
 
-use crate::slcr::common::{PllCfg, PllCtrl, PllFracCfg, Unlocked};
+use super::ClockError;
+use crate::board::{Board, Target};
+use crate::slcr::common::{PllCfg, PllCtrl, PllFracCfg, PllSource, Unlocked};
 use crate::slcr::{
     crf_apb,
     crl_apb::{self, PsSysmonClkCtrl, RpuClkSource},
 };
 use libregister::{RegisterR, RegisterRW, RegisterW};
 
-#[cfg(feature = "target_zcu111")]
-pub const PS_REF_CLK: u32 = 33_333_000;
-// ALT_REF_CLK: MIO pin 28 or 51, pins used otherwise on ZCU111
-// VIDEO_REF_CLK: MIO pin 27 or 50, pins used otherwise on ZCU111
-// AUX_REF_CLK: from PL
-// GTR_REF_CLK
+/// Frequencies of the PLL reference inputs in Hz (`None` if the input isn't driven on the board)
+#[derive(Debug, Clone, Copy)]
+pub struct RefClocks {
+    /// PS_REF_CLK: dedicated pin, always present
+    pub ps_ref_clk: u32,
+    /// VIDEO_REF_CLK: MIO pin 27 or 50
+    pub video_ref_clk: Option<u32>,
+    /// ALT_REF_CLK: MIO pin 28 or 51
+    pub alt_ref_clk: Option<u32>,
+    /// AUX_REF_CLK: from the PL
+    pub aux_ref_clk: Option<u32>,
+    /// GT_REF_CLK: from the PS-GTR transceivers
+    pub gt_ref_clk: Option<u32>,
+}
+
+impl RefClocks {
+    /// Get the frequency of `source`, if it is available and not 0
+    pub fn freq(&self, source: PllSource) -> Option<u32> {
+        match source {
+            PllSource::PsRefClk => Some(self.ps_ref_clk),
+            PllSource::VideoRefClk => self.video_ref_clk,
+            PllSource::AltRefClk => self.alt_ref_clk,
+            PllSource::AuxRefClk => self.aux_ref_clk,
+            PllSource::GtRefClk => self.gt_ref_clk,
+        }
+        .filter(|&freq| freq > 0)
+    }
+}
+
+pub const REF_CLOCKS: RefClocks = Target::REF_CLOCKS;
 
 // DS926 Table 36: PS PLL Switching Characteristics (same for both speed grades)
-// const PS_PLL_MAX_LOCK_TIME: f32 = 100e-6; // 100 us
+const PS_PLL_MAX_LOCK_TIME_NS: u32 = 100_000; // 100 us
 const PS_PLL_MAX_OUT_FREQ: u32 = 1_600_000_000;
 const PS_PLL_MIN_OUT_FREQ: u32 = 750_000_000;
-// const PS_PLL_MAX_VCO_FREQ: u32 = 3_000_000_000;
+const PS_PLL_MAX_VCO_FREQ: u32 = 3_000_000_000;
 const PS_PLL_MIN_VCO_FREQ: u32 = 1_500_000_000;
 
+// Number of lock status polls before giving up on a reset cycle. Each poll is a register read
+// over the APB, which takes well over 1 ns even with the APU at full speed, so this waits for at
+// least PS_PLL_MAX_LOCK_TIME_NS.
+const PLL_LOCK_TIMEOUT: u32 = PS_PLL_MAX_LOCK_TIME_NS;
+// Number of reset cycles to attempt before reporting a lock failure
+const PLL_LOCK_RETRIES: u8 = 3;
+
 // Default PLL frequencies
 pub const RPU_PLL_FREQ: u32 = 1_000_000_000;
 pub const IO_PLL_FREQ: u32 = 1_500_000_000;
@@ -32,7 +65,7 @@ pub const APU_PLL_FREQ: u32 = 1_200_000_000;
 pub const DDR_PLL_FREQ: u32 = 1_066_666_666;
 pub const VIDEO_PLL_FREQ: u32 = 1_500_000_000;
 
-pub fn init_plls() {
+pub fn init_plls() -> Result<(), ClockError> {
     crl_apb::RegisterBlock::unlocked(|crl_apb| {
         crl_apb.ps_sysmon_clk_ctrl.write(
             PsSysmonClkCtrl::zeroed()
@@ -43,18 +76,22 @@ pub fn init_plls() {
         );
         crl_apb.peri_rst_ctrl.modify(|_, w| w.qspi_rst(true));
     });
-    RpuPll::setup(RPU_PLL_FREQ);
+    RpuPll::setup(PllSource::PsRefClk, PllSource::PsRefClk, RPU_PLL_FREQ)?;
     crl_apb::RegisterBlock::unlocked(|crl_apb| {
         crl_apb
             .ps_sysmon_clk_ctrl
             .modify(|_, w| w.srcsel(RpuClkSource::RpuPll));
     });
-    IoPll::setup(IO_PLL_FREQ);
-    ApuPll::setup(APU_PLL_FREQ);
-    DdrPll::setup(DDR_PLL_FREQ);
-    VideoPll::setup(VIDEO_PLL_FREQ);
+    IoPll::setup(PllSource::PsRefClk, PllSource::PsRefClk, IO_PLL_FREQ)?;
+    ApuPll::setup(PllSource::PsRefClk, PllSource::PsRefClk, APU_PLL_FREQ)?;
+    DdrPll::setup(PllSource::PsRefClk, PllSource::PsRefClk, DDR_PLL_FREQ)?;
+    VideoPll::setup(PllSource::PsRefClk, PllSource::PsRefClk, VIDEO_PLL_FREQ)?;
+    Ok(())
 }
 
+// smallest feedback divider listed in PLL_FDIV_LOCK_PARAM
+const PLL_FDIV_MIN: u32 = 25;
+
 /// UG1085 table 37-1
 /// (pll_fdiv_max, (pll_cp, pll_res, lfhf, lock_dly, lock_cnt))
 const PLL_FDIV_LOCK_PARAM: &[(u8, (u8, u8, u8, u8, u16))] = &[
@@ -116,42 +153,75 @@ pub trait ClockSource<T: Unlocked> {
         bool::from(pll_frac_cfg.read().enabled())
     }
 
-    /// get configured frequency
+    /// get configured frequency, or 0 if the selected reference isn't available
     fn freq(pll_ctrl: &mut PllCtrl) -> u32 {
         // todo: take into account fractional part (if enabled)
         let ctrl = pll_ctrl.read();
-        let (mul, div) = if ctrl.pll_bypass() {
-            // todo: should technically read POST_SRC field to get src
-            (1u32, 1u32)
+        if ctrl.pll_bypass() {
+            // reference passed straight through to the output
+            REF_CLOCKS
+                .freq(PllSource::from(ctrl.pll_post_src()))
+                .unwrap_or(0)
         } else {
-            // same as above, but PRE_SRC
-            (u32::from(ctrl.pll_fdiv()), u32::from(ctrl.pll_div2()) + 1)
-        };
-        mul * PS_REF_CLK / div
+            let ref_clk = REF_CLOCKS
+                .freq(PllSource::from(ctrl.pll_pre_src()))
+                .unwrap_or(0);
+            let mul = u64::from(ctrl.pll_fdiv());
+            let div = u64::from(ctrl.pll_div2()) + 1;
+            (mul * u64::from(ref_clk) / div) as u32
+        }
     }
 
     fn name() -> &'static str;
 
+    /// Poll the lock status for up to `PLL_LOCK_TIMEOUT` reads
+    fn wait_for_lock() -> bool {
+        let mut timeout = PLL_LOCK_TIMEOUT;
+        while timeout > 0 && !Self::pll_locked() {
+            timeout -= 1;
+        }
+        Self::pll_locked()
+    }
+
     // UG1085 Chapter 37: PS Clock Subsystem
-    fn setup(target_freq: u32) {
-        assert!(target_freq >= PS_PLL_MIN_OUT_FREQ && target_freq <= PS_PLL_MAX_OUT_FREQ);
+    /// Lock the PLL to `target_freq` using `pre_src` as its reference input. `post_src` is
+    /// passed through to the output while the PLL is bypassed.
+    fn setup(pre_src: PllSource, post_src: PllSource, target_freq: u32) -> Result<(), ClockError> {
+        if !(PS_PLL_MIN_OUT_FREQ..=PS_PLL_MAX_OUT_FREQ).contains(&target_freq) {
+            return Err(ClockError::OutputOutOfRange(target_freq));
+        }
+        let ref_clk = REF_CLOCKS
+            .freq(pre_src)
+            .ok_or(ClockError::RefClkUnavailable(pre_src))?;
+        REF_CLOCKS
+            .freq(post_src)
+            .ok_or(ClockError::RefClkUnavailable(post_src))?;
         let div2 = target_freq <= PS_PLL_MIN_VCO_FREQ;
         let divisor = u32::from(div2) + 1;
-        let fdiv = (target_freq * divisor / PS_REF_CLK).min(125) as u8;
+        let fdiv = target_freq * divisor / ref_clk;
+        let vco_freq = fdiv * ref_clk;
+        if !(PS_PLL_MIN_VCO_FREQ..=PS_PLL_MAX_VCO_FREQ).contains(&vco_freq) {
+            return Err(ClockError::VcoOutOfRange(vco_freq));
+        }
+        if fdiv < PLL_FDIV_MIN {
+            return Err(ClockError::NoLockParams(fdiv));
+        }
         let (pll_cp, pll_res, lfhf, lock_dly, lock_cnt) = PLL_FDIV_LOCK_PARAM
             .iter()
-            .filter(|(fdiv_max, _)| fdiv <= *fdiv_max)
-            .nth(0)
-            .expect("PLL_FDIV_LOCK_PARAM")
-            .1
-            .clone();
+            .find(|(fdiv_max, _)| fdiv <= u32::from(*fdiv_max))
+            .ok_or(ClockError::NoLockParams(fdiv))?
+            .1;
+        // the table lookup above limits fdiv to <= 125
+        let fdiv = fdiv as u8;
 
         // debug!("Set {} to {} Hz", Self::name(), target_freq);
         T::unlocked(|slcr| {
             let (pll_ctrl, pll_cfg, _) = Self::pll_ctrl_regs(slcr);
 
-            // Write fdiv, div2
-            pll_ctrl.modify(|_, w| w.pll_fdiv(fdiv).pll_div2(div2));
+            // Bypass before changing the reference so the output stays on a valid clock
+            pll_ctrl.modify(|_, w| w.pll_post_src(post_src as u8).pll_bypass(true));
+            // Write sources, fdiv, div2
+            pll_ctrl.modify(|_, w| w.pll_pre_src(pre_src as u8).pll_fdiv(fdiv).pll_div2(div2));
             // Configure
             // no need to zero as we're writing every field
             pll_cfg.modify(|_, w| {
@@ -161,17 +231,24 @@ pub trait ClockSource<T: Unlocked> {
                     .pll_cp(pll_cp)
                     .pll_res(pll_res)
             });
-            // Bypass
-            pll_ctrl.modify(|_, w| w.pll_bypass(true));
-            // Reset
-            pll_ctrl.modify(|_, w| w.pll_reset(true));
-            pll_ctrl.modify(|_, w| w.pll_reset(false));
-            // Wait for PLL lock
-            // todo: add timeout here according to the 100 us spec?
-            while !Self::pll_locked() {}
+            // Reset and wait for PLL lock, retrying the reset cycle if it doesn't lock in time
+            let mut locked = false;
+            for _ in 0..PLL_LOCK_RETRIES {
+                pll_ctrl.modify(|_, w| w.pll_reset(true));
+                pll_ctrl.modify(|_, w| w.pll_reset(false));
+                locked = Self::wait_for_lock();
+                if locked {
+                    break;
+                }
+            }
+            if !locked {
+                // leave the PLL bypassed so downstream clocks keep running from the reference
+                return Err(ClockError::LockTimeout(Self::name()));
+            }
             // Remove bypass
             pll_ctrl.modify(|_, w| w.pll_bypass(false));
-        });
+            Ok(())
+        })
     }
 }
 
@@ -0,0 +1,261 @@
+//! Soldered LPDDR4 (JESD209-4B)
+//!
+//! LPDDR4 has no SPD EEPROM, so the board describes the devices with an [Lpddr4Device] and the
+//! timings come from the JEDEC minima. Mode registers are numbered as in the LPDDR4 spec and
+//! are mapped to the DDRC INIT and PHY MR registers by the caller.
+use super::spd::{DeviceType, GeneralConfig, Rtt};
+use super::DdrError;
+
+// JESD209-4B Table 28: upper clock frequency (MHz) of each set, RL without and with read DBI,
+// WL set A and nWR. The row index is the MR1/MR2 code.
+const LATENCIES: [(u32, u32, u32, u32, u32); 8] = [
+    (266, 6, 6, 4, 6),
+    (533, 10, 12, 6, 10),
+    (800, 14, 16, 8, 16),
+    (1066, 20, 22, 10, 20),
+    (1333, 24, 28, 12, 24),
+    (1600, 28, 32, 14, 30),
+    (1866, 32, 36, 16, 34),
+    (2133, 36, 40, 18, 40),
+];
+// tCK(avg) max, 10 MHz
+const T_CKAVG_MAX_PS: u32 = 100_000;
+// 8 banks, 1 KiB pages of x16
+const BANK_ADDR_BITS: u8 = 3;
+const COL_ADDR_BITS: u8 = 10;
+const CHANNEL_WIDTH: u8 = 16;
+// MR3 OP5-3: pull-down drive strength RZQ/6, OP0: pull-up calibration point VDDQ * 0.5
+const MR3_PDDS_RZQ6: u16 = 0b110 << 3;
+const MR3_PU_CAL: u16 = 1;
+// MR12: VREF(CA) 27.2 % of VDD2 (range 2, FSBL default)
+const MR12_VREF_CA: u16 = 0x4d;
+// initial VREF(DQ), range 2 value 0x0d like VREF(CA), refined by PHY VREF training
+const VREF_DQ_INIT: u8 = 0x0d;
+
+/// LPDDR4 devices wired to the PS, as one or two x16 channels
+///
+/// The controller drives both channels with the same commands and the PHY trains them together,
+/// so a second channel only widens the data bus: every setting, including the mode registers,
+/// applies to both.
+#[derive(Debug, Clone, Copy)]
+pub struct Lpddr4Device {
+    /// Density of one channel of one die, in megabits
+    pub channel_capacity_megabits: u16,
+    pub row_addr_bits: u8,
+    /// Dies sharing a channel
+    pub ranks: u8,
+    /// x16 channels in use, 2 for a 32 bit bus
+    pub channels: u8,
+    /// t_ck_min of the speed grade
+    pub t_ck_min_ps: u32,
+    /// DQ termination, RZQ/7 isn't available
+    pub dq_odt: Rtt,
+    /// CA termination, RZQ/7 isn't available
+    pub ca_odt: Rtt,
+}
+
+impl Lpddr4Device {
+    /// Describe the devices as a [GeneralConfig] with JEDEC minimum timings.
+    pub fn config(&self) -> Result<GeneralConfig, DdrError> {
+        odt(self.dq_odt)?;
+        odt(self.ca_odt)?;
+        // tRFCab by density per channel
+        let t_rfc_ab_ps = match self.channel_capacity_megabits {
+            0..=2048 => 130_000,
+            2049..=4096 => 180_000,
+            4097..=8192 => 280_000,
+            _ => 380_000,
+        };
+        // tRAS + tRPab
+        let t_ras_min_ps = 42_000;
+        let t_rp_min_ps = 21_000;
+        Ok(GeneralConfig {
+            device_type: DeviceType::LpDdr4,
+            bank_addr_bits: BANK_ADDR_BITS,
+            capacity_megabits: self.channel_capacity_megabits,
+            row_addr_bits: self.row_addr_bits,
+            col_addr_bits: COL_ADDR_BITS,
+            package_ranks: self.ranks,
+            device_width: CHANNEL_WIDTH,
+            bus_width: CHANNEL_WIDTH * self.channels,
+            t_ckavg_min_ps: self.t_ck_min_ps,
+            t_ckavg_max_ps: T_CKAVG_MAX_PS,
+            t_rcd_min_ps: 18_000,
+            t_rp_min_ps,
+            t_ras_min_ps,
+            t_rc_min_ps: t_ras_min_ps + t_rp_min_ps,
+            t_rfc1_min_ps: t_rfc_ab_ps,
+            // per bank refresh
+            t_rfc2_min_ps: t_rfc_ab_ps / 2,
+            t_rfc4_min_ps: t_rfc_ab_ps / 2,
+            t_faw_min_ps: 40_000,
+            t_rrd_s_min_ps: 10_000,
+            t_rrd_l_min_ps: 10_000,
+            // MR11 takes the DQ ODT from RTT_NOM and the CA ODT from RTT_PARK
+            rtt_nom: self.dq_odt,
+            rtt_park: self.ca_odt,
+            vref_dq_range_2: true,
+            vref_dq: VREF_DQ_INIT,
+            t_ck_ps: self.t_ck_min_ps,
+            ..GeneralConfig::default()
+        })
+    }
+}
+
+/// Row of [LATENCIES] for the operating clock, also the MR1 nWR and MR2 RL/WL code.
+fn latency_set(config: &GeneralConfig) -> usize {
+    LATENCIES
+        .iter()
+        .position(|&(f_max_mhz, ..)| 1_000_000 / config.t_ck_ps <= f_max_mhz)
+        .unwrap_or(LATENCIES.len() - 1)
+}
+
+/// Data rate (MT/s) at the top of the latency set the operating clock falls in.
+pub fn speed_grade(config: &GeneralConfig) -> u32 {
+    LATENCIES[latency_set(config)].0 * 2
+}
+
+pub fn read_latency_nck(config: &GeneralConfig) -> u32 {
+    let (_, rl, rl_dbi, ..) = LATENCIES[latency_set(config)];
+    if config.rd_dbi_en {
+        rl_dbi
+    } else {
+        rl
+    }
+}
+
+pub fn write_latency_nck(config: &GeneralConfig) -> u32 {
+    LATENCIES[latency_set(config)].3
+}
+
+pub fn write_recovery_nck(config: &GeneralConfig) -> u32 {
+    LATENCIES[latency_set(config)].4
+}
+
+/// DQ and CA ODT code (MR11) for `rtt`, RZQ/7 isn't available
+fn odt(rtt: Rtt) -> Result<u16, DdrError> {
+    match rtt {
+        Rtt::Disabled => Ok(0b000),
+        Rtt::Rzq1 => Ok(0b001),
+        Rtt::Rzq2 => Ok(0b010),
+        Rtt::Rzq3 => Ok(0b011),
+        Rtt::Rzq4 => Ok(0b100),
+        Rtt::Rzq5 => Ok(0b101),
+        Rtt::Rzq6 => Ok(0b110),
+        Rtt::Rzq7 => Err(DdrError::Odt(rtt)),
+    }
+}
+
+pub fn mr1(config: &GeneralConfig) -> u16 {
+    // OP6-4: nWR, OP2: 2 tCK write preamble (the only valid setting), OP1-0: BL16
+    (latency_set(config) as u16) << 4 | 1 << 2
+}
+
+pub fn mr2(config: &GeneralConfig) -> u16 {
+    // OP6: WL set A, OP5-3: WL, OP2-0: RL
+    let set = latency_set(config) as u16;
+    set << 3 | set
+}
+
+pub fn mr3(config: &GeneralConfig) -> u16 {
+    // OP7: write DBI, OP6: read DBI
+    (config.wr_dbi_en as u16) << 7 | (config.rd_dbi_en as u16) << 6 | MR3_PDDS_RZQ6 | MR3_PU_CAL
+}
+
+pub fn mr11(config: &GeneralConfig) -> Result<u16, DdrError> {
+    // OP6-4: CA ODT, OP2-0: DQ ODT
+    Ok(odt(config.rtt_park)? << 4 | odt(config.rtt_nom)?)
+}
+
+pub fn mr12(_config: &GeneralConfig) -> u16 {
+    MR12_VREF_CA
+}
+
+pub fn mr13(config: &GeneralConfig) -> u16 {
+    // OP5: data mask disable, OP3: VREF current generator on for training
+    (!config.dm_en as u16) << 5 | 1 << 3
+}
+
+pub fn mr14(config: &GeneralConfig) -> u16 {
+    // OP6: VREF(DQ) range, OP5-0: VREF(DQ)
+    (config.vref_dq_range_2 as u16) << 6 | (config.vref_dq & 0x3f) as u16
+}
+
+pub fn mr22(_config: &GeneralConfig) -> u16 {
+    // SoC ODT and CK/CS/CA ODT overrides off
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two x16 channels of 8 Gb LPDDR4-2133 per rank
+    fn device() -> Lpddr4Device {
+        Lpddr4Device {
+            channel_capacity_megabits: 8192,
+            row_addr_bits: 16,
+            ranks: 1,
+            channels: 2,
+            t_ck_min_ps: 938,
+            dq_odt: Rtt::Disabled,
+            ca_odt: Rtt::Disabled,
+        }
+    }
+
+    #[test]
+    fn general_config() {
+        let config = device().config().unwrap();
+        assert_eq!(config.device_type, DeviceType::LpDdr4);
+        assert_eq!(config.module_capacity_megabytes(), 2048);
+        assert_eq!(config.bus_width, 32);
+        assert_eq!(config.burst_len(), 16);
+        assert_eq!(config.t_rfc_min_ps(), 280_000);
+        assert_eq!(config.t_refi(), 3_904_000);
+        assert_eq!(config.speed_grade(), 2132);
+        assert_eq!(config.cas_latency_nck(), 20);
+        assert_eq!(config.cas_write_latency_nck(), 10);
+        assert_eq!(config.mr1(), 0x34);
+        assert_eq!(config.mr2(), 0x1b);
+    }
+
+    #[test]
+    fn mode_registers_533() {
+        let mut config = device().config().unwrap();
+        config.t_ck_ps = 1875;
+        assert_eq!(config.read_latency_nck(), 10);
+        assert_eq!(config.write_latency_nck(), 6);
+        assert_eq!(write_recovery_nck(&config), 10);
+        assert_eq!(config.t_zq_cs_nck(), 16);
+        assert_eq!(config.mr0(), 0);
+        assert_eq!(config.mr1(), 0x14);
+        assert_eq!(config.mr2(), 0x09);
+        assert_eq!(config.mr3(), 0x31);
+        // the FSBL values
+        assert_eq!(mr11(&config), Ok(0x00));
+        assert_eq!(mr12(&config), 0x4d);
+        assert_eq!(mr13(&config), 0x08);
+        assert_eq!(mr14(&config), 0x4d);
+
+        config.rd_dbi_en = true;
+        config.dm_en = false;
+        assert_eq!(config.read_latency_nck(), 12);
+        assert_eq!(config.mr3(), 0x71);
+        assert_eq!(mr13(&config), 0x28);
+    }
+
+    #[test]
+    fn odt_codes() {
+        let mut device = device();
+        device.dq_odt = Rtt::Rzq4;
+        device.ca_odt = Rtt::Rzq6;
+        let mut config = device.config().unwrap();
+        assert_eq!(mr11(&config), Ok(0x64));
+        // a config that didn't come from an Lpddr4Device
+        config.rtt_nom = Rtt::Rzq7;
+        assert_eq!(mr11(&config), Err(DdrError::Odt(Rtt::Rzq7)));
+
+        device.ca_odt = Rtt::Rzq7;
+        assert_eq!(device.config().err(), Some(DdrError::Odt(Rtt::Rzq7)));
+    }
+}
@@ -0,0 +1,151 @@
+//! Physical address ranges of the populated DRAM
+//!
+//! The PS DDR controller is reached through two windows of the system address map (UG1085
+//! Table 10-1): DDR_LO holds the first 2 GB of DRAM and DDR_HI the rest.
+use super::spd::GeneralConfig;
+
+const DDR_LO_BASE: usize = 0x0000_0000;
+const DDR_LO_SIZE: usize = 0x8000_0000;
+const DDR_HI_BASE: usize = 0x8_0000_0000;
+const DDR_HI_SIZE: usize = 0x8_0000_0000;
+
+// HIF addresses count words of the full 64 bit bus, narrower buses leave low column bits out
+const HIF_WORD_BYTES: usize = 8;
+
+/// Contiguous range of physical addresses backed by DRAM
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryRegion {
+    pub base: usize,
+    pub size: usize,
+}
+
+impl MemoryRegion {
+    /// First address past the region
+    pub fn end(&self) -> usize {
+        self.base + self.size
+    }
+
+    pub fn contains(&self, addr: usize) -> bool {
+        addr >= self.base && addr < self.end()
+    }
+}
+
+/// DRAM as seen by the APU: DDR_LO at 0, and DDR_HI at 32 GB if there's more than 2 GB
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryMap {
+    pub ddr_lo: MemoryRegion,
+    pub ddr_hi: Option<MemoryRegion>,
+}
+
+impl MemoryMap {
+    /// Lay out `capacity` bytes of DRAM over the DDR windows. Anything that doesn't fit in them
+    /// can't be reached by the APU and is left out.
+    pub fn new(capacity: usize) -> Self {
+        let hi_size = capacity.saturating_sub(DDR_LO_SIZE).min(DDR_HI_SIZE);
+        MemoryMap {
+            ddr_lo: MemoryRegion {
+                base: DDR_LO_BASE,
+                size: capacity.min(DDR_LO_SIZE),
+            },
+            ddr_hi: if hi_size > 0 {
+                Some(MemoryRegion {
+                    base: DDR_HI_BASE,
+                    size: hi_size,
+                })
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Memory map of the DRAM described by `config`, as far as the controller's address map
+    /// reaches it
+    pub fn from_config(config: &GeneralConfig) -> Self {
+        Self::new(HIF_WORD_BYTES << config.mapped_hif_addr_bits())
+    }
+
+    /// Populated regions in address order
+    pub fn regions(&self) -> impl Iterator<Item = MemoryRegion> {
+        core::iter::once(self.ddr_lo).chain(self.ddr_hi)
+    }
+
+    /// Total size in bytes
+    pub fn size(&self) -> usize {
+        self.regions().map(|region| region.size).sum()
+    }
+
+    pub fn contains(&self, addr: usize) -> bool {
+        self.regions().any(|region| region.contains(addr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: usize = 1024 * 1024 * 1024;
+
+    #[test]
+    fn split_over_both_windows() {
+        let map = MemoryMap::new(4 * GB);
+        assert_eq!(
+            map.regions().collect::<Vec<_>>(),
+            [
+                MemoryRegion {
+                    base: 0,
+                    size: 2 * GB,
+                },
+                MemoryRegion {
+                    base: 0x8_0000_0000,
+                    size: 2 * GB,
+                },
+            ]
+        );
+        assert!(map.contains(0x7fff_ffff));
+        assert!(!map.contains(0x8000_0000));
+        assert!(map.contains(0x8_7fff_ffff));
+        assert!(!map.contains(0x8_8000_0000));
+
+        let map = MemoryMap::new(16 * GB);
+        assert_eq!(map.size(), 16 * GB);
+        assert_eq!(map.ddr_hi.map(|region| region.size), Some(14 * GB));
+    }
+
+    #[test]
+    fn ddr_lo_only() {
+        let map = MemoryMap::new(2 * GB);
+        assert_eq!(map.ddr_lo.size, 2 * GB);
+        assert_eq!(map.ddr_hi, None);
+    }
+
+    #[test]
+    fn ddr_hi_limit() {
+        assert_eq!(MemoryMap::new(48 * GB).size(), 34 * GB);
+    }
+
+    #[test]
+    fn mapped_address_bits() {
+        // 8 Gb x16 devices on a 64 bit bus, 4 GB per rank
+        let mut config = GeneralConfig {
+            capacity_megabits: 8192,
+            col_addr_bits: 10,
+            bank_addr_bits: 2,
+            bg_addr_bits: 1,
+            row_addr_bits: 16,
+            package_ranks: 1,
+            device_width: 16,
+            bus_width: 64,
+            ..GeneralConfig::default()
+        };
+        assert_eq!(MemoryMap::from_config(&config).size(), 4 * GB);
+
+        // only rank 0 is addressed
+        config.package_ranks = 2;
+        assert_eq!(config.module_capacity_megabytes(), 8192);
+        assert_eq!(MemoryMap::from_config(&config).size(), 4 * GB);
+
+        // the narrower bus drops one column bit from the HIF address
+        config.bus_width = 32;
+        assert_eq!(MemoryMap::from_config(&config).size(), 2 * GB);
+    }
+}
@@ -1,21 +1,138 @@
 //! DDR initialization and configuration
+use core::fmt;
+
 use libm::ceilf;
-use libregister::{RegisterRW, RegisterW};
+use libregister::{RegisterR, RegisterRW, RegisterW};
+use log::{debug, info};
 
-use super::slcr::{common::Unlocked, crf_apb};
+use super::board::{Board, Dram, Target};
+use super::clocks::Clocks;
+use super::reset::Reset;
 use super::{print, println};
+pub mod lpddr4;
+mod memory_map;
 pub mod phy;
 pub mod regs;
 pub mod spd;
 
-#[cfg(feature = "target_zcu111")]
-// Micron MTA4ATF51264HZ-2G6E1, DDR4, max data rate 2666 MHz
-// But US+ only supports up to 2133 MHz for a single-rank DIMM
-pub const DDR_FREQ: u32 = 2_133_333_333;
+pub use memory_map::{MemoryMap, MemoryRegion};
+
+pub const DDR_FREQ: u32 = Target::DDR_FREQ;
+
+const DEFAULT_TIMEOUT: u32 = 1_000_000; // arbitrary
+
+// idle controller clocks (x32) before entering power down and self refresh
+const POWERDOWN_TO_X32: u8 = 0x10;
+const SELF_REF_TO_X32: u8 = 0x40;
+// interval between ZQCS commands
+const ZQ_SHORT_INTERVAL_US: u32 = 128_000;
+// DFI timing of the DDR PHY, in DRAM clocks (dfi_*_use_sdr)
+const DFI_T_CTRL_DELAY: u8 = 4;
+const DFI_TPHY_WRDATA: u8 = 2;
+const DFI_T_WRDATA_DELAY: u8 = 3;
+const DFI_T_DRAM_CLK_DISABLE: u8 = 3;
+const DFI_T_DRAM_CLK_ENABLE: u8 = 4;
+// controller-initiated update window, in controller clocks
+const DFI_T_CTRLUP_MIN: u16 = 0x18;
+const DFI_T_CTRLUP_MAX: u16 = 0x40;
+// memtest leaves the first MB of DDR_LO alone, so it never forms a null pointer
+const MEMTEST_SKIP: usize = 0x0010_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DdrError {
+    /// SPD EEPROM unreadable or its contents invalid
+    Spd(spd::SpdError),
+    /// DRAM clock (MHz) is outside the range supported by the module
+    ClockOutOfRange(u32),
+    /// Device width (bits) the controller doesn't support
+    DeviceWidth(u8),
+    /// Burst length the controller doesn't support
+    BurstLength(u8),
+    /// Data bus width (bits) the controller doesn't support
+    BusWidth(u8),
+    /// Termination the DRAM doesn't support
+    Odt(spd::Rtt),
+    /// PHY PLLs failed to lock after all retries
+    PllLock,
+    /// Named init step didn't finish
+    Timeout(&'static str),
+    /// PHY reported training errors; raw PGSR0
+    Training(u32),
+    /// Controller didn't reach normal operating mode
+    OperatingMode(regs::ControllerStatus),
+}
+
+/// Bring up the board's DRAM, described by the SPD EEPROM or the board itself
+pub fn init(clocks: &Clocks) -> Result<DdrRam, DdrError> {
+    let config = match Target::DRAM {
+        Dram::Spd(spd) => {
+            let config = spd::read_spd_eeprom(&spd).map_err(DdrError::Spd)?;
+            print_module_info(&config.module_info);
+            config
+        }
+        Dram::Lpddr4(device) => device.config()?,
+    };
+    debug!("DRAM config: {:?}", config);
+    init_with_config(clocks, config)
+}
+
+/// JEP-106 manufacturer name, or the raw ID if it isn't in the table
+struct Manufacturer(u16);
+
+impl fmt::Display for Manufacturer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match spd::jep106::manufacturer(self.0) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "manufacturer {:#06x}", self.0),
+        }
+    }
+}
+
+fn print_module_info(module_info: &spd::ModuleInfo) {
+    info!(
+        "DIMM: {} {} rev {:#x}, serial {:08x}",
+        Manufacturer(module_info.module_manufacturer_id),
+        module_info.part_number(),
+        module_info.revision,
+        module_info.serial_number
+    );
+    info!(
+        "DIMM: made in week {} of {} at location {:#x}, {} DRAM stepping {:#x}",
+        module_info.week,
+        module_info.year,
+        module_info.location,
+        Manufacturer(module_info.dram_manufacturer_id),
+        module_info.dram_stepping
+    );
+}
+
+/// Bring up the DRAM described by `config`, in the order of UG1085 Ch. 17 "DDR Memory
+/// Controller Initialization"
+pub fn init_with_config(
+    clocks: &Clocks,
+    mut config: spd::GeneralConfig,
+) -> Result<DdrRam, DdrError> {
+    let ck_freq = u64::from(clocks.ddr_ctl_clk()) * 2;
+    let t_ck_ps = 1_000_000_000_000_u64.div_ceil(ck_freq.max(1)) as u32;
+    if t_ck_ps < config.t_ckavg_min_ps || t_ck_ps > config.t_ckavg_max_ps {
+        return Err(DdrError::ClockOutOfRange((ck_freq / 1_000_000) as u32));
+    }
+    config.t_ck_ps = t_ck_ps;
+
+    let mut ddr = DdrRam::ddr_ram();
+    ddr.configure(&config)?;
+    ddr.phy.configure(&config)?;
+    ddr.phy.init_plls()?;
+    ddr.phy.execute_zcal_dcal()?;
+    ddr.start()?;
+    ddr.phy.execute_training(&config)?;
+    Ok(ddr)
+}
 
 pub struct DdrRam {
     regs: &'static mut regs::RegisterBlock,
     phy: phy::DdrPhy,
+    memory_map: MemoryMap,
 }
 
 impl DdrRam {
@@ -23,43 +140,92 @@ impl DdrRam {
         let self_ = DdrRam {
             regs: regs::RegisterBlock::ddrc(),
             phy: phy::DdrPhy::ddr_phy(),
+            // nothing is populated until configured
+            memory_map: MemoryMap::new(0),
         };
         self_
     }
 
-    pub fn configure(&mut self, config: spd::GeneralConfig) {
+    /// Physical address ranges of the DRAM, for the allocator, MMU tables and memtest
+    pub fn memory_map(&self) -> MemoryMap {
+        self.memory_map
+    }
+
+    pub fn configure(&mut self, config: &spd::GeneralConfig) -> Result<(), DdrError> {
+        self.memory_map = MemoryMap::from_config(config);
+
         // assert DDRC reset
-        crf_apb::RegisterBlock::unlocked(|crf_apb| {
-            crf_apb.rst_ddr_ss.modify(|_, w| w.ddr_reset(true))
-        });
+        Reset::Ddr.assert();
 
-        self.write_regs(config);
+        self.write_regs(config)?;
 
         // bring controller out of reset
-        crf_apb::RegisterBlock::unlocked(|crf_apb| {
-            crf_apb.rst_ddr_ss.modify(|_, w| w.ddr_reset(false))
-        });
+        Reset::Ddr.deassert();
+        Ok(())
+    }
+
+    /// Hand over to the controller once the PHY has signalled DFI init complete, and wait for
+    /// it to finish DRAM init
+    pub fn start(&mut self) -> Result<(), DdrError> {
+        let mut timeout = DEFAULT_TIMEOUT;
+        while !self.regs.dfi_status.read().dfi_init_complete() {
+            if timeout == 0 {
+                return Err(DdrError::Timeout("DFI init"));
+            }
+            timeout -= 1;
+        }
+
+        // dfi_misc is quasi-dynamic
+        self.regs
+            .sw_ctrl
+            .write(regs::SwCtrl::zeroed().sw_done(false));
+        self.regs
+            .dfi_misc
+            .modify(|_, w| w.dfi_init_complete_en(true));
+        self.regs
+            .sw_ctrl
+            .write(regs::SwCtrl::zeroed().sw_done(true));
+        timeout = DEFAULT_TIMEOUT;
+        while !self.regs.sw_status.read().sw_done_ack() {
+            if timeout == 0 {
+                return Err(DdrError::Timeout("register update"));
+            }
+            timeout -= 1;
+        }
+
+        timeout = DEFAULT_TIMEOUT;
+        loop {
+            let operating_mode = self.regs.status.read().operating_mode();
+            if operating_mode == regs::ControllerStatus::Normal {
+                return Ok(());
+            }
+            if timeout == 0 {
+                return Err(DdrError::OperatingMode(operating_mode));
+            }
+            timeout -= 1;
+        }
     }
 
-    pub fn write_regs(&mut self, config: spd::GeneralConfig) {
+    pub fn write_regs(&mut self, config: &spd::GeneralConfig) -> Result<(), DdrError> {
         // master
         let device_config = match config.device_width {
+            4 => regs::DeviceConfig::X4,
             8 => regs::DeviceConfig::X8,
             16 => regs::DeviceConfig::X16,
             32 => regs::DeviceConfig::X32,
-            _ => panic!("Invalid device width"),
+            width => return Err(DdrError::DeviceWidth(width)),
         };
         let burst_rdwr = match config.burst_len() {
             4 => regs::BurstRdwr::Len4,
             8 => regs::BurstRdwr::Len8,
             16 => regs::BurstRdwr::Len16,
-            _ => panic!("Invalid burst length"),
+            len => return Err(DdrError::BurstLength(len)),
         };
         let bus_width = match config.bus_width {
             16 => regs::DataBusWidth::Quarter,
             32 => regs::DataBusWidth::Half,
             64 => regs::DataBusWidth::Full,
-            _ => panic!("Invalid bus width"),
+            width => return Err(DdrError::BusWidth(width)),
         };
         self.regs.master.write(
             regs::Master::zeroed()
@@ -87,7 +253,13 @@ impl DdrRam {
                 .self_ref_en(config.self_ref_en),
         );
 
-        // TODO: power_timing if enabled
+        // power_timing: only used if power down or self refresh is enabled
+        self.regs.power_timing.write(
+            regs::PowerTiming::zeroed()
+                .self_ref_to_x32(SELF_REF_TO_X32)
+                .powerdown_to_x32(POWERDOWN_TO_X32),
+        );
+
         // refresh_ctrl1
         // extra division by 2 since that's what the registers want
         let t_refi_x32 = (config.ps_to_nck(config.t_refi()) / 64) as u16;
@@ -156,18 +328,56 @@ impl DdrRam {
         self.regs
             .init1
             .write(regs::Init1::zeroed().dram_rstn_x1024(dram_rstn_x1024));
+        // the INIT registers hold different mode registers for LPDDR4, see regs.rs
+        let [mr, emr, emr2, emr3, mr4, mr5, mr6, mr22] = match config.device_type {
+            spd::DeviceType::LpDdr4 => [
+                lpddr4::mr1(config),
+                lpddr4::mr2(config),
+                lpddr4::mr3(config),
+                lpddr4::mr13(config),
+                lpddr4::mr11(config)?,
+                lpddr4::mr12(config),
+                lpddr4::mr14(config),
+                lpddr4::mr22(config),
+            ],
+            _ => [
+                config.mr0(),
+                config.mr1(),
+                config.mr2(),
+                config.mr3(),
+                config.mr4(),
+                config.mr5(),
+                config.mr6(),
+                0,
+            ],
+        };
+        self.regs.init3.write(regs::Init3::zeroed().mr(mr).emr(emr));
         self.regs
-            .init3
-            .write(regs::Init3::zeroed().mr(config.mr0()));
+            .init4
+            .write(regs::Init4::zeroed().emr2(emr2).emr3(emr3));
+        let dev_zqinit_x32 = (config.t_zq_init_nck() / 32).div_ceil(2) as u8 + 1;
+        self.regs
+            .init5
+            .write(regs::Init5::zeroed().dev_zqinit_x32(dev_zqinit_x32));
+        self.regs
+            .init6
+            .write(regs::Init6::zeroed().mr4(mr4).mr5(mr5));
+        self.regs
+            .init7
+            .write(regs::Init7::zeroed().mr6(mr6).mr22(mr22));
 
-        // dimm_ctrl
-        let dimm_addr_mirr_en = match config.module_config {
-            spd::ModuleConfig::Unbuffered(mod_config) => mod_config.rank_1_mirrored,
-            _ => false,
-        };
+        // keep the controller off the DFI until the PHY is initialized
         self.regs
-            .dimm_ctrl
-            .write(regs::DimmCtrl::zeroed().dimm_addr_mirr_en(dimm_addr_mirr_en));
+            .dfi_misc
+            .write(regs::DfiMisc::zeroed().dfi_init_complete_en(false));
+
+        // dimm_ctrl
+        // the RCD inverts the B side outputs by default (RC00), so MRS are sent to both sides
+        self.regs.dimm_ctrl.write(
+            regs::DimmCtrl::zeroed()
+                .dimm_output_inv_en(config.rcd().is_some())
+                .dimm_addr_mirr_en(config.rank_1_mirrored()),
+        );
 
         // dram_tmg0
         let t_faw = config.ps_to_nck(config.t_faw_min_ps).div_ceil(2) as u8;
@@ -191,10 +401,22 @@ impl DdrRam {
                 .read_latency(read_latency),
         );
 
+        // dram_tmg3
+        let t_mod = config.t_mod_nck().div_ceil(2) as u16;
+        let t_mrd = config.ps_to_nck(10_000).max(8).div_ceil(2) as u8;
+        // LPDDR: max(10 nCK, 10 ns)
+        let t_mrw = config.ps_to_nck(10_000).max(10).div_ceil(2) as u16;
+        self.regs.dram_tmg3.write(
+            regs::DramTmg3::zeroed()
+                .t_mrw(t_mrw)
+                .t_mrd(t_mrd)
+                .t_mod(t_mod),
+        );
+
         // dram_tmg4
         let t_rcd = (config.ps_to_nck(config.t_rcd_min_ps) - config.additive_latency_nck())
             .div_ceil(2) as u8;
-        let t_ccd = config.ps_to_nck(config.t_ccd_l_min_ps).div_ceil(2) as u8;
+        let t_ccd = config.t_ccd_l_nck().div_ceil(2) as u8;
         let t_rrd = config.ps_to_nck(config.t_rrd_l_min_ps).div_ceil(2) as u8;
         let t_rp = (config.t_rp_min_ps.div_ceil(config.t_ckavg_min_ps) / 2 + 1) as u8;
         self.regs.dram_tmg4.write(
@@ -205,6 +427,30 @@ impl DdrRam {
                 .t_rp(t_rp),
         );
 
+        // dram_tmg5
+        let t_cksrx = config.t_cksrx_nck().div_ceil(2) as u8;
+        let t_cksre = config.t_cksre_nck().div_ceil(2) as u8;
+        let t_ckesr = config.t_ckesr_nck().div_ceil(2) as u8;
+        let t_cke = config.t_cke_nck().div_ceil(2) as u8;
+        self.regs.dram_tmg5.write(
+            regs::DramTmg5::zeroed()
+                .t_cksrx(t_cksrx)
+                .t_cksre(t_cksre)
+                .t_ckesr(t_ckesr)
+                .t_cke(t_cke),
+        );
+
+        // dram_tmg6 (LPDDR clock stop exit)
+        let t_ckcsx = (config.t_xp_nck() + 2).div_ceil(2) as u8;
+        self.regs
+            .dram_tmg6
+            .write(regs::DramTmg6::zeroed().t_ckcsx(t_ckcsx));
+
+        // dram_tmg7: clock is only stopped in self refresh for DDR4
+        self.regs
+            .dram_tmg7
+            .write(regs::DramTmg7::zeroed().t_ckpdx(t_cksrx).t_ckpde(t_cksre));
+
         // dram_tmg8
         let t_xs_fast_x32 =
             (config.ps_to_nck(config.t_xs_fast_min_ns() * 1000) / 32).div_ceil(2) as u8;
@@ -220,7 +466,33 @@ impl DdrRam {
                 .t_xs_x32(t_xs_x32),
         );
 
-        // TODO: dram_tmg9 (wr preamble = 0?)
+        // dram_tmg9
+        let wr2rd_s = (config.cas_write_latency_nck()
+            + config.parity_latency_nck()
+            + config.burst_len() as u32 / 2
+            + config.t_wtr_s_nck())
+        .div_ceil(2) as u8;
+        let t_rrd_s = config.ps_to_nck(config.t_rrd_s_min_ps).max(4).div_ceil(2) as u8;
+        self.regs.dram_tmg9.write(
+            regs::DramTmg9::zeroed()
+                .ddr4_wr_preamble(config.wr_preamble_2nck())
+                // tCCD_S = 4 nCK
+                .t_ccd_s(2)
+                .t_rrd_s(t_rrd_s)
+                .wr2rd_s(wr2rd_s),
+        );
+
+        // dram_tmg11: tCKMPE = tMOD + tCPDED, tXMPDLL = tXMP + tXSDLL with tXMP = tXS
+        let t_ckmpe = (config.t_mod_nck() + 4).div_ceil(2) as u8;
+        let t_mpx_lh = config.ps_to_nck(12_000).div_ceil(2) as u8;
+        let t_xmp_dll = config.ps_to_nck(config.t_xs_min_ns() * 1000) + config.t_xs_dll_min_nck();
+        self.regs.dram_tmg11.write(
+            regs::DramTmg11::zeroed()
+                .post_mpsm_gap_x32((t_xmp_dll / 32).div_ceil(2) as u8)
+                .t_mpx_lh(t_mpx_lh)
+                .t_mpx_s(1)
+                .t_ckmpe(t_ckmpe),
+        );
 
         // dram_tmg12
         let t_mrd_pda = config.t_mrd_pda_min_nck().div_ceil(2) as u8;
@@ -228,6 +500,95 @@ impl DdrRam {
             .dram_tmg12
             .write(regs::DramTmg12::zeroed().t_mrd_pda(t_mrd_pda));
 
+        // dram_tmg13: LPDDR4 tCCDMW = 32 nCK, tPPD = 4 nCK
+        self.regs
+            .dram_tmg13
+            .write(regs::DramTmg13::zeroed().t_ccd_mw(16).t_ppd(2));
+
+        // dram_tmg14 (LPDDR tXSR)
+        let t_xsr = config.ps_to_nck(config.t_xs_min_ns() * 1000).div_ceil(2) as u16;
+        self.regs
+            .dram_tmg14
+            .write(regs::DramTmg14::zeroed().t_xsr(t_xsr));
+
+        // zq_ctrl0/1
+        self.regs.zq_ctrl0.write(
+            regs::ZqCtrl0::zeroed()
+                .t_zq_long_nop(config.t_zq_oper_nck().div_ceil(2) as u16)
+                .t_zq_short_nop(config.t_zq_cs_nck().div_ceil(2) as u16),
+        );
+        let t_zq_short_interval_x1024 = config.ctl_clock_mhz() * ZQ_SHORT_INTERVAL_US / 1024;
+        // LPDDR4 tZQRESET: max(3 nCK, 50 ns)
+        let t_zq_reset_nop = config.ps_to_nck(50_000).max(3).div_ceil(2) as u16;
+        self.regs.zq_ctrl1.write(
+            regs::ZqCtrl1::zeroed()
+                .t_zq_reset_nop(t_zq_reset_nop)
+                .t_zq_short_interval_x1024(t_zq_short_interval_x1024),
+        );
+
+        // dfi_tmg0/1
+        let dfi_t_rddata_en = config.read_latency_nck().saturating_sub(4) as u8;
+        let dfi_tphy_wrlat = config.write_latency_nck().saturating_sub(3) as u8;
+        self.regs.dfi_tmg0.write(
+            regs::DfiTmg0::zeroed()
+                .dfi_t_ctrl_delay(DFI_T_CTRL_DELAY)
+                .dfi_rddata_use_sdr(true)
+                .dfi_t_rddata_en(dfi_t_rddata_en)
+                .dfi_wrdata_use_sdr(true)
+                .dfi_tphy_wrdata(DFI_TPHY_WRDATA)
+                .dfi_tphy_wrlat(dfi_tphy_wrlat),
+        );
+        self.regs.dfi_tmg1.write(
+            regs::DfiTmg1::zeroed()
+                .dfi_t_wrdata_delay(DFI_T_WRDATA_DELAY)
+                .dfi_t_dram_clk_disable(DFI_T_DRAM_CLK_DISABLE)
+                .dfi_t_dram_clk_enable(DFI_T_DRAM_CLK_ENABLE),
+        );
+
+        // dfi_update0-2: the PHY requests its own VT drift updates
+        self.regs.dfi_update0.write(
+            regs::DfiUpdate0::zeroed()
+                .dis_auto_ctrlupd(true)
+                .dis_auto_ctrlupd_srx(true)
+                .ctrlupd_pre_srx(true)
+                .dfi_t_ctrlup_max(DFI_T_CTRLUP_MAX)
+                .dfi_t_ctrlup_min(DFI_T_CTRLUP_MIN),
+        );
+        // unused with dis_auto_ctrlupd, but min must not exceed max
+        self.regs.dfi_update1.write(
+            regs::DfiUpdate1::zeroed()
+                .dfi_t_ctrlupd_interval_min_x1024(0xdf)
+                .dfi_t_ctrlupd_interval_max_x1024(0xe0),
+        );
+        self.regs
+            .dfi_update2
+            .write(regs::DfiUpdate2::zeroed().dfi_phyupd_en(true));
+
+        // odt_cfg
+        let wr_preamble = 1 + config.wr_preamble_2nck() as u32;
+        let rd_preamble = 1 + config.rd_preamble_2nck as u32;
+        let rd_odt_delay = (config.cas_latency_nck() + wr_preamble)
+            .saturating_sub(config.cas_write_latency_nck() + rd_preamble);
+        self.regs.odt_cfg.write(
+            regs::OdtCfg::zeroed()
+                .wr_odt_hold((5 + wr_preamble + config.crc_en as u32) as u8)
+                .wr_odt_delay(0)
+                .rd_odt_hold((5 + rd_preamble) as u8)
+                .rd_odt_delay(rd_odt_delay as u8),
+        );
+
+        // odt_map: terminate writes at the target rank, reads at the other one
+        let odt_map = if config.logical_ranks() > 1 {
+            regs::OdtMap::zeroed()
+                .rank1_rd_odt(0b01)
+                .rank1_wr_odt(0b10)
+                .rank0_rd_odt(0b10)
+                .rank0_wr_odt(0b01)
+        } else {
+            regs::OdtMap::zeroed().rank0_wr_odt(0b01)
+        };
+        self.regs.odt_map.write(odt_map);
+
         // address map
         self.regs
             .addr_map0
@@ -337,54 +698,54 @@ impl DdrRam {
                 .dq_nibble_map_cb_4_7(config.dq_map[9])
                 .dq_nibble_map_cb_0_3(config.dq_map[8]),
         );
+        Ok(())
     }
 
-    pub fn ptr<T>(&mut self) -> *mut T {
-        // TODO: starts at zero but runtime checks for null pointers don't like that
-        0x0010_0000 as *mut _
+    pub fn memtest(&mut self) {
+        for region in self.memory_map.regions() {
+            let base = region.base.max(MEMTEST_SKIP);
+            if base >= region.end() {
+                continue;
+            }
+            println!("memtest {:#X}-{:#X}", base, region.end() - 1);
+            let slice = unsafe {
+                core::slice::from_raw_parts_mut(base as *mut u32, (region.end() - base) / 4)
+            };
+            memtest_slice(slice);
+        }
     }
+}
 
-    pub fn size(&self) -> usize {
-        // TODO: DDR_LO is 2 GB, but there's another 2 GB (for ZCU 111) at the high addr
-        2047 * 1024 * 1024
-    }
+fn memtest_slice(slice: &mut [u32]) {
+    let patterns: &'static [u32] = &[0xffff_ffff, 0x5555_5555, 0xaaaa_aaaa, 0];
+    for (i, pattern) in patterns.iter().enumerate() {
+        println!("memtest phase {}: {:#08X}", i, pattern);
 
-    pub fn memtest(&mut self) {
-        let slice = unsafe { core::slice::from_raw_parts_mut(self.ptr(), self.size()) };
-        let patterns: &'static [u32] = &[0xffff_ffff, 0x5555_5555, 0xaaaa_aaaa, 0];
-        for (i, pattern) in patterns.iter().enumerate() {
-            println!("memtest phase {}: {:#08X}", i, pattern);
-
-            println!("writing...");
-            for megabyte in 0..slice.len() / (1024 * 1024) {
-                let start = megabyte * 1024 * 1024 / 4;
-                let end = (megabyte + 1) * 1024 * 1024 / 4;
-                for b in slice[start..end].iter_mut() {
-                    *b = *pattern;
-                }
-
-                print!("\r{} MB", megabyte);
+        println!("writing...");
+        for (megabyte, chunk) in slice.chunks_mut(1024 * 1024 / 4).enumerate() {
+            for b in chunk.iter_mut() {
+                *b = *pattern;
             }
-            println!(" Ok");
-
-            println!("reading...");
-            let expected = *pattern;
-            for megabyte in 0..slice.len() / (1024 * 1024) {
-                let start = megabyte * 1024 * 1024 / 4;
-                let end = (megabyte + 1) * 1024 * 1024 / 4;
-                for b in slice[start..end].iter_mut() {
-                    let read: u32 = *b;
-                    if read != expected {
-                        println!(
-                            "{:08X}: expected {:08X}, read {:08X}",
-                            b as *mut _ as usize, expected, read
-                        );
-                    };
-                }
-
-                print!("\r{} MB", megabyte);
+
+            print!("\r{} MB", megabyte);
+        }
+        println!(" Ok");
+
+        println!("reading...");
+        let expected = *pattern;
+        for (megabyte, chunk) in slice.chunks_mut(1024 * 1024 / 4).enumerate() {
+            for b in chunk.iter_mut() {
+                let read: u32 = *b;
+                if read != expected {
+                    println!(
+                        "{:08X}: expected {:08X}, read {:08X}",
+                        b as *mut _ as usize, expected, read
+                    );
+                };
             }
-            println!(" Ok");
+
+            print!("\r{} MB", megabyte);
         }
+        println!(" Ok");
     }
 }
@@ -1,4 +1,7 @@
-use libregister::{RegisterR, RegisterW};
+use libregister::{RegisterR, RegisterRW, RegisterW};
+
+use super::{lpddr4, spd, DdrError};
+use crate::board::{Board, Target};
 
 mod regs;
 
@@ -8,6 +11,69 @@ pub struct DdrPhy {
 
 const DEFAULT_TIMEOUT: u32 = 1_000_000; // arbitrary
 
+// byte lanes DX0-7 carry data, DX8 the ECC byte
+const NUM_DATA_LANES: usize = 8;
+const ECC_LANE: usize = 8;
+// DDR4 fixed timings (JESD 79-4 Table 132)
+const T_MRD_NCK: u8 = 8;
+const T_WLMRD_NCK: u8 = 40;
+// FSBL defaults: ODT turn-on/off delays, read system delay and ODT hold
+const T_OFDX_NCK: u8 = 4;
+const T_DQSCK_MAX_NCK: u8 = 8;
+const RD_DLY: u8 = 8;
+const RTT_OH: u8 = 3;
+// FSBL defaults: byte lane VREF. Select codes for ranks 2-3 are unused, the controller has 2 ranks
+const DX_REF_SSEL: u8 = 0x30;
+const DX_REF_ISEL: u8 = 0x55;
+const DX_REF_ISEL_UNUSED: u8 = 0x09;
+const DQ_VREF: u8 = 0x2b;
+const DQ_VREF_UNUSED: u8 = 0x09;
+// refreshes before data training, fewer during VREF training
+const RFSH_DT: u8 = 8;
+const VREF_TRAINING_RFSH_DT: u8 = 1;
+// DX8SL4 holds DX8 and the absent DX9
+const ECC_SLICE: usize = 4;
+
+/// VREF generator settings of a byte lane (DXnGCR4), depend on how the board routes its DQ lines
+#[derive(Debug, Clone, Copy)]
+pub struct LaneVref {
+    /// Single-ended VREF generator enable
+    pub ref_sen: bool,
+    /// Internal VREF enables, one bit per group of the lane's IOs
+    pub ref_ien: u8,
+}
+
+impl LaneVref {
+    /// All of the lane's IOs on the internal VREF
+    pub const INTERNAL: Self = LaneVref {
+        ref_sen: true,
+        ref_ien: 0xf,
+    };
+    /// VREF generators off, for a lane without DQ lines
+    pub const OFF: Self = LaneVref {
+        ref_sen: false,
+        ref_ien: 0,
+    };
+}
+
+type SliceRegs<'a> = (
+    &'a mut regs::DX8SLnOSC,
+    &'a mut regs::PLLCR0,
+    &'a mut regs::DX8SLnDQSCTL,
+    &'a mut regs::DX8SLnDXCTL2,
+    &'a mut regs::DX8SLnIOCR,
+);
+
+type LaneGcrs<'a> = (
+    &'a mut regs::DXnGCR0,
+    &'a mut regs::DXnGCR1,
+    &'a mut regs::DXnGCR2,
+    &'a mut regs::DXnGCR3,
+    &'a mut regs::DXnGCR4,
+    &'a mut regs::DXnGCR5,
+    &'a mut regs::DXnGCR6,
+);
+
 impl DdrPhy {
     pub fn ddr_phy() -> Self {
         let self_ = DdrPhy {
@@ -15,52 +81,50 @@ impl DdrPhy {
         };
         self_
     }
-    /// PHY data training configuration
-    pub fn configure(&mut self) {
-        unsafe {
-            self.regs.pgcr0.write(0x07001E00);
-            self.regs.pgcr2.write(0x00F10010);
-            self.regs.pgcr3.write(0x55AA5480);
-            self.regs.pgcr5.write(0x010100F4);
-
-            self.regs.ptr0.write(0x42C21590);
-            self.regs.ptr1.write(0xD05512C0);
+    /// PHY timing, mode register and lane configuration for `config` at its operating clock.
+    /// Lane VREF enables come from the board, other analog settings (impedance, IO and PLL
+    /// tuning) are defaults from the FSBL.
+    pub fn configure(&mut self, config: &spd::GeneralConfig) -> Result<(), DdrError> {
+        self.write_timing(config);
+        self.write_mode_regs(config)?;
+        let data_lanes = usize::from(config.bus_width / 8);
+        let ecc_lane = config.ecc_en && config.bus_width_extension == 8;
+        for lane in 0..NUM_DATA_LANES {
+            self.configure_lane(lane, lane < data_lanes);
+        }
+        self.configure_lane(ECC_LANE, ecc_lane);
+        for slice in 0..ECC_SLICE {
+            self.configure_slice(slice, true);
+        }
+        self.configure_slice(ECC_SLICE, ecc_lane);
+        // broadcast to all slices
+        self.regs.dx8_slb_dqsctl.write(
+            regs::DX8SLnDQSCTL::zeroed()
+                .rrr_mode(true)
+                .wrr_mode(true)
+                .lp_pll_pd(true)
+                .lp_io_pd(true)
+                .qs_cnt_en(true)
+                .dx_sr(3)
+                .dqsn_res(0xc)
+                .dqs_res(4),
+        );
+        self.write_rdimm_regs(config);
+        self.write_general_config();
+        self.write_training_config();
+        self.write_impedance();
 
-            self.regs.pll_ctrl0.write(0x01100000);
+        unsafe {
             self.regs.ddr_sys_cfg.write(0x02A04161);
             self.regs.gpr0.write(0x00000000);
             self.regs.gpr1.write(0x000000E5);
-            self.regs.dram_cfg.write(0x0800040C);
-
-            self.regs.dtpr0.write(0x07240F08);
-            self.regs.dtpr1.write(0x28200008);
-            self.regs.dtpr2.write(0x000F0300);
-            self.regs.dtpr3.write(0x83000800);
-            self.regs.dtpr4.write(0x01762B07);
-            self.regs.dtpr5.write(0x00330F08);
-            self.regs.dtpr6.write(0x00000E0F);
+            self.regs
+                .dram_cfg
+                .write(0x08000408 | dram_mode(config.device_type));
 
-            self.regs.rdimm_cfg0.write(0x08400020);
             self.regs.rdimm_cfg1.write(0x00000C80);
-            self.regs.rdimm_ctrl0.write(0x00000000);
-            self.regs.rdimm_ctrl1.write(0x00000300);
-
-            self.regs.mode0.write(0x00000630);
-            self.regs.mode1.write(0x00000301);
-            self.regs.mode2.write(0x00000020);
-            self.regs.mode3.write(0x00000200);
-            self.regs.mode4.write(0x00000000);
-            self.regs.mode5.write(0x000006C0);
-            self.regs.mode6.write(0x00000819);
-            self.regs.mode11.write(0x00000000);
-            self.regs.mode12.write(0x0000004D);
-            self.regs.mode13.write(0x00000008);
-            self.regs.mode14.write(0x0000004D);
-            self.regs.mode22.write(0x00000000);
-
-            self.regs.dt_cfg0.write(0x800091C7);
+
             self.regs.dt_cfg1.write(0x00010236);
-            self.regs.catr0.write(0x00141054);
             self.regs.dqs_drift0.write(0x00088000);
             // BISTLSR: self-test, undocumented
             self.regs.rio_cr5.write(0x00000005);
@@ -80,150 +144,576 @@ impl DdrPhy {
             self.regs.ac_bdlr7.write(0x00000000);
             self.regs.ac_bdlr8.write(0x00000000);
             self.regs.ac_bdlr9.write(0x00000000);
+        }
+        Ok(())
+    }
+
+    /// FSBL defaults for the PHY general configuration and PLL
+    fn write_general_config(&mut self) {
+        self.regs.pgcr0.write(
+            regs::PGCR0::zeroed()
+                .phy_frst(true)
+                .osc_acdl(3)
+                .osc_div(0xf),
+        );
+        self.regs
+            .pgcr2
+            .write(regs::PGCR2::zeroed().dtp_mx_tmr(0x0f).t_refprd(0x10010));
+        self.regs.pgcr3.write(
+            regs::PGCR3::zeroed()
+                .ckn_en(0x55)
+                .ck_en(0xaa)
+                .gate_ac_rd_clk(2)
+                .gate_ac_ddr_clk(2)
+                .gate_ac_ctl_clk(2)
+                .ddl_byp_mode(2),
+        );
+        self.regs.pgcr5.write(
+            regs::PGCR5::zeroed()
+                .frq_bt(1)
+                .frq_at(1)
+                .vref_rb_ctrl(0xf)
+                .dx_ref_isel_range(true),
+        );
+        self.regs
+            .pll_ctrl0
+            .write(regs::PLLCR0::zeroed().frq_sel(1).cppc(8));
+    }
+
+    /// FSBL defaults for data and LPDDR CA training
+    fn write_training_config(&mut self) {
+        self.write_dtcr0(RFSH_DT);
+        self.regs.catr0.write(
+            regs::CATR0::zeroed()
+                .ca_cd(0x14)
+                .ca_adr(0x10)
+                .ca1_byte1(5)
+                .ca1_byte0(4),
+        );
+    }
+
+    /// Data training configuration, issuing `rfsh_dt` refreshes before training
+    fn write_dtcr0(&mut self, rfsh_dt: u8) {
+        self.regs.dt_cfg0.write(
+            regs::DTCR0::zeroed()
+                .rfsh_dt(rfsh_dt)
+                .dt_rd_bitr(2)
+                .dt_wb_ddm(true)
+                .rfsh_ent(1)
+                .dt_cmpd(true)
+                .dt_mpr(true)
+                .dt_rptn(7),
+        );
+    }
+
+    /// Static read mode (PGCR3 and DX8SLnDXCTL2 RDMODE) is needed during VREF training
+    fn set_static_read_mode(&mut self, enable: bool) {
+        let rd_mode = if enable { 0b11 } else { 0b00 };
+        self.regs.pgcr3.modify(|_, w| w.rd_mode(rd_mode));
+        for slice in 0..=ECC_SLICE {
+            let (_, _, _, dxctl2, _) = self.slice_regs(slice);
+            dxctl2.modify(|_, w| w.rd_mode(rd_mode));
+        }
+    }
+
+    /// FSBL defaults for ZQ calibration and the programmed impedances
+    fn write_impedance(&mut self) {
+        self.regs.zqcr.write(
+            regs::ZQCR::zeroed()
+                .pgwait_frqb(0x11)
+                .pgwait_frqa(0x15)
+                .pu_odt_only(true)
+                .odt_mode(1)
+                .io_dlmt(2)
+                .avg_en(true)
+                .avg_max(2),
+        );
+        self.regs.zq0_pr0.write(
+            regs::ZQnPR0::zeroed()
+                .zprog_dram_odt(7)
+                .zprog_host_odt(9)
+                .zprog_asym_drv_pd(0xd)
+                .zprog_asym_drv_pu(0xd),
+        );
+        self.regs.zq0_or0.write(
+            regs::ZQnOR0::zeroed()
+                .zdata_pd_drv(0x1e1)
+                .zdata_pu_drv(0x210),
+        );
+        self.regs
+            .zq0_or1
+            .write(regs::ZQnOR1::zeroed().zdata_pd_odt(0x1e1));
+        self.regs.zq1_pr0.write(
+            regs::ZQnPR0::zeroed()
+                .pu_drv_adjust(2)
+                .zprog_dram_odt(7)
+                .zprog_host_odt(0xb)
+                .zprog_asym_drv_pd(0xd)
+                .zprog_asym_drv_pu(0xb),
+        );
+    }
+
+    /// PLL, PHY reset and DRAM timing parameters
+    fn write_timing(&mut self, config: &spd::GeneralConfig) {
+        // PTR0/1 count controller clock cycles (2 tCK)
+        let ctl_cycles = |time_ns: u32| (time_ns * 1000).div_ceil(2 * config.t_ck_ps);
+        self.regs.ptr0.write(
+            regs::PTR0::zeroed()
+                .t_pll_pd(ctl_cycles(1_000) as u16)
+                .t_pll_gs(ctl_cycles(4_000) as u16)
+                .t_phy_rst(16),
+        );
+        self.regs.ptr1.write(
+            regs::PTR1::zeroed()
+                .t_pll_lock(ctl_cycles(100_000) as u16)
+                .t_pll_rst(ctl_cycles(9_000) as u16),
+        );
+
+        // DTPRn count DRAM clock cycles
+        let nck = |time_ps: u32| config.ps_to_nck(time_ps);
+        self.regs.dtpr0.write(
+            regs::DTPR0::zeroed()
+                .t_rrd(nck(config.t_rrd_l_min_ps).max(4) as u8)
+                .t_ras(nck(config.t_ras_min_ps) as u8)
+                .t_rp(nck(config.t_rp_min_ps) as u8)
+                // max(4 nCK, 7.5 ns)
+                .t_rtp(nck(7_500).max(4) as u8),
+        );
+        let t_mod_offset = match config.device_type {
+            spd::DeviceType::Ddr4 => 24,
+            _ => 12,
+        };
+        self.regs.dtpr1.write(
+            regs::DTPR1::zeroed()
+                .t_wlmrd(T_WLMRD_NCK)
+                .t_faw(nck(config.t_faw_min_ps) as u8)
+                .t_mod(config.t_mod_nck().saturating_sub(t_mod_offset) as u8)
+                .t_mrd(T_MRD_NCK),
+        );
+        // minimum time in power down or self refresh: the longer of tCKESR and tCKSRE
+        let t_cke = config.t_ckesr_nck().max(config.t_cksre_nck());
+        self.regs.dtpr2.write(
+            regs::DTPR2::zeroed()
+                .t_cke(t_cke.min(0xf) as u8)
+                .t_xs(config.t_xs_dll_min_nck() as u16),
+        );
+        self.regs.dtpr3.write(
+            regs::DTPR3::zeroed()
+                .t_ofdx(T_OFDX_NCK)
+                .t_dllk(config.t_dllk_min_nck() as u16)
+                .t_dqsck_max(T_DQSCK_MAX_NCK),
+        );
+        self.regs.dtpr4.write(
+            regs::DTPR4::zeroed()
+                .t_rfc(nck(config.t_rfc_min_ps()) as u16)
+                // tWLO max 9.5 ns
+                .t_wlo(nck(9_500) as u8)
+                .t_xp(config.t_xp_nck() as u8),
+        );
+        self.regs.dtpr5.write(
+            regs::DTPR5::zeroed()
+                .t_rc(nck(config.t_rc_min_ps) as u8)
+                .t_rcd(nck(config.t_rcd_min_ps) as u8)
+                // tWTR_L: max(4 nCK, 7.5 ns)
+                .t_wtr(nck(7_500).max(4) as u8),
+        );
+        // only used if enabled, the PHY otherwise derives them from the mode registers
+        self.regs.dtpr6.write(
+            regs::DTPR6::zeroed()
+                .pub_wl(config.write_latency_nck() as u8)
+                .pub_rl(config.read_latency_nck() as u8),
+        );
+    }
+
+    /// Mode register images used by the PHY for DRAM init and training
+    fn write_mode_regs(&mut self, config: &spd::GeneralConfig) -> Result<(), DdrError> {
+        self.regs.mode0.write(regs::MR0::zeroed().mr(config.mr0()));
+        self.regs.mode1.write(regs::MR1::zeroed().mr(config.mr1()));
+        self.regs.mode2.write(regs::MR2::zeroed().mr(config.mr2()));
+        self.regs.mode3.write(regs::MR3::zeroed().mr(config.mr3()));
+        self.regs.mode4.write(regs::MR4::zeroed().mr(config.mr4()));
+        self.regs.mode5.write(regs::MR5::zeroed().mr(config.mr5()));
+        self.regs.mode6.write(regs::MR6::zeroed().mr(config.mr6()));
+        // LPDDR4 only, FSBL defaults otherwise
+        let [mr11, mr12, mr13, mr14, mr22] = match config.device_type {
+            spd::DeviceType::LpDdr4 => [
+                lpddr4::mr11(config)?,
+                lpddr4::mr12(config),
+                lpddr4::mr13(config),
+                lpddr4::mr14(config),
+                lpddr4::mr22(config),
+            ],
+            _ => [0x00, 0x4D, 0x08, 0x4D, 0x00],
+        };
+        unsafe {
+            self.regs.mode11.write(mr11.into());
+            self.regs.mode12.write(mr12.into());
+            self.regs.mode13.write(mr13.into());
+            self.regs.mode14.write(mr14.into());
+            self.regs.mode22.write(mr22.into());
+        }
+        Ok(())
+    }
+
+    /// RCD control words, written to RDIMMs and LRDIMMs by the PHY before DRAM init. The data
+    /// buffer control words of LRDIMMs are left at their defaults.
+    fn write_rdimm_regs(&mut self, config: &spd::GeneralConfig) {
+        let lrdimm = matches!(config.module_config, spd::ModuleConfig::LoadReduced(_));
+        let rnk_mrr_en = if config.rank_1_mirrored() { 0b10 } else { 0b00 };
+        self.regs.rdimm_cfg0.write(
+            regs::RDIMMGCR0::zeroed()
+                .rdimm_iom(true)
+                .errout_iom(true)
+                .lrdimm(lrdimm)
+                .rnk_mrr_en(rnk_mrr_en)
+                .rdimm(config.rcd().is_some()),
+        );
+
+        // RC03-RC05: output drive strengths from the SPD, in the same order as bytes 137-138
+        let (rc3, rc4, rc5) = config.rcd().map_or((0, 0, 0), |rcd| {
+            (
+                (rcd.cs_drive as u8) << 2 | rcd.ca_drive as u8,
+                (rcd.odt_drive as u8) << 2 | rcd.cke_drive as u8,
+                (rcd.y1_y3_drive as u8) << 2 | rcd.y0_y2_drive as u8,
+            )
+        });
+        self.regs
+            .rdimm_ctrl0
+            .write(regs::RDIMMCR0::zeroed().rc5(rc5).rc4(rc4).rc3(rc3));
+
+        // RC08: disable A17 if unused, RC0A: operating speed, RC0D: RDIMM/LRDIMM and MRS mirroring
+        let rc8 = ((config.row_addr_bits < 18) as u8) << 3;
+        let rc10 = match config.speed_grade() {
+            1600 => 0,
+            1866 => 1,
+            2133 => 2,
+            2400 => 3,
+            2666 => 4,
+            2933 => 5,
+            _ => 6,
+        };
+        let rc13 = (config.rank_1_mirrored() as u8) << 3 | (!lrdimm as u8) << 2;
+        self.regs
+            .rdimm_ctrl1
+            .write(regs::RDIMMCR1::zeroed().rc13(rc13).rc10(rc10).rc8(rc8));
+
+        // RC3x: operating speed in 20 MT/s steps above 1240 MT/s
+        let rc3x = ((config.data_rate_mhz().clamp(1241, 3200) - 1241) / 20) as u8;
+        self.regs.rdimm_ctrl2.modify(|_, w| w.rc3x(rc3x));
+    }
+
+    fn lane_gcrs(&mut self, lane: usize) -> LaneGcrs<'_> {
+        let r = &mut *self.regs;
+        match lane {
+            0 => (
+                &mut r.dx0_gcr0,
+                &mut r.dx0_gcr1,
+                &mut r.dx0_gcr2,
+                &mut r.dx0_gcr3,
+                &mut r.dx0_gcr4,
+                &mut r.dx0_gcr5,
+                &mut r.dx0_gcr6,
+            ),
+            1 => (
+                &mut r.dx1_gcr0,
+                &mut r.dx1_gcr1,
+                &mut r.dx1_gcr2,
+                &mut r.dx1_gcr3,
+                &mut r.dx1_gcr4,
+                &mut r.dx1_gcr5,
+                &mut r.dx1_gcr6,
+            ),
+            2 => (
+                &mut r.dx2_gcr0,
+                &mut r.dx2_gcr1,
+                &mut r.dx2_gcr2,
+                &mut r.dx2_gcr3,
+                &mut r.dx2_gcr4,
+                &mut r.dx2_gcr5,
+                &mut r.dx2_gcr6,
+            ),
+            3 => (
+                &mut r.dx3_gcr0,
+                &mut r.dx3_gcr1,
+                &mut r.dx3_gcr2,
+                &mut r.dx3_gcr3,
+                &mut r.dx3_gcr4,
+                &mut r.dx3_gcr5,
+                &mut r.dx3_gcr6,
+            ),
+            4 => (
+                &mut r.dx4_gcr0,
+                &mut r.dx4_gcr1,
+                &mut r.dx4_gcr2,
+                &mut r.dx4_gcr3,
+                &mut r.dx4_gcr4,
+                &mut r.dx4_gcr5,
+                &mut r.dx4_gcr6,
+            ),
+            5 => (
+                &mut r.dx5_gcr0,
+                &mut r.dx5_gcr1,
+                &mut r.dx5_gcr2,
+                &mut r.dx5_gcr3,
+                &mut r.dx5_gcr4,
+                &mut r.dx5_gcr5,
+                &mut r.dx5_gcr6,
+            ),
+            6 => (
+                &mut r.dx6_gcr0,
+                &mut r.dx6_gcr1,
+                &mut r.dx6_gcr2,
+                &mut r.dx6_gcr3,
+                &mut r.dx6_gcr4,
+                &mut r.dx6_gcr5,
+                &mut r.dx6_gcr6,
+            ),
+            7 => (
+                &mut r.dx7_gcr0,
+                &mut r.dx7_gcr1,
+                &mut r.dx7_gcr2,
+                &mut r.dx7_gcr3,
+                &mut r.dx7_gcr4,
+                &mut r.dx7_gcr5,
+                &mut r.dx7_gcr6,
+            ),
+            _ => (
+                &mut r.dx8_gcr0,
+                &mut r.dx8_gcr1,
+                &mut r.dx8_gcr2,
+                &mut r.dx8_gcr3,
+                &mut r.dx8_gcr4,
+                &mut r.dx8_gcr5,
+                &mut r.dx8_gcr6,
+            ),
+        }
+    }
+
+    /// Enable a byte lane, or bypass its calibration and power down its IOs
+    fn configure_lane(&mut self, lane: usize, enable: bool) {
+        use regs::IoMode;
+
+        let (gcr0, gcr1, gcr2, gcr3, gcr4, gcr5, gcr6) = self.lane_gcrs(lane);
+        if enable {
+            gcr0.write(
+                regs::DXnGCR0::zeroed()
+                    .mdl_en(true)
+                    .rd_dly(RD_DLY)
+                    .rtt_oh(RTT_OH)
+                    .dqsg_oe(true),
+            );
+            gcr1.write(
+                regs::DXnGCR1::zeroed()
+                    .qsn_sel(true)
+                    .qs_sel(true)
+                    .oe_en(true)
+                    .pdr_en(true)
+                    .te_en(true)
+                    .ds_en(true)
+                    .dm_en(true)
+                    .dq_en(0xff),
+            );
+            gcr2.write(
+                regs::DXnGCR2::zeroed()
+                    .dx_oe_mode(IoMode::Dynamic.all())
+                    .dx_te_mode(IoMode::Dynamic.all()),
+            );
+            gcr3.write(
+                regs::DXnGCR3::zeroed()
+                    .rd_bvt(true)
+                    .wd_bvt(true)
+                    .rgl_vt(true)
+                    .rdl_vt(true)
+                    .wdl_vt(true)
+                    .wll_vt(true)
+                    .ds_pdr_mode(IoMode::Off),
+            );
+        } else {
+            gcr0.write(
+                regs::DXnGCR0::zeroed()
+                    .cal_byp(true)
+                    .rd_dly(RD_DLY)
+                    .dqsn_se_pdr(true)
+                    .dqs_se_pdr(true)
+                    .rtt_oh(RTT_OH)
+                    .dqs_rpd(true)
+                    .dqsg_pdr(true),
+            );
+            gcr1.write(
+                regs::DXnGCR1::zeroed()
+                    .dx_pdr_mode(IoMode::On.all())
+                    .qsn_sel(true)
+                    .qs_sel(true),
+            );
+            gcr2.write(
+                regs::DXnGCR2::zeroed()
+                    .dx_oe_mode(IoMode::Off.all())
+                    .dx_te_mode(IoMode::Off.all()),
+            );
+            gcr3.write(
+                regs::DXnGCR3::zeroed()
+                    .dsn_oe_mode(IoMode::Off)
+                    .dsn_te_mode(IoMode::Off)
+                    .dsn_pdr_mode(IoMode::On)
+                    .dm_oe_mode(IoMode::Off)
+                    .dm_te_mode(IoMode::Off)
+                    .dm_pdr_mode(IoMode::On)
+                    .ds_oe_mode(IoMode::Off)
+                    .ds_te_mode(IoMode::Off)
+                    .ds_pdr_mode(IoMode::On),
+            );
+        }
+        let vref = Target::DDR_LANE_VREF[lane];
+        gcr4.write(
+            regs::DXnGCR4::zeroed()
+                .dx_ref_een(3)
+                .dx_ref_sen(vref.ref_sen)
+                .dx_ref_ssel_range(true)
+                .dx_ref_ssel(DX_REF_SSEL)
+                .dx_ref_ien(vref.ref_ien),
+        );
+        gcr5.write(
+            regs::DXnGCR5::zeroed()
+                .dx_ref_isel_r3(DX_REF_ISEL_UNUSED)
+                .dx_ref_isel_r2(DX_REF_ISEL_UNUSED)
+                .dx_ref_isel_r1(DX_REF_ISEL)
+                .dx_ref_isel_r0(DX_REF_ISEL),
+        );
+        gcr6.write(
+            regs::DXnGCR6::zeroed()
+                .dx_dq_vref_r3(DQ_VREF_UNUSED)
+                .dx_dq_vref_r2(DQ_VREF_UNUSED)
+                .dx_dq_vref_r1(DQ_VREF)
+                .dx_dq_vref_r0(DQ_VREF),
+        );
+    }
+
+    fn slice_regs(&mut self, slice: usize) -> SliceRegs<'_> {
+        let r = &mut *self.regs;
+        match slice {
+            0 => (
+                &mut r.dx8_sl0_osc,
+                &mut r.dx8_sl0_pllcr0,
+                &mut r.dx8_sl0_dqsctl,
+                &mut r.dx8_sl0_dxctl2,
+                &mut r.dx8_sl0_iocr,
+            ),
+            1 => (
+                &mut r.dx8_sl1_osc,
+                &mut r.dx8_sl1_pllcr0,
+                &mut r.dx8_sl1_dqsctl,
+                &mut r.dx8_sl1_dxctl2,
+                &mut r.dx8_sl1_iocr,
+            ),
+            2 => (
+                &mut r.dx8_sl2_osc,
+                &mut r.dx8_sl2_pllcr0,
+                &mut r.dx8_sl2_dqsctl,
+                &mut r.dx8_sl2_dxctl2,
+                &mut r.dx8_sl2_iocr,
+            ),
+            3 => (
+                &mut r.dx8_sl3_osc,
+                &mut r.dx8_sl3_pllcr0,
+                &mut r.dx8_sl3_dqsctl,
+                &mut r.dx8_sl3_dxctl2,
+                &mut r.dx8_sl3_iocr,
+            ),
+            _ => (
+                &mut r.dx8_sl4_osc,
+                &mut r.dx8_sl4_pllcr0,
+                &mut r.dx8_sl4_dqsctl,
+                &mut r.dx8_sl4_dxctl2,
+                &mut r.dx8_sl4_iocr,
+            ),
+        }
+    }
 
-            self.regs.zqcr.write(0x008AAA58);
-            self.regs.zq0_pr0.write(0x000079DD);
-            self.regs.zq0_or0.write(0x01E10210);
-            self.regs.zq0_or1.write(0x01E10000);
-            self.regs.zq1_pr0.write(0x00087BDB);
-
-            self.regs.dx0_gcr0.write(0x40800604);
-            self.regs.dx0_gcr1.write(0x00007FFF);
-            self.regs.dx0_gcr3.write(0x3F000008);
-            self.regs.dx0_gcr4.write(0x0E00B03C);
-            self.regs.dx0_gcr5.write(0x09095555);
-            self.regs.dx0_gcr6.write(0x09092B2B);
-
-            self.regs.dx1_gcr0.write(0x40800604);
-            self.regs.dx1_gcr1.write(0x00007FFF);
-            self.regs.dx1_gcr3.write(0x3F000008);
-            self.regs.dx1_gcr4.write(0x0E00B03C);
-            self.regs.dx1_gcr5.write(0x09095555);
-            self.regs.dx1_gcr6.write(0x09092B2B);
-
-            self.regs.dx2_gcr0.write(0x40800604);
-            self.regs.dx2_gcr1.write(0x00007FFF);
-            self.regs.dx2_gcr3.write(0x3F000008);
-            self.regs.dx2_gcr4.write(0x0E00B004);
-            self.regs.dx2_gcr5.write(0x09095555);
-            self.regs.dx2_gcr6.write(0x09092B2B);
-
-            self.regs.dx3_gcr0.write(0x40800604);
-            self.regs.dx3_gcr1.write(0x00007FFF);
-            self.regs.dx3_gcr3.write(0x3F000008);
-            self.regs.dx3_gcr4.write(0x0E00B004);
-            self.regs.dx3_gcr5.write(0x09095555);
-            self.regs.dx3_gcr6.write(0x09092B2B);
-
-            self.regs.dx4_gcr0.write(0x40800604);
-            self.regs.dx4_gcr1.write(0x00007FFF);
-            self.regs.dx4_gcr2.write(0x00000000);
-            self.regs.dx4_gcr3.write(0x3F000008);
-            self.regs.dx4_gcr4.write(0x0E00B004);
-            self.regs.dx4_gcr5.write(0x09095555);
-            self.regs.dx4_gcr6.write(0x09092B2B);
-
-            self.regs.dx5_gcr0.write(0x40800604);
-            self.regs.dx5_gcr1.write(0x00007FFF);
-            self.regs.dx5_gcr2.write(0x00000000);
-            self.regs.dx5_gcr3.write(0x3F000008);
-            self.regs.dx5_gcr4.write(0x0E00B03C);
-            self.regs.dx5_gcr5.write(0x09095555);
-            self.regs.dx5_gcr6.write(0x09092B2B);
-
-            self.regs.dx6_gcr0.write(0x40800604);
-            self.regs.dx6_gcr1.write(0x00007FFF);
-            self.regs.dx6_gcr2.write(0x00000000);
-            self.regs.dx6_gcr3.write(0x3F000008);
-            self.regs.dx6_gcr4.write(0x0E00B004);
-            self.regs.dx6_gcr5.write(0x09095555);
-            self.regs.dx6_gcr6.write(0x09092B2B);
-
-            self.regs.dx7_gcr0.write(0x40800604);
-            self.regs.dx7_gcr1.write(0x00007FFF);
-            self.regs.dx7_gcr2.write(0x00000000);
-            self.regs.dx7_gcr3.write(0x3F000008);
-            self.regs.dx7_gcr4.write(0x0E00B03C);
-            self.regs.dx7_gcr5.write(0x09095555);
-            self.regs.dx7_gcr6.write(0x09092B2B);
-
-            self.regs.dx8_gcr0.write(0x80803660);
-            self.regs.dx8_gcr1.write(0x55556000);
-            self.regs.dx8_gcr2.write(0xAAAAAAAA);
-            self.regs.dx8_gcr3.write(0x0029A4A4);
-            self.regs.dx8_gcr4.write(0x0C00B000);
-            self.regs.dx8_gcr5.write(0x09095555);
-            self.regs.dx8_gcr6.write(0x09092B2B);
-
-            self.regs.dx8_sl0_osc.write(0x2A019FFE);
-            self.regs.dx8_sl0_pllcr0.write(0x01100000);
-            self.regs.dx8_sl0_dqsctl.write(0x01264300);
-            self.regs.dx8_sl0_dxctl2.write(0x00041800);
-            self.regs.dx8_sl0_iocr.write(0x70800000);
-
-            self.regs.dx8_sl1_osc.write(0x2A019FFE);
-            self.regs.dx8_sl1_pllcr0.write(0x01100000);
-            self.regs.dx8_sl1_dqsctl.write(0x01264300);
-            self.regs.dx8_sl1_dxctl2.write(0x00041800);
-            self.regs.dx8_sl1_iocr.write(0x70800000);
-
-            self.regs.dx8_sl2_osc.write(0x2A019FFE);
-            self.regs.dx8_sl2_pllcr0.write(0x01100000);
-            self.regs.dx8_sl2_dqsctl.write(0x01264300);
-            self.regs.dx8_sl2_dxctl2.write(0x00041800);
-            self.regs.dx8_sl2_iocr.write(0x70800000);
-
-            self.regs.dx8_sl3_osc.write(0x2A019FFE);
-            self.regs.dx8_sl3_pllcr0.write(0x01100000);
-            self.regs.dx8_sl3_dqsctl.write(0x01264300);
-            self.regs.dx8_sl3_dxctl2.write(0x00041800);
-            self.regs.dx8_sl3_iocr.write(0x70800000);
-
-            self.regs.dx8_sl4_osc.write(0x15019FFE);
-            self.regs.dx8_sl4_pllcr0.write(0x21100000);
-            self.regs.dx8_sl4_dqsctl.write(0x01266300);
-            self.regs.dx8_sl4_dxctl2.write(0x00041800);
-            self.regs.dx8_sl4_iocr.write(0x70400000);
-
-            self.regs.dx8_slb_dqsctl.write(0x012643C4);
+    /// FSBL defaults for the byte lane slice `slice`, with its clocks gated and PLL powered
+    /// down if none of its lanes are `used`
+    fn configure_slice(&mut self, slice: usize, used: bool) {
+        let (osc, pllcr0, dqsctl, dxctl2, iocr) = self.slice_regs(slice);
+        let clk_setting = if used { 2 } else { 1 };
+        osc.write(
+            regs::DX8SLnOSC::zeroed()
+                .gate_dx_ddr_clk(clk_setting)
+                .gate_dx_ctl_clk(clk_setting)
+                .clk_level(clk_setting)
+                .phy_hrst(true)
+                .phy_frst(true)
+                .osc_wddl(0xf)
+                .osc_wdl(0xf)
+                .osc_div(0xf),
+        );
+        pllcr0.write(regs::PLLCR0::zeroed().pll_pd(!used).frq_sel(1).cppc(8));
+        dqsctl.write(
+            regs::DX8SLnDQSCTL::zeroed()
+                .rrr_mode(true)
+                .wrr_mode(true)
+                .lp_pll_pd(true)
+                .lp_io_pd(true)
+                .qs_cnt_en(true)
+                .udq_iom(!used)
+                .dx_sr(3),
+        );
+        dxctl2.write(regs::DX8SLnDXCTL2::zeroed().pre_oex(1).lp_wakeup_thrsh(0xc));
+        iocr.write(
+            regs::DX8SLnIOCR::zeroed()
+                .dx_dac_range(7)
+                .dx_iom(if used { 2 } else { 1 }),
+        );
+    }
+
+    /// Poll PGSR0 until `done`, giving up after [DEFAULT_TIMEOUT] reads
+    fn wait_pgsr0<F>(&self, step: &'static str, done: F) -> Result<regs::pgsr0::Read, DdrError>
+    where
+        F: Fn(&regs::pgsr0::Read) -> bool,
+    {
+        let mut timeout = DEFAULT_TIMEOUT;
+        let mut pgsr0_read = self.regs.pgsr0.read();
+        while !done(&pgsr0_read) {
+            if timeout == 0 {
+                return Err(DdrError::Timeout(step));
+            }
+            pgsr0_read = self.regs.pgsr0.read();
+            timeout -= 1;
         }
+        Ok(pgsr0_read)
     }
 
     /// Initialize DDR PLLs
-    pub fn init_plls(&mut self) {
+    pub fn init_plls(&mut self) -> Result<(), DdrError> {
         let mut pll_retry: u8 = 10;
         let mut pll_locked: bool = false;
 
         while pll_retry > 0 && !pll_locked {
+            pll_retry -= 1;
             self.regs.phy_init.write(
                 regs::PhyInit::zeroed()
                     .ctrl_dram_init(true)
                     .pll_init(true)
                     .init(),
             );
-            // self.regs.phy_init.modify(|_, w| w.init());
 
-            let mut timeout = DEFAULT_TIMEOUT;
-            while timeout > 0 && !self.regs.pgsr0.read().pl_done() {
-                timeout -= 1;
-            }
-            if timeout == 0 {
+            if self.wait_pgsr0("PLL init", |r| r.pl_done()).is_err() {
                 continue;
-            };
+            }
 
             pll_locked = self.regs.pgsr0.read().ap_lock()
                 && self.regs.dx0_gsr0.read().dp_lock()
                 && self.regs.dx2_gsr0.read().dp_lock()
                 && self.regs.dx4_gsr0.read().dp_lock()
                 && self.regs.dx6_gsr0.read().dp_lock();
-            pll_retry -= 1;
         }
         // random write of pll_retry to GPR0?
-        assert!(pll_locked, "DDR PLLs failed to lock");
+        if !pll_locked {
+            return Err(DdrError::PllLock);
+        }
+        Ok(())
     }
 
     /// Execute impedance and digital delay line calibrations in parallel
-    pub fn execute_zcal_dcal(&mut self) {
+    pub fn execute_zcal_dcal(&mut self) -> Result<(), DdrError> {
         self.regs.phy_init.write(
             regs::PhyInit::zeroed()
                 .ctrl_dram_init(true)
@@ -232,44 +722,30 @@ impl DdrPhy {
                 .zcal(true)
                 .init(),
         );
+        self.wait_pgsr0("calibration", |r| r.zc_done() && r.dc_done() && r.i_done())?;
 
-        let mut timeout = DEFAULT_TIMEOUT;
-        let mut pgsr0_read = self.regs.pgsr0.read();
-        while timeout > 0 && !(pgsr0_read.zc_done() && pgsr0_read.dc_done() && pgsr0_read.i_done())
-        {
-            pgsr0_read = self.regs.pgsr0.read();
-            timeout -= 1;
-        }
-        assert!(
-            timeout != 0,
-            "Timed out while waiting for calibration to finish"
-        );
-
-        // wait for DRAM init done
-        self.regs
-            .phy_init
-            .write(regs::PhyInit::zeroed().ctrl_dram_init(true).init());
-
-        timeout = DEFAULT_TIMEOUT;
-        pgsr0_read = self.regs.pgsr0.read();
-        while timeout > 0 && !(pgsr0_read.di_done() && pgsr0_read.i_done()) {
-            pgsr0_read = self.regs.pgsr0.read();
-            timeout -= 1;
-        }
-        assert!(
-            timeout != 0,
-            "Timed out while waiting for calibration to finish"
+        // wait for DRAM init done, after the PHY has initialized the RCD of registered modules
+        let rdimm = self.regs.rdimm_cfg0.read().rdimm();
+        self.regs.phy_init.write(
+            regs::PhyInit::zeroed()
+                .ctrl_dram_init(true)
+                .rdimm_init(rdimm)
+                .init(),
         );
+        self.wait_pgsr0("DRAM init", |r| r.di_done() && r.i_done())?;
+        Ok(())
     }
 
-    /// Execute training sequences
-    pub fn execute_training(&mut self) {
+    /// Execute training sequences, starting with CA training for LPDDR4
+    pub fn execute_training(&mut self, config: &spd::GeneralConfig) -> Result<(), DdrError> {
+        let ca_training = config.device_type == spd::DeviceType::LpDdr4;
         unsafe {
             self.regs.pgcr1.write(0x00000040);
         }
         self.regs.phy_init.write(
             regs::PhyInit::zeroed()
                 .ctrl_dram_init(true)
+                .lpddr3_ca(ca_training)
                 .wr_eye(true)
                 .rd_eye(true)
                 .wr_deskew(true)
@@ -280,95 +756,66 @@ impl DdrPhy {
                 .init(),
         );
 
-        let mut timeout = DEFAULT_TIMEOUT;
-        let mut pgsr0_read = self.regs.pgsr0.read();
-        while timeout > 0
-            && !(pgsr0_read.we_done()
-                && pgsr0_read.re_done()
-                && pgsr0_read.wd_done()
-                && pgsr0_read.rd_done()
-                && pgsr0_read.wla_done()
-                && pgsr0_read.qsg_done()
-                && pgsr0_read.wl_done())
+        let pgsr0_read = self.wait_pgsr0("training", |r| {
+            (r.ca_done() || !ca_training)
+                && r.we_done()
+                && r.re_done()
+                && r.wd_done()
+                && r.rd_done()
+                && r.wla_done()
+                && r.qsg_done()
+                && r.wl_done()
+        })?;
+        if pgsr0_read.ca_err()
+            || pgsr0_read.we_err()
+            || pgsr0_read.re_err()
+            || pgsr0_read.wd_err()
+            || pgsr0_read.rd_err()
+            || pgsr0_read.wla_err()
+            || pgsr0_read.qsg_err()
+            || pgsr0_read.wl_err()
+            || pgsr0_read.zc_err()
+            || pgsr0_read.v_err()
+            || pgsr0_read.dqs2dq_err()
         {
-            pgsr0_read = self.regs.pgsr0.read();
-            timeout -= 1;
+            return Err(DdrError::Training(pgsr0_read.inner));
         }
-        assert!(
-            timeout != 0,
-            "Timed out while waiting for training to finish"
-        );
-
-        assert!(
-            !(pgsr0_read.ca_err()
-                || pgsr0_read.we_err()
-                || pgsr0_read.re_err()
-                || pgsr0_read.wd_err()
-                || pgsr0_read.rd_err()
-                || pgsr0_read.wla_err()
-                || pgsr0_read.qsg_err()
-                || pgsr0_read.wl_err()
-                || pgsr0_read.zc_err()
-                || pgsr0_read.v_err()
-                || pgsr0_read.dqs2dq_err()),
-            "Error flag(s) set in PGSR0"
-        );
 
         // vref training
-        unsafe {
-            // set static read mode
-            self.regs.dt_cfg0.write(0x100091C7);
-            self.regs.pgcr3.modify(|cur| cur | (0x3 << 3));
-            self.regs.dx8_sl0_dxctl2.modify(|cur| cur | (0x3 << 4));
-            self.regs.dx8_sl1_dxctl2.modify(|cur| cur | (0x3 << 4));
-            self.regs.dx8_sl2_dxctl2.modify(|cur| cur | (0x3 << 4));
-            self.regs.dx8_sl3_dxctl2.modify(|cur| cur | (0x3 << 4));
-            self.regs.dx8_sl4_dxctl2.modify(|cur| cur | (0x3 << 4));
-        }
+        self.write_dtcr0(VREF_TRAINING_RFSH_DT);
+        self.set_static_read_mode(true);
 
         self.regs.phy_init.write(
-            regs::PhyInit::zeroed().ctrl_dram_init(true).vref(true).init()
+            regs::PhyInit::zeroed()
+                .ctrl_dram_init(true)
+                .vref(true)
+                .init(),
         );
 
-        timeout = DEFAULT_TIMEOUT;
-        pgsr0_read = self.regs.pgsr0.read();
-        while timeout > 0 && !(pgsr0_read.v_done() && pgsr0_read.i_done()) {
-            pgsr0_read = self.regs.pgsr0.read();
-            timeout -= 1;
+        let pgsr0_read = self.wait_pgsr0("VREF training", |r| r.v_done() && r.i_done())?;
+        if pgsr0_read.v_err() {
+            return Err(DdrError::Training(pgsr0_read.inner));
         }
-        assert!(
-            timeout != 0,
-            "Timed out while waiting for training to finish"
-        );
 
-        assert!(!pgsr0_read.v_err(), "VREF training error");
-
-
-        unsafe {
-            // disable static read mode
-            self.regs.pgcr3.modify(|cur| cur & !(0x3 << 3));
-            self.regs.dx8_sl0_dxctl2.modify(|cur| cur & !(0x3 << 4));
-            self.regs.dx8_sl1_dxctl2.modify(|cur| cur & !(0x3 << 4));
-            self.regs.dx8_sl2_dxctl2.modify(|cur| cur & !(0x3 << 4));
-            self.regs.dx8_sl3_dxctl2.modify(|cur| cur & !(0x3 << 4));
-            self.regs.dx8_sl4_dxctl2.modify(|cur| cur & !(0x3 << 4));
-            self.regs.dt_cfg0.write(0x800091C7);
-        }
+        self.set_static_read_mode(false);
+        self.write_dtcr0(RFSH_DT);
 
         // execute wr/rd eye training again...?
-        self.regs.phy_init.write(
-            regs::PhyInit::zeroed().wr_eye(true).rd_eye(true).init()
-        );
+        self.regs
+            .phy_init
+            .write(regs::PhyInit::zeroed().wr_eye(true).rd_eye(true).init());
 
-        timeout = DEFAULT_TIMEOUT;
-        pgsr0_read = self.regs.pgsr0.read();
-        while timeout > 0 && !(pgsr0_read.we_done() && pgsr0_read.re_done() && pgsr0_read.i_done()) {
-            pgsr0_read = self.regs.pgsr0.read();
-            timeout -= 1;
-        }
-        assert!(
-            timeout != 0,
-            "Timed out while waiting for training to finish"
-        );
+        self.wait_pgsr0("eye training", |r| r.we_done() && r.re_done() && r.i_done())?;
+        Ok(())
+    }
+}
+
+/// DCR DDRMD encoding of the SDRAM type
+fn dram_mode(device_type: spd::DeviceType) -> u32 {
+    match device_type {
+        spd::DeviceType::LpDdr3 => 0b001,
+        spd::DeviceType::Ddr3 => 0b011,
+        spd::DeviceType::Ddr4 => 0b100,
+        spd::DeviceType::LpDdr4 => 0b101,
     }
 }
@@ -1,6 +1,6 @@
 use volatile_register::{RO, RW};
 
-use libregister::{register, register_at, register_bit, register_bits_typed};
+use libregister::{register, register_at, register_bit, register_bits, register_bits_typed};
 
 #[allow(unused)]
 #[derive(Clone, Copy)]
@@ -10,19 +10,6 @@ pub enum DataBusWidth {
     Width16bit = 0b01,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[repr(u8)]
-pub enum ControllerStatus {
-    Init = 0,
-    Normal = 1,
-    Powerdown = 2,
-    SelfRefresh = 3,
-    Powerdown1 = 4,
-    Powerdown2 = 5,
-    Powerdown3 = 6,
-    Powerdown4 = 7,
-}
-
 #[repr(u8)]
 pub enum ZCalStatus {
     Success = 0b00,
@@ -31,32 +18,48 @@ pub enum ZCalStatus {
     InProgress = 0b11,
 }
 
+/// DXnGCR1-3 output enable, termination enable and power-down receiver modes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum IoMode {
+    Dynamic = 0b00,
+    On = 0b01,
+    Off = 0b10,
+}
+
+impl IoMode {
+    /// Same mode for all 8 fields of a 16-bit DXnGCR1/2 mode field
+    pub fn all(self) -> u16 {
+        self as u16 * 0x5555
+    }
+}
+
 #[repr(C)]
 pub struct RegisterBlock {
     unused0: [u32; 1],
     pub phy_init: PhyInit,
     unused1: [u32; 2],
-    pub pgcr0: RW<u32>,
+    pub pgcr0: PGCR0,
     pub pgcr1: RW<u32>, // undocumented
-    pub pgcr2: RW<u32>,
-    pub pgcr3: RW<u32>,
+    pub pgcr2: PGCR2,
+    pub pgcr3: PGCR3,
     pub pgcr4: RW<u32>,
-    pub pgcr5: RW<u32>,
+    pub pgcr5: PGCR5,
     pub pgcr6: RW<u32>,
     pub pgcr7: RW<u32>,
     pub pgsr0: PGSR0,
     pub pgsr1: RO<u32>,
     pub pgsr2: RO<u32>,
     unused3: [u32; 1],
-    pub ptr0: RW<u32>,
-    pub ptr1: RW<u32>,
+    pub ptr0: PTR0,
+    pub ptr1: PTR1,
     pub ptr2: RW<u32>,
     pub ptr3: RW<u32>,
     pub ptr4: RW<u32>,
     pub ptr5: RW<u32>,
     pub ptr6: RW<u32>,
-    unused4: [u32; 1],
-    pub pll_ctrl0: RW<u32>,
+    unused4: [u32; 3],
+    pub pll_ctrl0: PLLCR0,
     pub pll_ctrl1: RW<u32>,
     pub pll_ctrl2: RW<u32>,
     pub pll_ctrl3: RW<u32>,
@@ -76,34 +79,34 @@ pub struct RegisterBlock {
     unused10: [u32; 14],
     pub dram_cfg: RW<u32>,
     unused11: [u32; 3],
-    pub dtpr0: RW<u32>,
-    pub dtpr1: RW<u32>,
-    pub dtpr2: RW<u32>,
-    pub dtpr3: RW<u32>,
-    pub dtpr4: RW<u32>,
-    pub dtpr5: RW<u32>,
-    pub dtpr6: RW<u32>,
+    pub dtpr0: DTPR0,
+    pub dtpr1: DTPR1,
+    pub dtpr2: DTPR2,
+    pub dtpr3: DTPR3,
+    pub dtpr4: DTPR4,
+    pub dtpr5: DTPR5,
+    pub dtpr6: DTPR6,
     unused12: [u32; 5],
-    pub rdimm_cfg0: RW<u32>,
+    pub rdimm_cfg0: RDIMMGCR0,
     pub rdimm_cfg1: RW<u32>,
     pub rdimm_cfg2: RW<u32>,
     unused13: [u32; 1],
-    pub rdimm_ctrl0: RW<u32>,
-    pub rdimm_ctrl1: RW<u32>,
-    pub rdimm_ctrl2: RW<u32>,
+    pub rdimm_ctrl0: RDIMMCR0,
+    pub rdimm_ctrl1: RDIMMCR1,
+    pub rdimm_ctrl2: RDIMMCR2,
     pub rdimm_ctrl3: RW<u32>,
     pub rdimm_ctrl4: RW<u32>,
     unused14: [u32; 1],
     pub sched_cmd0: RW<u32>,
     pub sched_cmd1: RW<u32>,
     unused15: [u32; 4],
-    pub mode0: RW<u32>,
-    pub mode1: RW<u32>,
-    pub mode2: RW<u32>,
-    pub mode3: RW<u32>,
-    pub mode4: RW<u32>,
-    pub mode5: RW<u32>,
-    pub mode6: RW<u32>,
+    pub mode0: MR0,
+    pub mode1: MR1,
+    pub mode2: MR2,
+    pub mode3: MR3,
+    pub mode4: MR4,
+    pub mode5: MR5,
+    pub mode6: MR6,
     pub mode7: RW<u32>,
     unused16: [u32; 3],
     pub mode11: RW<u32>,
@@ -113,7 +116,7 @@ pub struct RegisterBlock {
     unused17: [u32; 7],
     pub mode22: RW<u32>,
     unused18: [u32; 9],
-    pub dt_cfg0: RW<u32>,
+    pub dt_cfg0: DTCR0,
     pub dt_cfg1: RW<u32>,
     pub dt_addr0: RW<u32>,
     pub dt_addr1: RW<u32>,
@@ -126,7 +129,7 @@ pub struct RegisterBlock {
     pub dt_eye_data1: RO<u32>,
     pub dt_eye_data2: RO<u32>,
     pub vt_data: RO<u32>,
-    pub catr0: RW<u32>,
+    pub catr0: CATR0,
     pub catr1: RW<u32>,
     unused21: [u32; 2],
     pub dqs_drift0: RW<u32>,
@@ -166,30 +169,30 @@ pub struct RegisterBlock {
     pub ac_mdlr0: RW<u32>,
     pub ac_mdlr1: RW<u32>,
     unused27: [u32; 54],
-    pub zqcr: RW<u32>,
-    pub zq0_pr0: RW<u32>,
+    pub zqcr: ZQCR,
+    pub zq0_pr0: ZQnPR0,
     pub zq0_pr1: RW<u32>,
     pub zq0_dr0: RW<u32>,
     pub zq0_dr1: RW<u32>,
-    pub zq0_or0: RW<u32>,
-    pub zq0_or1: RW<u32>,
+    pub zq0_or0: ZQnOR0,
+    pub zq0_or1: ZQnOR1,
     pub zq0_sr: ZQnSR, // 0x69c
     unused28: [u32; 1],
-    pub zq1_pr0: RW<u32>,
+    pub zq1_pr0: ZQnPR0,
     pub zq1_pr1: RW<u32>,
     pub zq1_dr0: RW<u32>,
     pub zq1_dr1: RW<u32>,
-    pub zq1_or0: RW<u32>,
-    pub zq1_or1: RW<u32>,
+    pub zq1_or0: ZQnOR0,
+    pub zq1_or1: ZQnOR1,
     pub zq1_sr: ZQnSR, // 0x6bc
     unused29: [u32; 16],
-    pub dx0_gcr0: RW<u32>,
-    pub dx0_gcr1: RW<u32>,
-    pub dx0_gcr2: RW<u32>,
-    pub dx0_gcr3: RW<u32>,
-    pub dx0_gcr4: RW<u32>,
-    pub dx0_gcr5: RW<u32>,
-    pub dx0_gcr6: RW<u32>,
+    pub dx0_gcr0: DXnGCR0,
+    pub dx0_gcr1: DXnGCR1,
+    pub dx0_gcr2: DXnGCR2,
+    pub dx0_gcr3: DXnGCR3,
+    pub dx0_gcr4: DXnGCR4,
+    pub dx0_gcr5: DXnGCR5,
+    pub dx0_gcr6: DXnGCR6,
     unused30: [u32; 9],
     pub dx0_bdlr0: RW<u32>,
     pub dx0_bdlr1: RW<u32>,
@@ -221,13 +224,13 @@ pub struct RegisterBlock {
     pub dx0_gsr2: RW<u32>,
     pub dx0_gsr3: RW<u32>,
     unused37: [u32; 4],
-    pub dx1_gcr0: RW<u32>,
-    pub dx1_gcr1: RW<u32>,
-    pub dx1_gcr2: RW<u32>,
-    pub dx1_gcr3: RW<u32>,
-    pub dx1_gcr4: RW<u32>,
-    pub dx1_gcr5: RW<u32>,
-    pub dx1_gcr6: RW<u32>,
+    pub dx1_gcr0: DXnGCR0,
+    pub dx1_gcr1: DXnGCR1,
+    pub dx1_gcr2: DXnGCR2,
+    pub dx1_gcr3: DXnGCR3,
+    pub dx1_gcr4: DXnGCR4,
+    pub dx1_gcr5: DXnGCR5,
+    pub dx1_gcr6: DXnGCR6,
     unused38: [u32; 9],
     pub dx1_bdlr0: RW<u32>,
     pub dx1_bdlr1: RW<u32>,
@@ -259,13 +262,13 @@ pub struct RegisterBlock {
     pub dx1_gsr2: RW<u32>,
     pub dx1_gsr3: RW<u32>,
     unused45: [u32; 4],
-    pub dx2_gcr0: RW<u32>,
-    pub dx2_gcr1: RW<u32>,
-    pub dx2_gcr2: RW<u32>,
-    pub dx2_gcr3: RW<u32>,
-    pub dx2_gcr4: RW<u32>,
-    pub dx2_gcr5: RW<u32>,
-    pub dx2_gcr6: RW<u32>,
+    pub dx2_gcr0: DXnGCR0,
+    pub dx2_gcr1: DXnGCR1,
+    pub dx2_gcr2: DXnGCR2,
+    pub dx2_gcr3: DXnGCR3,
+    pub dx2_gcr4: DXnGCR4,
+    pub dx2_gcr5: DXnGCR5,
+    pub dx2_gcr6: DXnGCR6,
     unused46: [u32; 9],
     pub dx2_bdlr0: RW<u32>,
     pub dx2_bdlr1: RW<u32>,
@@ -297,13 +300,13 @@ pub struct RegisterBlock {
     pub dx2_gsr2: RW<u32>,
     pub dx2_gsr3: RW<u32>,
     unused53: [u32; 4],
-    pub dx3_gcr0: RW<u32>,
-    pub dx3_gcr1: RW<u32>,
-    pub dx3_gcr2: RW<u32>,
-    pub dx3_gcr3: RW<u32>,
-    pub dx3_gcr4: RW<u32>,
-    pub dx3_gcr5: RW<u32>,
-    pub dx3_gcr6: RW<u32>,
+    pub dx3_gcr0: DXnGCR0,
+    pub dx3_gcr1: DXnGCR1,
+    pub dx3_gcr2: DXnGCR2,
+    pub dx3_gcr3: DXnGCR3,
+    pub dx3_gcr4: DXnGCR4,
+    pub dx3_gcr5: DXnGCR5,
+    pub dx3_gcr6: DXnGCR6,
     unused54: [u32; 9],
     pub dx3_bdlr0: RW<u32>,
     pub dx3_bdlr1: RW<u32>,
@@ -335,13 +338,13 @@ pub struct RegisterBlock {
     pub dx3_gsr2: RW<u32>,
     pub dx3_gsr3: RW<u32>,
     unused61: [u32; 4],
-    pub dx4_gcr0: RW<u32>,
-    pub dx4_gcr1: RW<u32>,
-    pub dx4_gcr2: RW<u32>,
-    pub dx4_gcr3: RW<u32>,
-    pub dx4_gcr4: RW<u32>,
-    pub dx4_gcr5: RW<u32>,
-    pub dx4_gcr6: RW<u32>,
+    pub dx4_gcr0: DXnGCR0,
+    pub dx4_gcr1: DXnGCR1,
+    pub dx4_gcr2: DXnGCR2,
+    pub dx4_gcr3: DXnGCR3,
+    pub dx4_gcr4: DXnGCR4,
+    pub dx4_gcr5: DXnGCR5,
+    pub dx4_gcr6: DXnGCR6,
     unused62: [u32; 9],
     pub dx4_bdlr0: RW<u32>,
     pub dx4_bdlr1: RW<u32>,
@@ -373,13 +376,13 @@ pub struct RegisterBlock {
     pub dx4_gsr2: RW<u32>,
     pub dx4_gsr3: RW<u32>,
     unused69: [u32; 4],
-    pub dx5_gcr0: RW<u32>,
-    pub dx5_gcr1: RW<u32>,
-    pub dx5_gcr2: RW<u32>,
-    pub dx5_gcr3: RW<u32>,
-    pub dx5_gcr4: RW<u32>,
-    pub dx5_gcr5: RW<u32>,
-    pub dx5_gcr6: RW<u32>,
+    pub dx5_gcr0: DXnGCR0,
+    pub dx5_gcr1: DXnGCR1,
+    pub dx5_gcr2: DXnGCR2,
+    pub dx5_gcr3: DXnGCR3,
+    pub dx5_gcr4: DXnGCR4,
+    pub dx5_gcr5: DXnGCR5,
+    pub dx5_gcr6: DXnGCR6,
     unused70: [u32; 9],
     pub dx5_bdlr0: RW<u32>,
     pub dx5_bdlr1: RW<u32>,
@@ -411,13 +414,13 @@ pub struct RegisterBlock {
     pub dx5_gsr2: RW<u32>,
     pub dx5_gsr3: RW<u32>,
     unused77: [u32; 4],
-    pub dx6_gcr0: RW<u32>,
-    pub dx6_gcr1: RW<u32>,
-    pub dx6_gcr2: RW<u32>,
-    pub dx6_gcr3: RW<u32>,
-    pub dx6_gcr4: RW<u32>,
-    pub dx6_gcr5: RW<u32>,
-    pub dx6_gcr6: RW<u32>,
+    pub dx6_gcr0: DXnGCR0,
+    pub dx6_gcr1: DXnGCR1,
+    pub dx6_gcr2: DXnGCR2,
+    pub dx6_gcr3: DXnGCR3,
+    pub dx6_gcr4: DXnGCR4,
+    pub dx6_gcr5: DXnGCR5,
+    pub dx6_gcr6: DXnGCR6,
     unused78: [u32; 9],
     pub dx6_bdlr0: RW<u32>,
     pub dx6_bdlr1: RW<u32>,
@@ -449,13 +452,13 @@ pub struct RegisterBlock {
     pub dx6_gsr2: RW<u32>,
     pub dx6_gsr3: RW<u32>,
     unused85: [u32; 4],
-    pub dx7_gcr0: RW<u32>,
-    pub dx7_gcr1: RW<u32>,
-    pub dx7_gcr2: RW<u32>,
-    pub dx7_gcr3: RW<u32>,
-    pub dx7_gcr4: RW<u32>,
-    pub dx7_gcr5: RW<u32>,
-    pub dx7_gcr6: RW<u32>,
+    pub dx7_gcr0: DXnGCR0,
+    pub dx7_gcr1: DXnGCR1,
+    pub dx7_gcr2: DXnGCR2,
+    pub dx7_gcr3: DXnGCR3,
+    pub dx7_gcr4: DXnGCR4,
+    pub dx7_gcr5: DXnGCR5,
+    pub dx7_gcr6: DXnGCR6,
     unused86: [u32; 9],
     pub dx7_bdlr0: RW<u32>,
     pub dx7_bdlr1: RW<u32>,
@@ -487,13 +490,13 @@ pub struct RegisterBlock {
     pub dx7_gsr2: RW<u32>,
     pub dx7_gsr3: RW<u32>,
     unused93: [u32; 4],
-    pub dx8_gcr0: RW<u32>,
-    pub dx8_gcr1: RW<u32>,
-    pub dx8_gcr2: RW<u32>,
-    pub dx8_gcr3: RW<u32>,
-    pub dx8_gcr4: RW<u32>,
-    pub dx8_gcr5: RW<u32>,
-    pub dx8_gcr6: RW<u32>,
+    pub dx8_gcr0: DXnGCR0,
+    pub dx8_gcr1: DXnGCR1,
+    pub dx8_gcr2: DXnGCR2,
+    pub dx8_gcr3: DXnGCR3,
+    pub dx8_gcr4: DXnGCR4,
+    pub dx8_gcr5: DXnGCR5,
+    pub dx8_gcr6: DXnGCR6,
     unused94: [u32; 9],
     pub dx8_bdlr0: RW<u32>,
     pub dx8_bdlr1: RW<u32>,
@@ -525,92 +528,165 @@ pub struct RegisterBlock {
     pub dx8_gsr2: RW<u32>,
     pub dx8_gsr3: RW<u32>,
     unused101: [u32; 260],
-    pub dx8_sl0_osc: RW<u32>,
-    pub dx8_sl0_pllcr0: RW<u32>,
+    pub dx8_sl0_osc: DX8SLnOSC,
+    pub dx8_sl0_pllcr0: PLLCR0,
     pub dx8_sl0_pllcr1: RW<u32>,
     pub dx8_sl0_pllcr2: RW<u32>,
     pub dx8_sl0_pllcr3: RW<u32>,
     pub dx8_sl0_pllcr4: RW<u32>,
     pub dx8_sl0_pllcr5: RW<u32>,
-    pub dx8_sl0_dqsctl: RW<u32>,
+    pub dx8_sl0_dqsctl: DX8SLnDQSCTL,
     pub dx8_sl0_trnctl: RW<u32>,
     pub dx8_sl0_ddlctl: RW<u32>,
     pub dx8_sl0_dxctl1: RW<u32>,
-    pub dx8_sl0_dxctl2: RW<u32>,
-    pub dx8_sl0_iocr: RW<u32>,
+    pub dx8_sl0_dxctl2: DX8SLnDXCTL2,
+    pub dx8_sl0_iocr: DX8SLnIOCR,
     unused102: [u32; 3],
-    pub dx8_sl1_osc: RW<u32>,
-    pub dx8_sl1_pllcr0: RW<u32>,
+    pub dx8_sl1_osc: DX8SLnOSC,
+    pub dx8_sl1_pllcr0: PLLCR0,
     pub dx8_sl1_pllcr1: RW<u32>,
     pub dx8_sl1_pllcr2: RW<u32>,
     pub dx8_sl1_pllcr3: RW<u32>,
     pub dx8_sl1_pllcr4: RW<u32>,
     pub dx8_sl1_pllcr5: RW<u32>,
-    pub dx8_sl1_dqsctl: RW<u32>,
+    pub dx8_sl1_dqsctl: DX8SLnDQSCTL,
     pub dx8_sl1_trnctl: RW<u32>,
     pub dx8_sl1_ddlctl: RW<u32>,
     pub dx8_sl1_dxctl1: RW<u32>,
-    pub dx8_sl1_dxctl2: RW<u32>,
-    pub dx8_sl1_iocr: RW<u32>,
+    pub dx8_sl1_dxctl2: DX8SLnDXCTL2,
+    pub dx8_sl1_iocr: DX8SLnIOCR,
     unused103: [u32; 3],
-    pub dx8_sl2_osc: RW<u32>,
-    pub dx8_sl2_pllcr0: RW<u32>,
+    pub dx8_sl2_osc: DX8SLnOSC,
+    pub dx8_sl2_pllcr0: PLLCR0,
     pub dx8_sl2_pllcr1: RW<u32>,
     pub dx8_sl2_pllcr2: RW<u32>,
     pub dx8_sl2_pllcr3: RW<u32>,
     pub dx8_sl2_pllcr4: RW<u32>,
     pub dx8_sl2_pllcr5: RW<u32>,
-    pub dx8_sl2_dqsctl: RW<u32>,
+    pub dx8_sl2_dqsctl: DX8SLnDQSCTL,
     pub dx8_sl2_trnctl: RW<u32>,
     pub dx8_sl2_ddlctl: RW<u32>,
     pub dx8_sl2_dxctl1: RW<u32>,
-    pub dx8_sl2_dxctl2: RW<u32>,
-    pub dx8_sl2_iocr: RW<u32>,
+    pub dx8_sl2_dxctl2: DX8SLnDXCTL2,
+    pub dx8_sl2_iocr: DX8SLnIOCR,
     unused104: [u32; 3],
-    pub dx8_sl3_osc: RW<u32>,
-    pub dx8_sl3_pllcr0: RW<u32>,
+    pub dx8_sl3_osc: DX8SLnOSC,
+    pub dx8_sl3_pllcr0: PLLCR0,
     pub dx8_sl3_pllcr1: RW<u32>,
     pub dx8_sl3_pllcr2: RW<u32>,
     pub dx8_sl3_pllcr3: RW<u32>,
     pub dx8_sl3_pllcr4: RW<u32>,
     pub dx8_sl3_pllcr5: RW<u32>,
-    pub dx8_sl3_dqsctl: RW<u32>,
+    pub dx8_sl3_dqsctl: DX8SLnDQSCTL,
     pub dx8_sl3_trnctl: RW<u32>,
     pub dx8_sl3_ddlctl: RW<u32>,
     pub dx8_sl3_dxctl1: RW<u32>,
-    pub dx8_sl3_dxctl2: RW<u32>,
-    pub dx8_sl3_iocr: RW<u32>,
+    pub dx8_sl3_dxctl2: DX8SLnDXCTL2,
+    pub dx8_sl3_iocr: DX8SLnIOCR,
     unused105: [u32; 3],
-    pub dx8_sl4_osc: RW<u32>,
-    pub dx8_sl4_pllcr0: RW<u32>,
+    pub dx8_sl4_osc: DX8SLnOSC,
+    pub dx8_sl4_pllcr0: PLLCR0,
     pub dx8_sl4_pllcr1: RW<u32>,
     pub dx8_sl4_pllcr2: RW<u32>,
     pub dx8_sl4_pllcr3: RW<u32>,
     pub dx8_sl4_pllcr4: RW<u32>,
     pub dx8_sl4_pllcr5: RW<u32>,
-    pub dx8_sl4_dqsctl: RW<u32>,
+    pub dx8_sl4_dqsctl: DX8SLnDQSCTL,
     pub dx8_sl4_trnctl: RW<u32>,
     pub dx8_sl4_ddlctl: RW<u32>,
     pub dx8_sl4_dxctl1: RW<u32>,
-    pub dx8_sl4_dxctl2: RW<u32>,
-    pub dx8_sl4_iocr: RW<u32>,
+    pub dx8_sl4_dxctl2: DX8SLnDXCTL2,
+    pub dx8_sl4_iocr: DX8SLnIOCR,
     unused106: [u32; 163],
-    pub dx8_slb_osc: RW<u32>,
-    pub dx8_slb_pllcr0: RW<u32>,
+    pub dx8_slb_osc: DX8SLnOSC,
+    pub dx8_slb_pllcr0: PLLCR0,
     pub dx8_slb_pllcr1: RW<u32>,
     pub dx8_slb_pllcr2: RW<u32>,
     pub dx8_slb_pllcr3: RW<u32>,
     pub dx8_slb_pllcr4: RW<u32>,
     pub dx8_slb_pllcr5: RW<u32>,
-    pub dx8_slb_dqsctl: RW<u32>,
+    pub dx8_slb_dqsctl: DX8SLnDQSCTL,
     pub dx8_slb_trnctl: RW<u32>,
     pub dx8_slb_ddlctl: RW<u32>,
     pub dx8_slb_dxctl1: RW<u32>,
-    pub dx8_slb_dxctl2: RW<u32>,
-    pub dx8_slb_iocr: RW<u32>,
+    pub dx8_slb_dxctl2: DX8SLnDXCTL2,
+    pub dx8_slb_iocr: DX8SLnIOCR,
 }
 
 register_at!(RegisterBlock, 0xFD08_0000, ddr_phy);
+assert_layout!(RegisterBlock, 0xFD08_0000, {
+    phy_init => 0xFD08_0004,
+    pgcr0 => 0xFD08_0010,
+    pgcr7 => 0xFD08_002C,
+    pgsr0 => 0xFD08_0030,
+    pgsr2 => 0xFD08_0038,
+    ptr0 => 0xFD08_0040,
+    ptr6 => 0xFD08_0058,
+    pll_ctrl0 => 0xFD08_0068,
+    pll_ctrl5 => 0xFD08_007C,
+    dx_cfg => 0xFD08_0088,
+    ddr_sys_cfg => 0xFD08_0090,
+    odt_cfg => 0xFD08_0098,
+    aa_ctrl => 0xFD08_00A0,
+    gpr0 => 0xFD08_00C0,
+    gpr1 => 0xFD08_00C4,
+    dram_cfg => 0xFD08_0100,
+    dtpr0 => 0xFD08_0110,
+    dtpr6 => 0xFD08_0128,
+    rdimm_cfg0 => 0xFD08_0140,
+    rdimm_ctrl0 => 0xFD08_0150,
+    sched_cmd0 => 0xFD08_0168,
+    mode0 => 0xFD08_0180,
+    mode6 => 0xFD08_0198,
+    mode11 => 0xFD08_01AC,
+    mode14 => 0xFD08_01B8,
+    mode22 => 0xFD08_01D8,
+    dt_cfg0 => 0xFD08_0200,
+    dt_addr0 => 0xFD08_0208,
+    dt_data0 => 0xFD08_0218,
+    dt_eye_data0 => 0xFD08_0230,
+    vt_data => 0xFD08_023C,
+    catr0 => 0xFD08_0240,
+    dqs_drift0 => 0xFD08_0250,
+    dt_eye_data3 => 0xFD08_025C,
+    rio_cr5 => 0xFD08_04F4,
+    aio_cr0 => 0xFD08_0500,
+    io_vcr0 => 0xFD08_0520,
+    vtcr0 => 0xFD08_0528,
+    ac_bdlr0 => 0xFD08_0540,
+    ac_bdlr9 => 0xFD08_0564,
+    ac_mdlr0 => 0xFD08_05A0,
+    zqcr => 0xFD08_0680,
+    zq0_pr0 => 0xFD08_0684,
+    zq0_sr => 0xFD08_069C,
+    zq1_pr0 => 0xFD08_06A4,
+    zq1_sr => 0xFD08_06BC,
+    dx0_gcr0 => 0xFD08_0700,
+    dx0_bdlr0 => 0xFD08_0740,
+    dx0_lcdlr0 => 0xFD08_0780,
+    dx0_mdlr0 => 0xFD08_07A0,
+    dx0_gtr0 => 0xFD08_07C0,
+    dx0_gsr0 => 0xFD08_07E0,
+    dx1_gcr0 => 0xFD08_0800,
+    dx2_gcr0 => 0xFD08_0900,
+    dx3_gcr0 => 0xFD08_0A00,
+    dx4_gcr0 => 0xFD08_0B00,
+    dx5_gcr0 => 0xFD08_0C00,
+    dx6_gcr0 => 0xFD08_0D00,
+    dx7_gcr0 => 0xFD08_0E00,
+    dx8_gcr0 => 0xFD08_0F00,
+    dx8_gsr0 => 0xFD08_0FE0,
+    dx8_sl0_osc => 0xFD08_1400,
+    dx8_sl0_dqsctl => 0xFD08_141C,
+    dx8_sl0_dxctl2 => 0xFD08_142C,
+    dx8_sl0_iocr => 0xFD08_1430,
+    dx8_sl1_osc => 0xFD08_1440,
+    dx8_sl2_osc => 0xFD08_1480,
+    dx8_sl3_osc => 0xFD08_14C0,
+    dx8_sl4_osc => 0xFD08_1500,
+    dx8_slb_osc => 0xFD08_17C0,
+    dx8_slb_dqsctl => 0xFD08_17DC,
+});
 
 // PIR
 register!(phy_init, PhyInit, RW, u32);
@@ -636,6 +712,191 @@ register_bit!(phy_init, lpddr3_ca, 2);
 register_bit!(phy_init, zcal, 1);
 register_bit!(phy_init, init, 0, WTC);
 
+register!(pgcr0, PGCR0, RW, u32);
+register_bit!(pgcr0, adcp, 31);
+register_bit!(pgcr0, phy_frst, 26);
+register_bits!(pgcr0, osc_acdl, u8, 24, 25);
+register_bits!(pgcr0, dto_sel, u8, 14, 18);
+register_bits!(pgcr0, osc_div, u8, 9, 12);
+register_bit!(pgcr0, osc_en, 8);
+
+register!(pgcr2, PGCR2, RW, u32);
+register_bit!(pgcr2, clr_training, 31);
+register_bit!(pgcr2, clr_zcal, 30);
+register_bit!(pgcr2, clr_perr, 29);
+register_bit!(pgcr2, icpc, 28);
+register_bits!(pgcr2, dtp_mx_tmr, u8, 20, 27);
+register_bit!(pgcr2, init_fsm_byp, 19);
+register_bit!(pgcr2, pll_fsm_byp, 18);
+// DRAM clocks between refreshes during PHY-driven training
+register_bits!(pgcr2, t_refprd, u32, 0, 17);
+
+register!(pgcr3, PGCR3, RW, u32);
+// one bit per CK/CK# output
+register_bits!(pgcr3, ckn_en, u8, 24, 31);
+register_bits!(pgcr3, ck_en, u8, 16, 23);
+register_bits!(pgcr3, gate_ac_rd_clk, u8, 13, 14);
+register_bits!(pgcr3, gate_ac_ddr_clk, u8, 11, 12);
+register_bits!(pgcr3, gate_ac_ctl_clk, u8, 9, 10);
+register_bits!(pgcr3, ddl_byp_mode, u8, 6, 7);
+register_bit!(pgcr3, io_lb, 5);
+register_bits!(pgcr3, rd_mode, u8, 3, 4);
+register_bit!(pgcr3, dis_rst, 2);
+register_bits!(pgcr3, clk_level, u8, 0, 1);
+
+register!(pgcr5, PGCR5, RW, u32);
+register_bits!(pgcr5, frq_bt, u8, 24, 31);
+register_bits!(pgcr5, frq_at, u8, 16, 23);
+register_bits!(pgcr5, dis_cn_period, u8, 8, 15);
+register_bits!(pgcr5, vref_rb_ctrl, u8, 4, 7);
+register_bit!(pgcr5, dx_ref_isel_range, 2);
+register_bit!(pgcr5, ddl_pg_act, 1);
+register_bit!(pgcr5, ddl_pg_rw, 0);
+
+// PLLCR0 and DX8SLnPLLCR0
+register!(pllcr0, PLLCR0, RW, u32);
+register_bit!(pllcr0, pll_byp, 31);
+register_bit!(pllcr0, pll_rst, 30);
+register_bit!(pllcr0, pll_pd, 29);
+register_bit!(pllcr0, rst_opm, 28);
+register_bits!(pllcr0, frq_sel, u8, 24, 27);
+register_bit!(pllcr0, rlock_m, 23);
+register_bits!(pllcr0, cppc, u8, 17, 22);
+register_bits!(pllcr0, cpic, u8, 13, 16);
+register_bit!(pllcr0, gshift, 12);
+register_bits!(pllcr0, ato_en, u8, 4, 7);
+register_bits!(pllcr0, atc, u8, 0, 3);
+
+register!(ptr0, PTR0, RW, u32);
+register_bits!(ptr0, t_pll_pd, u16, 21, 31);
+register_bits!(ptr0, t_pll_gs, u16, 6, 20);
+register_bits!(ptr0, t_phy_rst, u8, 0, 5);
+
+register!(ptr1, PTR1, RW, u32);
+register_bits!(ptr1, t_pll_lock, u16, 16, 31);
+register_bits!(ptr1, t_pll_rst, u16, 0, 12);
+
+register!(dtpr0, DTPR0, RW, u32);
+register_bits!(dtpr0, t_rrd, u8, 24, 29);
+register_bits!(dtpr0, t_ras, u8, 16, 22);
+register_bits!(dtpr0, t_rp, u8, 8, 14);
+register_bits!(dtpr0, t_rtp, u8, 0, 3);
+
+register!(dtpr1, DTPR1, RW, u32);
+register_bits!(dtpr1, t_wlmrd, u8, 24, 29);
+register_bits!(dtpr1, t_faw, u8, 16, 22);
+// DDR4: tMOD - 24, DDR3: tMOD - 12
+register_bits!(dtpr1, t_mod, u8, 8, 10);
+register_bits!(dtpr1, t_mrd, u8, 0, 4);
+
+register!(dtpr2, DTPR2, RW, u32);
+register_bit!(dtpr2, t_rtw, 28);
+register_bit!(dtpr2, t_rtodt, 24);
+register_bits!(dtpr2, t_cke, u8, 16, 19);
+register_bits!(dtpr2, t_xs, u16, 0, 9);
+
+register!(dtpr3, DTPR3, RW, u32);
+register_bits!(dtpr3, t_ofdx, u8, 29, 31);
+register_bits!(dtpr3, t_ccd, u8, 26, 28);
+register_bits!(dtpr3, t_dllk, u16, 16, 25);
+register_bits!(dtpr3, t_dqsck_max, u8, 8, 11);
+register_bits!(dtpr3, t_dqsck, u8, 0, 2);
+
+register!(dtpr4, DTPR4, RW, u32);
+register_bits!(dtpr4, t_aond_aofd, u8, 28, 29);
+register_bits!(dtpr4, t_rfc, u16, 16, 25);
+register_bits!(dtpr4, t_wlo, u8, 8, 13);
+register_bits!(dtpr4, t_xp, u8, 0, 4);
+
+register!(dtpr5, DTPR5, RW, u32);
+register_bits!(dtpr5, t_rc, u8, 16, 23);
+register_bits!(dtpr5, t_rcd, u8, 8, 14);
+register_bits!(dtpr5, t_wtr, u8, 0, 4);
+
+register!(dtpr6, DTPR6, RW, u32);
+register_bit!(dtpr6, pub_wl_en, 31);
+register_bit!(dtpr6, pub_rl_en, 30);
+register_bits!(dtpr6, pub_wl, u8, 8, 13);
+register_bits!(dtpr6, pub_rl, u8, 0, 5);
+
+register!(rdimm_cfg0, RDIMMGCR0, RW, u32);
+register_bit!(rdimm_cfg0, rdimm_iom, 27);
+register_bit!(rdimm_cfg0, errout_oe, 23);
+register_bit!(rdimm_cfg0, errout_iom, 22);
+register_bit!(rdimm_cfg0, lrdimm, 18);
+register_bit!(rdimm_cfg0, parin_iom, 17);
+// one bit per rank, sends mirrored MRS to the RCD of that rank
+register_bits!(rdimm_cfg0, rnk_mrr_en, u8, 4, 5);
+register_bit!(rdimm_cfg0, sop_err, 2);
+register_bit!(rdimm_cfg0, err_no_reg, 1);
+register_bit!(rdimm_cfg0, rdimm, 0);
+
+// RCD control words RC00-RC0F (JESD82-31), written by the PHY during RDIMM init
+register!(rdimm_ctrl0, RDIMMCR0, RW, u32);
+register_bits!(rdimm_ctrl0, rc7, u8, 28, 31);
+register_bits!(rdimm_ctrl0, rc6, u8, 24, 27);
+register_bits!(rdimm_ctrl0, rc5, u8, 20, 23);
+register_bits!(rdimm_ctrl0, rc4, u8, 16, 19);
+register_bits!(rdimm_ctrl0, rc3, u8, 12, 15);
+register_bits!(rdimm_ctrl0, rc2, u8, 8, 11);
+register_bits!(rdimm_ctrl0, rc1, u8, 4, 7);
+register_bits!(rdimm_ctrl0, rc0, u8, 0, 3);
+
+register!(rdimm_ctrl1, RDIMMCR1, RW, u32);
+register_bits!(rdimm_ctrl1, rc15, u8, 28, 31);
+register_bits!(rdimm_ctrl1, rc14, u8, 24, 27);
+register_bits!(rdimm_ctrl1, rc13, u8, 20, 23);
+register_bits!(rdimm_ctrl1, rc12, u8, 16, 19);
+register_bits!(rdimm_ctrl1, rc11, u8, 12, 15);
+register_bits!(rdimm_ctrl1, rc10, u8, 8, 11);
+register_bits!(rdimm_ctrl1, rc9, u8, 4, 7);
+register_bits!(rdimm_ctrl1, rc8, u8, 0, 3);
+
+// 8 bit control words RC1x-RC4x
+register!(rdimm_ctrl2, RDIMMCR2, RW, u32);
+register_bits!(rdimm_ctrl2, rc4x, u8, 24, 31);
+register_bits!(rdimm_ctrl2, rc3x, u8, 16, 23);
+register_bits!(rdimm_ctrl2, rc2x, u8, 8, 15);
+register_bits!(rdimm_ctrl2, rc1x, u8, 0, 7);
+
+register!(dtcr0, DTCR0, RW, u32);
+// refreshes issued before training, and at its end
+register_bits!(dtcr0, rfsh_dt, u8, 28, 31);
+register_bits!(dtcr0, dt_drs, u8, 24, 25);
+register_bit!(dtcr0, dt_exg, 23);
+register_bit!(dtcr0, dt_exd, 22);
+register_bit!(dtcr0, dt_dstp, 21);
+register_bit!(dtcr0, dt_den, 20);
+register_bits!(dtcr0, dt_dbs, u8, 16, 19);
+register_bits!(dtcr0, dt_rd_bitr, u8, 14, 15);
+register_bit!(dtcr0, dt_wb_ddm, 12);
+register_bits!(dtcr0, rfsh_ent, u8, 8, 11);
+register_bit!(dtcr0, dt_cmpd, 7);
+register_bit!(dtcr0, dt_mpr, 6);
+register_bits!(dtcr0, dt_rptn, u8, 0, 3);
+
+// LPDDR CA training
+register!(catr0, CATR0, RW, u32);
+register_bits!(catr0, ca_cd, u8, 16, 20);
+register_bits!(catr0, ca_adr, u8, 8, 12);
+register_bits!(catr0, ca1_byte1, u8, 4, 7);
+register_bits!(catr0, ca1_byte0, u8, 0, 3);
+
+// mode register images sent to the DRAM during PHY-driven init and training
+macro_rules! mode_reg {
+    ($mod_name: ident, $struct_name: ident) => {
+        register!($mod_name, $struct_name, RW, u32);
+        register_bits!($mod_name, mr, u16, 0, 15);
+    };
+}
+mode_reg!(mr0, MR0);
+mode_reg!(mr1, MR1);
+mode_reg!(mr2, MR2);
+mode_reg!(mr3, MR3);
+mode_reg!(mr4, MR4);
+mode_reg!(mr5, MR5);
+mode_reg!(mr6, MR6);
+
 register!(pgsr0, PGSR0, RO, u32);
 register_bit!(pgsr0, ap_lock, 31);
 register_bit!(pgsr0, ca_wrn, 29);
@@ -669,6 +930,42 @@ register_bit!(pgsr0, pl_done, 1);
 // or triggering new PIR.INIT
 register_bit!(pgsr0, i_done, 0);
 
+register!(zqcr, ZQCR, RW, u32);
+register_bits!(zqcr, pgwait_frqb, u8, 19, 24);
+register_bits!(zqcr, pgwait_frqa, u8, 13, 18);
+register_bit!(zqcr, asym_drv_en, 12);
+register_bit!(zqcr, pu_odt_only, 11);
+register_bits!(zqcr, odt_mode, u8, 9, 10);
+register_bit!(zqcr, force_zcal_vt_update, 8);
+register_bits!(zqcr, io_dlmt, u8, 5, 7);
+register_bit!(zqcr, avg_en, 4);
+register_bits!(zqcr, avg_max, u8, 2, 3);
+register_bit!(zqcr, term_off, 0);
+
+// programmed impedances, as RZQ divisors
+register!(zqn_pr0, ZQnPR0, RW, u32);
+register_bit!(zqn_pr0, pd_drv_zden, 27);
+register_bit!(zqn_pr0, pu_drv_zden, 26);
+register_bit!(zqn_pr0, pd_odt_zden, 25);
+register_bit!(zqn_pr0, pu_odt_zden, 24);
+register_bit!(zqn_pr0, zseg_byp, 23);
+register_bits!(zqn_pr0, odt_adjust, u8, 20, 22);
+register_bits!(zqn_pr0, pu_drv_adjust, u8, 18, 19);
+register_bits!(zqn_pr0, pd_drv_adjust, u8, 16, 17);
+register_bits!(zqn_pr0, zprog_dram_odt, u8, 12, 15);
+register_bits!(zqn_pr0, zprog_host_odt, u8, 8, 11);
+register_bits!(zqn_pr0, zprog_asym_drv_pd, u8, 4, 7);
+register_bits!(zqn_pr0, zprog_asym_drv_pu, u8, 0, 3);
+
+// impedance overrides, used with ZQnDR0/1 ZDATA override enabled
+register!(zqn_or0, ZQnOR0, RW, u32);
+register_bits!(zqn_or0, zdata_pd_drv, u16, 16, 25);
+register_bits!(zqn_or0, zdata_pu_drv, u16, 0, 9);
+
+register!(zqn_or1, ZQnOR1, RW, u32);
+register_bits!(zqn_or1, zdata_pd_odt, u16, 16, 25);
+register_bits!(zqn_or1, zdata_pu_odt, u16, 0, 9);
+
 register!(zqnsr, ZQnSR, RO, u32);
 register_bit!(zqnsr, pd_odt_sat, 13);
 register_bit!(zqnsr, pu_odt_sat, 12);
@@ -685,3 +982,116 @@ register_bits_typed!(zqnsr, zpd, u8, ZCalStatus, 2, 3);
 register!(dxn_gsr0, DXnGSR0, RO, u32);
 register_bit!(dxn_gsr0, dp_lock, 16);
 // other bits excluded for now
+
+register!(dxn_gcr0, DXnGCR0, RW, u32);
+register_bit!(dxn_gcr0, cal_byp, 31);
+register_bit!(dxn_gcr0, mdl_en, 30);
+register_bits!(dxn_gcr0, rd_dly, u8, 20, 23);
+register_bit!(dxn_gcr0, dqsn_se_pdr, 13);
+register_bit!(dxn_gcr0, dqs_se_pdr, 12);
+register_bits!(dxn_gcr0, rtt_oh, u8, 9, 10);
+register_bit!(dxn_gcr0, dqs_rpd, 6);
+register_bit!(dxn_gcr0, dqsg_pdr, 5);
+register_bit!(dxn_gcr0, dqsg_oe, 2);
+
+register!(dxn_gcr1, DXnGCR1, RW, u32);
+// 2 bits per DQ, see IoMode
+register_bits!(dxn_gcr1, dx_pdr_mode, u16, 16, 31);
+register_bit!(dxn_gcr1, qsn_sel, 14);
+register_bit!(dxn_gcr1, qs_sel, 13);
+register_bit!(dxn_gcr1, oe_en, 12);
+register_bit!(dxn_gcr1, pdr_en, 11);
+register_bit!(dxn_gcr1, te_en, 10);
+register_bit!(dxn_gcr1, ds_en, 9);
+register_bit!(dxn_gcr1, dm_en, 8);
+register_bits!(dxn_gcr1, dq_en, u8, 0, 7);
+
+register!(dxn_gcr2, DXnGCR2, RW, u32);
+register_bits!(dxn_gcr2, dx_oe_mode, u16, 16, 31);
+register_bits!(dxn_gcr2, dx_te_mode, u16, 0, 15);
+
+register!(dxn_gcr3, DXnGCR3, RW, u32);
+register_bit!(dxn_gcr3, rd_bvt, 29);
+register_bit!(dxn_gcr3, wd_bvt, 28);
+register_bit!(dxn_gcr3, rgl_vt, 27);
+register_bit!(dxn_gcr3, rdl_vt, 26);
+register_bit!(dxn_gcr3, wdl_vt, 25);
+register_bit!(dxn_gcr3, wll_vt, 24);
+register_bits_typed!(dxn_gcr3, dsn_oe_mode, u8, IoMode, 20, 21);
+register_bits_typed!(dxn_gcr3, dsn_te_mode, u8, IoMode, 18, 19);
+register_bits_typed!(dxn_gcr3, dsn_pdr_mode, u8, IoMode, 16, 17);
+register_bits_typed!(dxn_gcr3, dm_oe_mode, u8, IoMode, 14, 15);
+register_bits_typed!(dxn_gcr3, dm_te_mode, u8, IoMode, 12, 13);
+register_bits_typed!(dxn_gcr3, dm_pdr_mode, u8, IoMode, 10, 11);
+register_bits_typed!(dxn_gcr3, ds_oe_mode, u8, IoMode, 6, 7);
+register_bits_typed!(dxn_gcr3, ds_te_mode, u8, IoMode, 4, 5);
+register_bits_typed!(dxn_gcr3, ds_pdr_mode, u8, IoMode, 2, 3);
+
+// byte lane VREF generators
+register!(dxn_gcr4, DXnGCR4, RW, u32);
+register_bits!(dxn_gcr4, dx_ref_iom, u8, 29, 31);
+register_bit!(dxn_gcr4, dx_ref_pen, 28);
+register_bits!(dxn_gcr4, dx_ref_een, u8, 26, 27);
+register_bit!(dxn_gcr4, dx_ref_sen, 25);
+register_bit!(dxn_gcr4, dx_ref_esel_range, 23);
+register_bits!(dxn_gcr4, dx_ref_esel, u8, 16, 22);
+register_bit!(dxn_gcr4, dx_ref_ssel_range, 15);
+register_bits!(dxn_gcr4, dx_ref_ssel, u8, 8, 14);
+// one bit per group of DQ IOs using the internal VREF
+register_bits!(dxn_gcr4, dx_ref_ien, u8, 2, 5);
+register_bits!(dxn_gcr4, dx_ref_imon, u8, 0, 1);
+
+// host side (read) VREF per rank
+register!(dxn_gcr5, DXnGCR5, RW, u32);
+register_bits!(dxn_gcr5, dx_ref_isel_r3, u8, 24, 30);
+register_bits!(dxn_gcr5, dx_ref_isel_r2, u8, 16, 22);
+register_bits!(dxn_gcr5, dx_ref_isel_r1, u8, 8, 14);
+register_bits!(dxn_gcr5, dx_ref_isel_r0, u8, 0, 6);
+
+// DRAM side (write) VREF per rank, the VREF training start value
+register!(dxn_gcr6, DXnGCR6, RW, u32);
+register_bits!(dxn_gcr6, dx_dq_vref_r3, u8, 24, 29);
+register_bits!(dxn_gcr6, dx_dq_vref_r2, u8, 16, 21);
+register_bits!(dxn_gcr6, dx_dq_vref_r1, u8, 8, 13);
+register_bits!(dxn_gcr6, dx_dq_vref_r0, u8, 0, 5);
+
+// DX8SLn registers control each of the byte lane slices (two lanes per slice), DX8SLb all of them
+register!(dx8sln_osc, DX8SLnOSC, RW, u32);
+register_bits!(dx8sln_osc, gate_dx_rd_clk, u8, 30, 31);
+register_bits!(dx8sln_osc, gate_dx_ddr_clk, u8, 28, 29);
+register_bits!(dx8sln_osc, gate_dx_ctl_clk, u8, 26, 27);
+register_bits!(dx8sln_osc, clk_level, u8, 24, 25);
+register_bit!(dx8sln_osc, phy_hrst, 16);
+register_bit!(dx8sln_osc, phy_frst, 15);
+register_bits!(dx8sln_osc, osc_wddl, u8, 9, 12);
+register_bits!(dx8sln_osc, osc_wdl, u8, 5, 8);
+register_bits!(dx8sln_osc, osc_div, u8, 1, 4);
+register_bit!(dx8sln_osc, osc_en, 0);
+
+register!(dx8sln_dqsctl, DX8SLnDQSCTL, RW, u32);
+register_bit!(dx8sln_dqsctl, rrr_mode, 24);
+register_bit!(dx8sln_dqsctl, wrr_mode, 21);
+register_bits!(dx8sln_dqsctl, dqsg_x, u8, 19, 20);
+register_bit!(dx8sln_dqsctl, lp_pll_pd, 18);
+register_bit!(dx8sln_dqsctl, lp_io_pd, 17);
+register_bit!(dx8sln_dqsctl, qs_cnt_en, 14);
+register_bit!(dx8sln_dqsctl, udq_iom, 13);
+register_bits!(dx8sln_dqsctl, dx_sr, u8, 8, 9);
+register_bits!(dx8sln_dqsctl, dqsn_res, u8, 4, 7);
+register_bits!(dx8sln_dqsctl, dqs_res, u8, 0, 3);
+
+register!(dx8sln_dxctl2, DX8SLnDXCTL2, RW, u32);
+register_bit!(dx8sln_dxctl2, crd_en, 23);
+register_bits!(dx8sln_dxctl2, pos_oex, u8, 20, 22);
+register_bits!(dx8sln_dxctl2, pre_oex, u8, 18, 19);
+register_bits!(dx8sln_dxctl2, lp_wakeup_thrsh, u8, 9, 12);
+register_bits!(dx8sln_dxctl2, rd_mode, u8, 4, 5);
+register_bit!(dx8sln_dxctl2, dis_rst, 3);
+register_bits!(dx8sln_dxctl2, dqsg_lb, u8, 1, 2);
+
+register!(dx8sln_iocr, DX8SLnIOCR, RW, u32);
+register_bits!(dx8sln_iocr, dx_dac_range, u8, 28, 30);
+register_bits!(dx8sln_iocr, dx_vref_iom, u8, 25, 27);
+register_bits!(dx8sln_iocr, dx_iom, u8, 22, 24);
+register_bits!(dx8sln_iocr, dx_txm, u16, 11, 21);
+register_bits!(dx8sln_iocr, dx_rxm, u16, 0, 10);
@@ -4,7 +4,7 @@ use volatile_register::{RO, RW};
 #[allow(unused)]
 #[repr(u8)]
 pub enum DeviceConfig {
-    Reserved = 0b00,
+    X4 = 0b00,
     X8 = 0b01,
     X16 = 0b10,
     X32 = 0b11,
@@ -26,6 +26,19 @@ pub enum DataBusWidth {
     Quarter = 0b10,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ControllerStatus {
+    Init = 0,
+    Normal = 1,
+    Powerdown = 2,
+    SelfRefresh = 3,
+    Powerdown1 = 4,
+    Powerdown2 = 5,
+    Powerdown3 = 6,
+    Powerdown4 = 7,
+}
+
 #[repr(C)]
 pub struct RegisterBlock {
     pub master: Master,
@@ -88,32 +101,33 @@ pub struct RegisterBlock {
     pub dram_tmg2: DramTmg2,
     pub dram_tmg3: DramTmg3,
     pub dram_tmg4: DramTmg4,
-    pub dram_tmg5: RW<u32>,
-    pub dram_tmg6: RW<u32>,
-    pub dram_tmg7: RW<u32>,
+    pub dram_tmg5: DramTmg5,
+    pub dram_tmg6: DramTmg6,
+    pub dram_tmg7: DramTmg7,
     pub dram_tmg8: DramTmg8,
     pub dram_tmg9: DramTmg9,
     pub dram_tmg10: RW<u32>,
-    pub dram_tmg11: RW<u32>,
+    pub dram_tmg11: DramTmg11,
     pub dram_tmg12: DramTmg12,
-    pub dram_tmg13: RW<u32>,
-    pub dram_tmg14: RW<u32>,
+    pub dram_tmg13: DramTmg13,
+    pub dram_tmg14: DramTmg14,
     unused7: [u32; 17],
-    pub zq_ctrl0: RW<u32>,
-    pub zq_ctrl1: RW<u32>,
+    pub zq_ctrl0: ZqCtrl0,
+    pub zq_ctrl1: ZqCtrl1,
     pub zq_ctrl2: RW<u32>,
     pub zq_status: RO<u32>,
-    pub dfi_tmg0: RW<u32>,
-    pub dfi_tmg1: RW<u32>,
+    pub dfi_tmg0: DfiTmg0,
+    pub dfi_tmg1: DfiTmg1,
     pub dfi_lp_cfg0: RW<u32>,
     pub dfi_lp_cfg1: RW<u32>,
-    pub dfi_update0: RW<u32>,
-    pub dfi_update1: RW<u32>,
-    pub dfi_update2: RW<u32>,
+    pub dfi_update0: DfiUpdate0,
+    pub dfi_update1: DfiUpdate1,
+    pub dfi_update2: DfiUpdate2,
     unused8: [u32; 1],
-    pub dfi_misc: RW<u32>,
+    pub dfi_misc: DfiMisc,
     pub dfi_tmg2: RW<u32>,
-    unused9: [u32; 2],
+    unused9: [u32; 1],
+    pub dfi_status: DfiStatus,
     pub dbi_ctrl: RW<u32>,
     unused10: [u32; 15],
     pub addr_map0: AddrMap0,
@@ -129,8 +143,8 @@ pub struct RegisterBlock {
     pub addr_map10: AddrMap10,
     pub addr_map11: AddrMap11,
     unused11: [u32; 4],
-    pub odt_cfg: RW<u32>,
-    pub odt_map: RW<u32>,
+    pub odt_cfg: OdtCfg,
+    pub odt_map: OdtMap,
     unused12: [u32; 2],
     pub sched: RW<u32>,
     pub sched1: RW<u32>,
@@ -157,8 +171,8 @@ pub struct RegisterBlock {
     pub dbg_cmd: RW<u32>,
     pub dbg_status: RO<u32>,
     unused19: [u32; 3],
-    pub sw_ctrl: RW<u32>,
-    pub sw_status: RO<u32>,
+    pub sw_ctrl: SwCtrl,
+    pub sw_status: SwStatus,
     unused20: [u32; 17],
     pub poison_cfg: RW<u32>,
     pub poison_status: RO<u32>,
@@ -262,6 +276,92 @@ pub struct RegisterBlock {
     pub odt_cfg_shadow: RW<u32>,
 }
 register_at!(RegisterBlock, 0xFD07_0000, ddrc);
+assert_layout!(RegisterBlock, 0xFD07_0000, {
+    master => 0xFD07_0000,
+    status => 0xFD07_0004,
+    mode_ctrl0 => 0xFD07_0010,
+    mode_ctrl2 => 0xFD07_001C,
+    derate_en => 0xFD07_0020,
+    derate_interval => 0xFD07_0024,
+    power_ctrl => 0xFD07_0030,
+    power_timing => 0xFD07_0034,
+    hw_lp_ctrl => 0xFD07_0038,
+    refresh_ctrl0 => 0xFD07_0050,
+    refresh_ctrl1 => 0xFD07_0054,
+    refresh_ctrl3 => 0xFD07_0060,
+    refresh_timing => 0xFD07_0064,
+    ecc_cfg0 => 0xFD07_0070,
+    ecc_poison_addr1 => 0xFD07_00BC,
+    crc_par_ctrl0 => 0xFD07_00C0,
+    crc_par_status => 0xFD07_00CC,
+    init0 => 0xFD07_00D0,
+    init3 => 0xFD07_00DC,
+    init7 => 0xFD07_00EC,
+    dimm_ctrl => 0xFD07_00F0,
+    rank_ctrl => 0xFD07_00F4,
+    dram_tmg0 => 0xFD07_0100,
+    dram_tmg8 => 0xFD07_0120,
+    dram_tmg14 => 0xFD07_0138,
+    zq_ctrl0 => 0xFD07_0180,
+    zq_status => 0xFD07_018C,
+    dfi_tmg0 => 0xFD07_0190,
+    dfi_lp_cfg0 => 0xFD07_0198,
+    dfi_update0 => 0xFD07_01A0,
+    dfi_update2 => 0xFD07_01A8,
+    dfi_misc => 0xFD07_01B0,
+    dfi_tmg2 => 0xFD07_01B4,
+    dfi_status => 0xFD07_01BC,
+    dbi_ctrl => 0xFD07_01C0,
+    addr_map0 => 0xFD07_0200,
+    addr_map11 => 0xFD07_022C,
+    odt_cfg => 0xFD07_0240,
+    odt_map => 0xFD07_0244,
+    sched => 0xFD07_0250,
+    perf_hpr1 => 0xFD07_025C,
+    perf_lpr1 => 0xFD07_0264,
+    perf_wr1 => 0xFD07_026C,
+    perf_vpr1 => 0xFD07_0274,
+    perf_vpw1 => 0xFD07_0278,
+    dq_map0 => 0xFD07_0280,
+    dq_map5 => 0xFD07_0294,
+    dbg0 => 0xFD07_0300,
+    dbg_status => 0xFD07_0310,
+    sw_ctrl => 0xFD07_0320,
+    sw_status => 0xFD07_0324,
+    poison_cfg => 0xFD07_036C,
+    poison_status => 0xFD07_0370,
+    port_status => 0xFD07_03FC,
+    port_common_cfg => 0xFD07_0400,
+    port0_cfgr => 0xFD07_0404,
+    port0_ctrl => 0xFD07_0490,
+    port0_wqos_cfg1 => 0xFD07_04A0,
+    port1_cfgr => 0xFD07_04B4,
+    port1_ctrl => 0xFD07_0540,
+    port2_cfgr => 0xFD07_0564,
+    port2_ctrl => 0xFD07_05F0,
+    port3_cfgr => 0xFD07_0614,
+    port3_ctrl => 0xFD07_06A0,
+    port4_cfgr => 0xFD07_06C4,
+    port4_ctrl => 0xFD07_0750,
+    port5_cfgr => 0xFD07_0774,
+    port5_ctrl => 0xFD07_0800,
+    port5_wqos_cfg1 => 0xFD07_0810,
+    sar_base0 => 0xFD07_0F04,
+    sar_size1 => 0xFD07_0F10,
+    derate_int_shadow => 0xFD07_2024,
+    refresh_ctrl0_shadow => 0xFD07_2050,
+    refresh_timing_shadow => 0xFD07_2064,
+    init3_shadow => 0xFD07_20DC,
+    init6_shadow => 0xFD07_20E8,
+    init7_shadow => 0xFD07_20EC,
+    dram_tmg0_shadow => 0xFD07_2100,
+    dram_tmg14_shadow => 0xFD07_2138,
+    zq_ctrl0_shadow => 0xFD07_2180,
+    dfi_tmg0_shadow => 0xFD07_2190,
+    dfi_tmg1_shadow => 0xFD07_2194,
+    dfi_tmg2_shadow => 0xFD07_21B4,
+    odt_cfg_shadow => 0xFD07_2240,
+});
 
 register!(master, Master, RW, u32);
 register_bits_typed!(master, device_config, u8, DeviceConfig, 30, 31);
@@ -279,7 +379,7 @@ register_bit!(master, ddr3, 0);
 register!(status, Status, RO, u32);
 register_bits!(status, selfref_state, u8, 8, 9);
 register_bits!(status, selfref_type, u8, 4, 5);
-register_bits!(status, operating_mode, u8, 0, 2);
+register_bits_typed!(status, operating_mode, u8, ControllerStatus, 0, 2);
 
 register!(mode_ctrl0, ModeCtrl0, RW, u32);
 register_bit!(mode_ctrl0, mr_wr, 31);
@@ -370,7 +470,11 @@ register_bits!(init3, mr, u16, 16, 31);
 register_bits!(init3, emr, u16, 0, 15);
 
 register!(init4, Init4, RW, u32);
+// DDR3/4: MR2
+// LPDDR4: MR3
 register_bits!(init4, emr2, u16, 16, 31);
+// DDR3/4: MR3
+// LPDDR4: MR13
 register_bits!(init4, emr3, u16, 0, 15);
 
 register!(init5, Init5, RW, u32);
@@ -378,11 +482,19 @@ register_bits!(init5, dev_zqinit_x32, u8, 16, 23);
 register_bits!(init5, max_auto_init_x1024, u16, 0, 9);
 
 register!(init6, Init6, RW, u32);
+// DDR4: MR4
+// LPDDR4: MR11
 register_bits!(init6, mr4, u16, 16, 31);
+// DDR4: MR5
+// LPDDR4: MR12
 register_bits!(init6, mr5, u16, 0, 15);
 
 register!(init7, Init7, RW, u32);
+// DDR4: MR6
+// LPDDR4: MR14
 register_bits!(init7, mr6, u16, 16, 31);
+// LPDDR4: MR22
+register_bits!(init7, mr22, u16, 0, 15);
 
 register!(dimm_ctrl, DimmCtrl, RW, u32);
 register_bit!(dimm_ctrl, dimm_dis_bg_mirroring, 5);
@@ -420,6 +532,22 @@ register_bits!(dram_tmg4, t_ccd, u8, 16, 19);
 register_bits!(dram_tmg4, t_rrd, u8, 8, 11);
 register_bits!(dram_tmg4, t_rp, u8, 0, 4);
 
+register!(dram_tmg5, DramTmg5, RW, u32);
+register_bits!(dram_tmg5, t_cksrx, u8, 24, 27);
+register_bits!(dram_tmg5, t_cksre, u8, 16, 19);
+register_bits!(dram_tmg5, t_ckesr, u8, 8, 13);
+register_bits!(dram_tmg5, t_cke, u8, 0, 4);
+
+// LPDDR only
+register!(dram_tmg6, DramTmg6, RW, u32);
+register_bits!(dram_tmg6, t_ckdpde, u8, 24, 27);
+register_bits!(dram_tmg6, t_ckdpdx, u8, 16, 19);
+register_bits!(dram_tmg6, t_ckcsx, u8, 0, 3);
+
+register!(dram_tmg7, DramTmg7, RW, u32);
+register_bits!(dram_tmg7, t_ckpdx, u8, 8, 11);
+register_bits!(dram_tmg7, t_ckpde, u8, 0, 3);
+
 register!(dram_tmg8, DramTmg8, RW, u32);
 register_bits!(dram_tmg8, t_xs_fast_x32, u8, 24, 30);
 register_bits!(dram_tmg8, t_xs_abort_x32, u8, 16, 22);
@@ -432,11 +560,75 @@ register_bits!(dram_tmg9, t_ccd_s, u8, 16, 18);
 register_bits!(dram_tmg9, t_rrd_s, u8, 8, 11);
 register_bits!(dram_tmg9, wr2rd_s, u8, 0, 5);
 
+// maximum power saving mode
+register!(dram_tmg11, DramTmg11, RW, u32);
+register_bits!(dram_tmg11, post_mpsm_gap_x32, u8, 24, 30);
+register_bits!(dram_tmg11, t_mpx_lh, u8, 16, 20);
+register_bits!(dram_tmg11, t_mpx_s, u8, 8, 9);
+register_bits!(dram_tmg11, t_ckmpe, u8, 0, 4);
+
 register!(dram_tmg12, DramTmg12, RW, u32);
 register_bits!(dram_tmg12, t_cmdcke, u8, 16, 17);
 register_bits!(dram_tmg12, t_ckehcmd, u8, 8, 11);
 register_bits!(dram_tmg12, t_mrd_pda, u8, 0, 4);
 
+// LPDDR4 only
+register!(dram_tmg13, DramTmg13, RW, u32);
+register_bits!(dram_tmg13, odtloff, u8, 24, 30);
+register_bits!(dram_tmg13, t_ccd_mw, u8, 16, 21);
+register_bits!(dram_tmg13, t_ppd, u8, 0, 3);
+
+// LPDDR only
+register!(dram_tmg14, DramTmg14, RW, u32);
+register_bits!(dram_tmg14, t_xsr, u16, 0, 11);
+
+register!(zq_ctrl0, ZqCtrl0, RW, u32);
+register_bit!(zq_ctrl0, dis_auto_zq, 31);
+register_bit!(zq_ctrl0, dis_srx_zqcl, 30);
+register_bit!(zq_ctrl0, zq_resistor_shared, 29);
+register_bit!(zq_ctrl0, dis_mpsmx_zqcl, 28);
+register_bits!(zq_ctrl0, t_zq_long_nop, u16, 16, 26);
+register_bits!(zq_ctrl0, t_zq_short_nop, u16, 0, 9);
+
+register!(zq_ctrl1, ZqCtrl1, RW, u32);
+register_bits!(zq_ctrl1, t_zq_reset_nop, u16, 20, 29);
+register_bits!(zq_ctrl1, t_zq_short_interval_x1024, u32, 0, 19);
+
+register!(dfi_tmg0, DfiTmg0, RW, u32);
+register_bits!(dfi_tmg0, dfi_t_ctrl_delay, u8, 24, 28);
+register_bit!(dfi_tmg0, dfi_rddata_use_sdr, 23);
+register_bits!(dfi_tmg0, dfi_t_rddata_en, u8, 16, 22);
+register_bit!(dfi_tmg0, dfi_wrdata_use_sdr, 15);
+register_bits!(dfi_tmg0, dfi_tphy_wrdata, u8, 8, 13);
+register_bits!(dfi_tmg0, dfi_tphy_wrlat, u8, 0, 5);
+
+register!(dfi_tmg1, DfiTmg1, RW, u32);
+register_bits!(dfi_tmg1, dfi_t_cmd_lat, u8, 28, 31);
+register_bits!(dfi_tmg1, dfi_t_wrdata_delay, u8, 16, 20);
+register_bits!(dfi_tmg1, dfi_t_dram_clk_disable, u8, 8, 12);
+register_bits!(dfi_tmg1, dfi_t_dram_clk_enable, u8, 0, 4);
+
+register!(dfi_update0, DfiUpdate0, RW, u32);
+register_bit!(dfi_update0, dis_auto_ctrlupd, 31);
+register_bit!(dfi_update0, dis_auto_ctrlupd_srx, 30);
+register_bit!(dfi_update0, ctrlupd_pre_srx, 29);
+register_bits!(dfi_update0, dfi_t_ctrlup_max, u16, 16, 25);
+register_bits!(dfi_update0, dfi_t_ctrlup_min, u16, 0, 9);
+
+register!(dfi_update1, DfiUpdate1, RW, u32);
+register_bits!(dfi_update1, dfi_t_ctrlupd_interval_min_x1024, u8, 16, 23);
+register_bits!(dfi_update1, dfi_t_ctrlupd_interval_max_x1024, u8, 0, 7);
+
+register!(dfi_update2, DfiUpdate2, RW, u32);
+register_bit!(dfi_update2, dfi_phyupd_en, 31);
+
+register!(dfi_misc, DfiMisc, RW, u32);
+register_bit!(dfi_misc, dfi_init_complete_en, 0);
+
+register!(dfi_status, DfiStatus, RO, u32);
+register_bit!(dfi_status, dfi_lp_ack, 1);
+register_bit!(dfi_status, dfi_init_complete, 0);
+
 register!(addr_map0, AddrMap0, RW, u32);
 register_bits!(addr_map0, addrmap_cs_bit0, u8, 0, 4);
 
@@ -497,6 +689,19 @@ register_bits!(addr_map10, addrmap_row_b6, u8, 0, 3);
 register!(addr_map11, AddrMap11, RW, u32);
 register_bits!(addr_map11, addrmap_row_b10, u8, 0, 3);
 
+register!(odt_cfg, OdtCfg, RW, u32);
+register_bits!(odt_cfg, wr_odt_hold, u8, 24, 27);
+register_bits!(odt_cfg, wr_odt_delay, u8, 16, 20);
+register_bits!(odt_cfg, rd_odt_hold, u8, 8, 11);
+register_bits!(odt_cfg, rd_odt_delay, u8, 2, 6);
+
+// bit n set: assert ODT of rank n
+register!(odt_map, OdtMap, RW, u32);
+register_bits!(odt_map, rank1_rd_odt, u8, 12, 13);
+register_bits!(odt_map, rank1_wr_odt, u8, 8, 9);
+register_bits!(odt_map, rank0_rd_odt, u8, 4, 5);
+register_bits!(odt_map, rank0_wr_odt, u8, 0, 1);
+
 register!(dq_map0, DqMap0, RW, u32);
 register_bits!(dq_map0, dq_nibble_map_12_15, u8, 24, 31);
 register_bits!(dq_map0, dq_nibble_map_8_11, u8, 16, 23);
@@ -527,3 +732,9 @@ register_bits!(dq_map4, dq_nibble_map_cb_0_3, u8, 0, 7);
 
 register!(dq_map5, DqMap5, RW, u32);
 register_bit!(dq_map5, dis_dq_rank_swap, 0);
+
+register!(sw_ctrl, SwCtrl, RW, u32);
+register_bit!(sw_ctrl, sw_done, 0);
+
+register!(sw_status, SwStatus, RO, u32);
+register_bit!(sw_status, sw_done_ack, 0);
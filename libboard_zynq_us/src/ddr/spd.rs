@@ -1,100 +1,171 @@
 //! SPD data decoding
 //!
 //! JEDEC Standard No. 21-C Release 23A Annex L: Serial Presence Detect (SPD) for DDR4 SDRAM Modules
+//! and Annex K for DDR3 (see [ddr3])
 use core::convert::TryInto;
 use libm::ceilf;
 use log::debug;
 
-use crate::i2c::{I2C, MUX_ADDR};
+use super::lpddr4;
+use crate::board::{I2cBus, SpdLocation};
+use crate::i2c::I2C;
 
-// select bits for SODIMM
-#[cfg(feature = "target_zcu111")]
-const I2C_MUX_SEL: u8 = 0x08;
-// SODIMM control addresses
-#[cfg(feature = "target_zcu111")]
+mod ddr3;
+pub mod jep106;
+
+// SPD page select addresses (broadcast to all DDR4 SPDs on the bus)
 const I2C_CTRL_ADDR_LO: u16 = 0x36;
-#[cfg(feature = "target_zcu111")]
 const I2C_CTRL_ADDR_HI: u16 = 0x37;
-// SODIMM address
-#[cfg(feature = "target_zcu111")]
-const I2C_ADDR: u16 = 0x51;
 
-pub fn read_spd_eeprom() -> GeneralConfig {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpdError {
+    /// I2C transfer to `addr` failed, with the raw interrupt status
+    I2c { addr: u16, status: u32 },
+    /// The I2C mux didn't select the SPD channel; value read back
+    MuxSelect(u8),
+    /// CRC mismatch in the section starting at byte `start`
+    Crc {
+        start: usize,
+        expected: u16,
+        actual: u16,
+    },
+    /// `value` at byte `byte` is reserved or unsupported
+    InvalidData {
+        byte: usize,
+        value: u8,
+        reason: &'static str,
+    },
+}
+
+pub fn read_spd_eeprom(spd: &SpdLocation) -> Result<GeneralConfig, SpdError> {
     let mut spd_data = [0u8; 512];
-    let mut i2c = I2C::i2c1();
+    let mut i2c = match spd.bus {
+        I2cBus::I2c0 => I2C::i2c0(),
+        I2cBus::I2c1 => I2C::i2c1(),
+    };
+    let i2c_err = |addr| move |status| SpdError::I2c { addr, status };
     // set clock
     i2c.set_sclk(100_000);
     // set mux to DDR
-    i2c.master_write_polled(MUX_ADDR, 1, &[I2C_MUX_SEL])
-        .unwrap();
+    i2c.master_write_polled(spd.mux_addr, 1, &[spd.mux_sel])
+        .map_err(i2c_err(spd.mux_addr))?;
     while i2c.busy() {}
     // read back selection to confirm
-    i2c.master_read_polled(MUX_ADDR, 1, &mut spd_data).unwrap();
-    assert!(spd_data[0] == I2C_MUX_SEL, "Error in I2C mux selection");
+    i2c.master_read_polled(spd.mux_addr, 1, &mut spd_data)
+        .map_err(i2c_err(spd.mux_addr))?;
+    if spd_data[0] != spd.mux_sel {
+        return Err(SpdError::MuxSelect(spd_data[0]));
+    }
 
-    // enable access to lower page
-    i2c.master_write_polled(I2C_CTRL_ADDR_LO, 1, &[0x00])
-        .unwrap();
+    // enable access to lower page (DDR3 SPDs aren't paged and won't acknowledge this)
+    let page_select = i2c
+        .master_write_polled(I2C_CTRL_ADDR_LO, 1, &[0x00])
+        .map_err(i2c_err(I2C_CTRL_ADDR_LO));
     while i2c.busy() {}
 
     // set start addr
-    i2c.master_write_polled(I2C_ADDR, 1, &[0x00]).unwrap();
+    i2c.master_write_polled(spd.addr, 1, &[0x00])
+        .map_err(i2c_err(spd.addr))?;
     while i2c.busy() {}
 
     // read lower page
-    i2c.master_read_polled(I2C_ADDR, 256, &mut spd_data[..256])
-        .unwrap();
+    i2c.master_read_polled(spd.addr, 256, &mut spd_data[..256])
+        .map_err(i2c_err(spd.addr))?;
     while i2c.busy() {}
+    if spd_data[2] == ddr3::DEVICE_TYPE {
+        dump_spd_data(&spd_data[..256]);
+        return GeneralConfig::from_spd_data(&spd_data);
+    }
+    page_select?;
 
     // enable access to upper page
     i2c.master_write_polled(I2C_CTRL_ADDR_HI, 1, &[0x01])
-        .unwrap();
+        .map_err(i2c_err(I2C_CTRL_ADDR_HI))?;
     while i2c.busy() {}
 
     // set start addr
-    i2c.master_write_polled(I2C_ADDR, 1, &[0x00]).unwrap();
+    i2c.master_write_polled(spd.addr, 1, &[0x00])
+        .map_err(i2c_err(spd.addr))?;
     while i2c.busy() {}
 
     // read upper page
-    i2c.master_read_polled(I2C_ADDR, 256, &mut spd_data[256..])
-        .unwrap();
+    i2c.master_read_polled(spd.addr, 256, &mut spd_data[256..])
+        .map_err(i2c_err(spd.addr))?;
     while i2c.busy() {}
 
+    dump_spd_data(&spd_data);
     GeneralConfig::from_spd_data(&spd_data)
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Log the raw EEPROM contents, 16 bytes per line, so they can be added to testdata/spd
+fn dump_spd_data(spd_data: &[u8]) {
+    for (i, line) in spd_data.chunks(16).enumerate() {
+        debug!("SPD {:03x}: {:02x?}", i * 16, line);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DeviceType {
     Ddr3,
     Ddr4,
     LpDdr3,
     LpDdr4,
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PackageType {
     Monolithic,
     NonMonolithic,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SignalLoading {
     Unspecified,
     MultiLoadStack,
     SingleLoadStack,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FineGranularityRefMode {
     X1,
     X2,
     X4,
 }
 
+/// Output driver impedance (MR1 A2-1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveStrength {
+    Rzq7 = 0b00,
+    Rzq5 = 0b01,
+}
+
+/// RTT_NOM (MR1 A10-8) and RTT_PARK (MR5 A8-6) termination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rtt {
+    Disabled = 0b000,
+    Rzq4 = 0b001,
+    Rzq2 = 0b010,
+    Rzq6 = 0b011,
+    Rzq1 = 0b100,
+    Rzq5 = 0b101,
+    Rzq3 = 0b110,
+    Rzq7 = 0b111,
+}
+
+/// Dynamic ODT during writes (MR2 A11-9)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RttWr {
+    Disabled = 0b000,
+    Rzq2 = 0b001,
+    Rzq1 = 0b010,
+    HiZ = 0b011,
+    Rzq3 = 0b100,
+}
+
 /// General Configuration Section: Bytes 0-127
 ///
 /// The struct data is intended to be a minimal representation of the data encoded in the SPD
 /// EEPROM. Any derived values are exposed as methods.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct GeneralConfig {
     // note: "reserved" is also used in comments if all fields in byte are reserved or should be zero.
     // presumably this struct will need to be extended for non-DDR4 SDRAM
@@ -106,7 +177,8 @@ pub struct GeneralConfig {
     pub spd_additions: u8, // 3-0
     // 2
     pub device_type: DeviceType,
-    // 3: module type (3-0)
+    // 3
+    pub module_type: u8, // 3-0
     pub module_config: ModuleConfig,
     // 4
     pub bg_addr_bits: u8,       // 7-6
@@ -146,7 +218,7 @@ pub struct GeneralConfig {
     // 19 & 124
     pub t_ckavg_max_ps: u32, // same ^
     // 20 & 21: all bits, 22: 1-0, 23: all reserved
-    // bit values from LSB to MSB correspond to CL = 7-24
+    // bit values from LSB to MSB correspond to CL = 7-24 (DDR3: bytes 14-15, CL = 4-18)
     pub supported_cas_latencies: u32,
     // 24 & 123
     pub t_aa_min_ps: u32,
@@ -178,6 +250,9 @@ pub struct GeneralConfig {
     // 78-116 reserved
     // 117-125 contain offsets for above timing parameters
     // 126-127: CRC for base section
+    // 128-255: module_config
+    // 320-383
+    pub module_info: ModuleInfo,
 
     // hardcoded parameters
     pub dm_en: bool,
@@ -197,12 +272,25 @@ pub struct GeneralConfig {
     pub self_ref_abort: bool,
     pub v_ref: bool,
     pub geardown: bool,
+    // board dependent
+    pub drive_strength: DriveStrength,
+    pub rtt_nom: Rtt,
+    pub rtt_wr: RttWr,
+    pub rtt_park: Rtt,
+    // 2 tCK preambles, write preamble only from DDR4-2400
+    pub wr_preamble_2nck: bool,
+    pub rd_preamble_2nck: bool,
+    // initial VrefDQ, refined by PHY VREF training
+    pub vref_dq_range_2: bool,
+    pub vref_dq: u8,
+    // operating clock period, longer than t_ckavg_min_ps if the DDR clock is slowed down
+    pub t_ck_ps: u32,
 }
 
 /// Bytes 128-255
 /// Annex L.1: Module Specific Bytes for Unbuffered Memory Module Types
 /// UDIMM and SO-DIMM
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct UnbufferedConfig {
     // 128
     pub raw_card_extension: u8,    // 7-5
@@ -219,23 +307,168 @@ pub struct UnbufferedConfig {
     // 254-255: CRC
 }
 
-// TODO
-#[derive(Debug)]
-pub struct RegisteredConfig;
-#[derive(Debug)]
-pub struct LoadReducedConfig;
+/// Register (RCD) output drive strength, as in SPD bytes 137-138 and RCD control words RC03-RC05
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RcdDriveStrength {
+    Light = 0b00,
+    Moderate = 0b01,
+    Strong = 0b10,
+    VeryStrong = 0b11,
+}
+
+/// Bytes 131-138 describing the registering clock driver, common to RDIMMs and LRDIMMs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RcdConfig {
+    // 131
+    pub register_type: u8, // 7-4, 0 = DDR4RCD01
+    pub dram_rows: u8,     // 3-2, 0 = undefined
+    pub registers: u8,     // 1-0, 0 = undefined
+    // 132
+    pub heat_spreader: bool, // 7
+    // 133-134: JEP-106 continuation count (with parity) and ID
+    pub manufacturer_id: u16,
+    // 135
+    pub revision: u8,
+    // 136
+    pub rank_1_mirrored: bool, // 0
+    // 137
+    pub cs_drive: RcdDriveStrength,  // 7-6
+    pub ca_drive: RcdDriveStrength,  // 5-4
+    pub odt_drive: RcdDriveStrength, // 3-2
+    pub cke_drive: RcdDriveStrength, // 1-0
+    // 138
+    pub y1_y3_drive: RcdDriveStrength, // 3-2
+    pub y0_y2_drive: RcdDriveStrength, // 1-0
+}
+
+/// Bytes 128-255
+/// Annex L.2: Module Specific Bytes for Registered Memory Module Types
+/// RDIMM, Mini-RDIMM and 72b-SO-RDIMM
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegisteredConfig {
+    // 128
+    pub raw_card_extension: u8,    // 7-5
+    pub module_nominal_height: u8, // 4-0
+    // 129
+    pub module_max_thickness_back: u8,  // 7-4
+    pub module_max_thickness_front: u8, // 3-0
+    // 130
+    pub ref_raw_card_rev: u8,
+    pub ref_raw_card: u8,
+    // 131-138
+    pub rcd: RcdConfig,
+    // 139-253 reserved
+    // 254-255: CRC
+}
+
+/// Bytes 128-255
+/// Annex L.3: Module Specific Bytes for Load Reduced Memory Module Types
+///
+/// The DRAM settings are given for data rates up to 1866, 2400 and 3200 MT/s, indexed by
+/// [speed_range].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadReducedConfig {
+    // 128
+    pub raw_card_extension: u8,    // 7-5
+    pub module_nominal_height: u8, // 4-0
+    // 129
+    pub module_max_thickness_back: u8,  // 7-4
+    pub module_max_thickness_front: u8, // 3-0
+    // 130
+    pub ref_raw_card_rev: u8,
+    pub ref_raw_card: u8,
+    // 131-138
+    pub rcd: RcdConfig,
+    // 138
+    pub bck_drive_strong: bool,       // 6, moderate otherwise
+    pub bcom_drive: RcdDriveStrength, // 5-4, BCOM, BODT and BCKE
+    // 139
+    pub db_revision: u8,
+    // 140-143 & 155 (bits 3-0)
+    pub dram_vref_dq: [u8; 4],
+    pub dram_vref_dq_range_2: [bool; 4],
+    // 144 & 155 (bit 4)
+    pub db_vref_dq: u8,
+    pub db_vref_dq_range_2: bool,
+    // 145-147: data buffer MDQ termination and driver impedance (BCW codes)
+    pub db_mdq_rtt: [u8; 3],   // 6-4
+    pub db_mdq_drive: [u8; 3], // 2-0
+    // 148
+    pub dram_drive_strength: [DriveStrength; 3],
+    // 149-151
+    pub dram_rtt_wr: [RttWr; 3], // 5-3
+    pub dram_rtt_nom: [Rtt; 3],  // 2-0
+    // 152-154: package ranks 0 & 1 (bits 2-0), 2 & 3 (bits 5-3)
+    pub dram_rtt_park: [[Rtt; 2]; 3],
+    // 156
+    pub db_dfe: bool, // 1
+    pub db_gain_adjust: bool, // 0
+                      // 157-253 reserved
+                      // 254-255: CRC
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ModuleConfig {
     Unbuffered(UnbufferedConfig),
-    #[allow(unused)]
     Registered(RegisteredConfig),
-    #[allow(unused)]
     LoadReduced(LoadReducedConfig),
 }
 
-// TODO: supplier data (bytes 320-383) if anyone ever cares about it
-//  (would require decoding from JEDEC JEP-106)
+/// Bytes 320-383 (DDR3: 117-149)
+/// Module supplier's data, for inventory and RMA tracking only
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModuleInfo {
+    // 320-321: JEP-106 continuation count (with parity) and ID, see [jep106]
+    pub module_manufacturer_id: u16,
+    // 322: manufacturer defined
+    pub location: u8,
+    // 323-324: BCD year and week, 0 if not given
+    pub year: u16,
+    pub week: u8,
+    // 325-328
+    pub serial_number: u32,
+    // 329-348: ASCII, padded with spaces (DDR3: 128-145, 18 characters)
+    pub part_number: [u8; 20],
+    // 349 (DDR3: 146-147)
+    pub revision: u16,
+    // 350-351 (DDR3: 148-149)
+    pub dram_manufacturer_id: u16,
+    // 352 (not in DDR3 SPDs)
+    pub dram_stepping: u8,
+    // 353-381: manufacturer specific
+    // 382-383 reserved
+}
+
+/// Index into the per data rate DRAM settings of [LoadReducedConfig] for `speed_grade`.
+pub fn speed_range(speed_grade: u32) -> usize {
+    match speed_grade {
+        0..=1866 => 0,
+        1867..=2400 => 1,
+        _ => 2,
+    }
+}
+
+fn rcd_drive_strength(code: u8) -> RcdDriveStrength {
+    match code & 0x3 {
+        0b00 => RcdDriveStrength::Light,
+        0b01 => RcdDriveStrength::Moderate,
+        0b10 => RcdDriveStrength::Strong,
+        _ => RcdDriveStrength::VeryStrong,
+    }
+}
+
+fn rtt(code: u8) -> Rtt {
+    match code & 0x7 {
+        0b000 => Rtt::Disabled,
+        0b001 => Rtt::Rzq4,
+        0b010 => Rtt::Rzq2,
+        0b011 => Rtt::Rzq6,
+        0b100 => Rtt::Rzq1,
+        0b101 => Rtt::Rzq5,
+        0b110 => Rtt::Rzq3,
+        _ => Rtt::Rzq7,
+    }
+}
 
 /// Calculate CRC code for `spd_data` according to the algorithm in the JEDEC spec (Page 4.1.2.12 – 37).
 fn crc(spd_data: &[u8]) -> u16 {
@@ -260,14 +493,96 @@ fn get_time_ps(mtb_ps: u32, ftb_ps: u32, n_mtb: u16, offset_ftb: i8) -> u32 {
     ((mtb_ps * (n_mtb as u32)) as i64 + (ftb_ps as i64) * (offset_ftb as i64)) as u32
 }
 
-fn bad_data(byte_num: usize, value: u8) -> ! {
-    panic!("Invalid SPD data at byte {}. Data: {:#X}", byte_num, value);
+/// Split `time_ps` into MTB units and an FTB offset, the inverse of [get_time_ps]. Rounds the
+/// MTB count up so the offset is zero or negative, as in JEDEC reference SPDs.
+fn split_time_ps(mtb_ps: u32, ftb_ps: u32, time_ps: u32) -> (u16, i8) {
+    let n_mtb = time_ps.div_ceil(mtb_ps);
+    let offset_ftb = (time_ps as i64 - (n_mtb * mtb_ps) as i64) / ftb_ps as i64;
+    (n_mtb as u16, offset_ftb as i8)
+}
+
+/// Store the CRC of the 128 byte `section` in its last two bytes.
+fn set_crc(section: &mut [u8]) {
+    let crc = crc(&section[..126]);
+    section[126..128].copy_from_slice(&crc.to_le_bytes());
+}
+
+/// Check the CRC stored in the last two bytes of the 128 byte `section` starting at `start`.
+fn check_crc(section: &[u8], start: usize) -> Result<(), SpdError> {
+    let expected = ((section[127] as u16) << 8) | (section[126] as u16);
+    let actual = crc(&section[..126]);
+    if expected != actual {
+        return Err(SpdError::Crc {
+            start,
+            expected,
+            actual,
+        });
+    }
+    debug!("CRC check passed");
+    Ok(())
+}
+
+/// Decode a BCD byte, `None` if either digit is out of range.
+fn from_bcd(byte: u8) -> Option<u8> {
+    if byte >> 4 > 9 || byte & 0xf > 9 {
+        None
+    } else {
+        Some((byte >> 4) * 10 + (byte & 0xf))
+    }
+}
+
+/// Two BCD digits of `value`, saturating at 99
+fn to_bcd(value: u16) -> u8 {
+    let value = value.min(99) as u8;
+    ((value / 10) << 4) | (value % 10)
+}
+
+fn bad_data(byte: usize, value: u8, reason: &'static str) -> SpdError {
+    SpdError::InvalidData {
+        byte,
+        value,
+        reason,
+    }
+}
+
+/// MR0 bits A12, A6-4 and A2 for `cl`, `None` if it has no encoding - JESD 79-4 Table 3.
+fn mr0_cas_latency(cl: u32) -> Option<u16> {
+    let code = MR0_CAS_LATENCIES.iter().position(|&c| c == cl)? as u16;
+    Some((code & 0x10) << 8 | (code & 0xe) << 3 | (code & 0x1) << 2)
+}
+
+/// MR0 bits A13 and A11-9 for write recovery (WR = 2 * RTP) of at least `wr_nck` -
+/// JESD 79-4 Table 2.
+fn mr0_write_recovery(wr_nck: u32) -> u16 {
+    // 22 and 24 are swapped in the table
+    let code: u16 = match wr_nck {
+        0..=10 => 0b0000,
+        11..=12 => 0b0001,
+        13..=14 => 0b0010,
+        15..=16 => 0b0011,
+        17..=18 => 0b0100,
+        19..=20 => 0b0101,
+        21..=22 => 0b0111,
+        23..=24 => 0b0110,
+        25..=26 => 0b1000,
+        _ => 0b1001,
+    };
+    (code & 0x8) << 10 | (code & 0x7) << 9
 }
 
 // hardcoded burst length for DDR4(?)
 const DDR4_BURST_LEN: u8 = 8;
+const LPDDR4_BURST_LEN: u8 = 16;
 // max t_refi for DDR4 (normal temperature range)
 const DDR4_TREFI_MAX_PS: u32 = 7_800_000;
+// JESD209-4 tREFI for densities up to 8 Gb per channel
+const LPDDR4_TREFI_MAX_PS: u32 = 3_904_000;
+// VrefDQ range 1 value for 76.25 % of VDDQ
+const VREF_DQ_INIT: u8 = 0x19;
+// CL in order of its MR0 code (A12, A6-4, A2) - JESD 79-4 Table 3, 0 = reserved
+const MR0_CAS_LATENCIES: [u32; 24] = [
+    9, 10, 11, 12, 13, 14, 15, 16, 18, 20, 22, 24, 23, 17, 19, 21, 25, 26, 27, 28, 0, 30, 0, 32,
+];
 // for converting between some size in MiB and number of address bits
 const LOG2_1_MIB: u32 = 23;
 // addressing maxima
@@ -282,22 +597,115 @@ const HIF_BANK_OFFSET: u32 = 300;
 const HIF_BG_OFFSET: u32 = 400;
 const HIF_RANK_OFFSET: u32 = 500;
 
+// empty SPD data with the hardcoded and board dependent parameters
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        GeneralConfig {
+            spd_bytes_total: 0,
+            spd_bytes_used: 0,
+            spd_encoding: 0,
+            spd_additions: 0,
+            device_type: DeviceType::Ddr4,
+            module_type: 0,
+            module_config: ModuleConfig::Unbuffered(UnbufferedConfig {
+                raw_card_extension: 0,
+                module_nominal_height: 0,
+                module_max_thickness_back: 0,
+                module_max_thickness_front: 0,
+                ref_raw_card_rev: 0,
+                ref_raw_card: 0,
+                rank_1_mirrored: false,
+            }),
+            bg_addr_bits: 0,
+            bank_addr_bits: 0,
+            capacity_megabits: 0,
+            row_addr_bits: 0,
+            col_addr_bits: 0,
+            package_type: PackageType::Monolithic,
+            die_count: 1,
+            signal_loading: SignalLoading::Unspecified,
+            t_maw: 8192,
+            mac: 0,
+            vdd_12_endurant: false,
+            vdd_12_operable: false,
+            package_ranks: 0,
+            device_width: 0,
+            bus_width_extension: 0,
+            bus_width: 0,
+            has_thermal_sensor: false,
+            mtb_ps: 125,
+            ftb_ps: 1,
+            t_ckavg_min_ps: 0,
+            t_ckavg_max_ps: 0,
+            supported_cas_latencies: 0,
+            t_aa_min_ps: 0,
+            t_rcd_min_ps: 0,
+            t_rp_min_ps: 0,
+            t_ras_min_ps: 0,
+            t_rc_min_ps: 0,
+            t_rfc1_min_ps: 0,
+            t_rfc2_min_ps: 0,
+            t_rfc4_min_ps: 0,
+            t_faw_min_ps: 0,
+            t_rrd_s_min_ps: 0,
+            t_rrd_l_min_ps: 0,
+            t_ccd_l_min_ps: 0,
+            dq_map: [0; 18],
+            module_info: ModuleInfo {
+                module_manufacturer_id: 0,
+                location: 0,
+                year: 0,
+                week: 0,
+                serial_number: 0,
+                part_number: [0; 20],
+                revision: 0,
+                dram_manufacturer_id: 0,
+                dram_stepping: 0,
+            },
+            dm_en: true,
+            rd_dbi_en: false,
+            wr_dbi_en: false,
+            ecc_en: false,
+            en_2nd_clk: false,
+            parity_en: false,
+            crc_en: false,
+            power_down_en: false,
+            clock_stop_en: false,
+            self_ref_en: false,
+            lp_auto_self_ref: false,
+            temp_ref_mode: false,
+            temp_ref_range: false,
+            fine_granularity_ref_mode: FineGranularityRefMode::X1,
+            self_ref_abort: false,
+            v_ref: true,
+            geardown: false,
+            drive_strength: DriveStrength::Rzq7,
+            rtt_nom: Rtt::Rzq6,
+            rtt_wr: RttWr::Disabled,
+            rtt_park: Rtt::Rzq6,
+            wr_preamble_2nck: false,
+            rd_preamble_2nck: false,
+            vref_dq_range_2: false,
+            vref_dq: VREF_DQ_INIT,
+            t_ck_ps: 0,
+        }
+    }
+}
+
 impl GeneralConfig {
-    pub fn from_spd_data(spd_data: &[u8; 512]) -> Self {
+    pub fn from_spd_data(spd_data: &[u8; 512]) -> Result<Self, SpdError> {
+        // DDR3 SPDs only share the device type byte, and are 256 bytes long
+        if spd_data[2] == ddr3::DEVICE_TYPE {
+            return ddr3::from_spd_data(&spd_data[..256].try_into().unwrap());
+        }
+
         // verify CRC
-        let expected_crc = ((spd_data[127] as u16) << 8) | (spd_data[126] as u16);
-        let actual_crc = crc(&spd_data[..126]);
-        assert_eq!(
-            expected_crc, actual_crc,
-            "CRC mismatch. Expected: {:#X}, actual: {:#X}",
-            expected_crc, actual_crc
-        );
-        debug!("CRC check passed");
+        check_crc(&spd_data[..128], 0)?;
 
         let spd_bytes_total: u16 = match (spd_data[0] >> 4) & 0x7 {
             0b001 => 256,
             0b010 => 512,
-            _ => bad_data(0, spd_data[0]),
+            _ => return Err(bad_data(0, spd_data[0], "SPD size")),
         };
 
         let spd_bytes_used: u16 = match spd_data[0] & 0xf {
@@ -305,7 +713,7 @@ impl GeneralConfig {
             0b010 => 256,
             0b011 => 384,
             0b100 => 512,
-            _ => bad_data(0, spd_data[0]),
+            _ => return Err(bad_data(0, spd_data[0], "SPD size")),
         };
 
         // 1
@@ -318,24 +726,29 @@ impl GeneralConfig {
             0x0c => DeviceType::Ddr4,
             0x0f => DeviceType::LpDdr3,
             0x10 => DeviceType::LpDdr4,
-            _ => bad_data(2, spd_data[2]),
+            _ => return Err(bad_data(2, spd_data[2], "device type")),
         };
 
-        // same as ^, but for SO-DIMM
-        let module_config = match spd_data[3] & 0xf {
+        // 3
+        let module_type = spd_data[3] & 0xf;
+        let module_config = match module_type {
             // 0b0000 => extended module type (byte 15),
-            // 0b0001 | 0b0101 | 0b1000 => rdimms,
-            // 0b0100 => lrdimm,
             0b0010 | 0b0011 | 0b0110 | 0b1001 | 0b1100 | 0b1101 => ModuleConfig::Unbuffered(
-                UnbufferedConfig::from_spd_data(&spd_data[128..256].try_into().unwrap()),
+                UnbufferedConfig::from_spd_data(&spd_data[128..256].try_into().unwrap())?,
             ),
-            _ => bad_data(3, spd_data[3]),
+            0b0001 | 0b0101 | 0b1000 => ModuleConfig::Registered(RegisteredConfig::from_spd_data(
+                &spd_data[128..256].try_into().unwrap(),
+            )?),
+            0b0100 => ModuleConfig::LoadReduced(LoadReducedConfig::from_spd_data(
+                &spd_data[128..256].try_into().unwrap(),
+            )?),
+            _ => return Err(bad_data(3, spd_data[3], "module type")),
         };
 
         // 4
         // check for reserved values
         if spd_data[4] >> 6 == 0x3 || spd_data[4] >> 4 & 0x2 == 0x2 || spd_data[4] & 0x8 == 0x8 {
-            bad_data(4, spd_data[4]);
+            return Err(bad_data(4, spd_data[4], "reserved density or bank bits"));
         }
         let bg_addr_bits = spd_data[4] >> 6;
         let bank_addr_bits = (spd_data[4] >> 4 & 0x3) + 2;
@@ -344,20 +757,20 @@ impl GeneralConfig {
         // 5
         // check for reserved values
         if spd_data[5] >> 3 == 0x7 || spd_data[5] & 0x4 == 0x4 {
-            bad_data(5, spd_data[5]);
+            return Err(bad_data(5, spd_data[5], "reserved address bits"));
         }
         let row_addr_bits = (spd_data[5] >> 3 & 0x7) + 12;
         let col_addr_bits = (spd_data[5] & 0x7) + 9;
 
         // 6
         if spd_data[6] & 0x3 == 0x3 {
-            bad_data(6, spd_data[6]);
+            return Err(bad_data(6, spd_data[6], "reserved signal loading"));
         }
         let package_type = match spd_data[6] >> 7 {
             0b0 => PackageType::Monolithic,
             0b1 => PackageType::NonMonolithic,
             // impossible, but must make rustc happy
-            _ => bad_data(6, spd_data[6]),
+            _ => return Err(bad_data(6, spd_data[6], "package type")),
         };
         let die_count = (spd_data[6] >> 4 & 0x7) + 1;
         let signal_loading = match spd_data[6] & 0x3 {
@@ -365,12 +778,12 @@ impl GeneralConfig {
             0b01 => SignalLoading::MultiLoadStack,
             0b10 => SignalLoading::SingleLoadStack,
             // 0b11 reserved
-            _ => bad_data(6, spd_data[6]),
+            _ => return Err(bad_data(6, spd_data[6], "reserved signal loading")),
         };
 
         // 7
         if spd_data[7] >> 4 == 0x3 || spd_data[7] & 0x7 == 0x7 || (spd_data[7] & 0xf) > 8 {
-            bad_data(7, spd_data[7]);
+            return Err(bad_data(7, spd_data[7], "reserved tMAW or MAC"));
         }
         let t_maw: u16 = 8192 >> (spd_data[7] >> 4 & 0x3);
         let mac = spd_data[7] & 0xf;
@@ -381,14 +794,14 @@ impl GeneralConfig {
 
         // 12
         if spd_data[12] >> 3 & 0x4 == 0x4 || spd_data[12] & 0x4 == 0x4 {
-            bad_data(12, spd_data[12]);
+            return Err(bad_data(12, spd_data[12], "reserved ranks or device width"));
         }
         let package_ranks = (spd_data[12] >> 3 & 0x7) + 1;
         let device_width: u8 = 4 << (spd_data[12] & 0x7); // 4 * 2^(bits)
 
         // 13
         if spd_data[13] >> 3 & 0x2 == 0x2 || spd_data[13] & 0x4 == 0x4 {
-            bad_data(13, spd_data[13]);
+            return Err(bad_data(13, spd_data[13], "reserved bus width"));
         }
         let bus_width_extension = (spd_data[13] >> 3 & 0x3) << 3; // 0 -> 0, 1 -> 8
         let bus_width: u8 = 8 << (spd_data[13] & 0x7); // 8 * 2^(bits)
@@ -399,11 +812,11 @@ impl GeneralConfig {
         // 17
         let mtb_ps: u32 = match spd_data[17] >> 2 & 0x3 {
             0b00 => 125,
-            _ => bad_data(17, spd_data[17]),
+            _ => return Err(bad_data(17, spd_data[17], "unsupported timebase")),
         };
         let ftb_ps: u32 = match spd_data[17] & 0x3 {
             0b00 => 1,
-            _ => bad_data(17, spd_data[17]),
+            _ => return Err(bad_data(17, spd_data[17], "unsupported timebase")),
         };
 
         // 18 & 125
@@ -462,12 +875,13 @@ impl GeneralConfig {
         let mut dq_map = [0_u8; 18];
         dq_map.copy_from_slice(&spd_data[60..78]);
 
-        GeneralConfig {
+        let mut config = GeneralConfig {
             spd_bytes_total,
             spd_bytes_used,
             spd_encoding,
             spd_additions,
             device_type,
+            module_type,
             module_config,
             bg_addr_bits,
             bank_addr_bits,
@@ -504,23 +918,168 @@ impl GeneralConfig {
             t_rrd_l_min_ps,
             t_ccd_l_min_ps,
             dq_map,
-            dm_en: true,
-            rd_dbi_en: false,
-            wr_dbi_en: false,
-            ecc_en: false,
-            en_2nd_clk: false,
-            parity_en: false,
-            crc_en: false,
-            power_down_en: false,
-            clock_stop_en: false,
-            self_ref_en: false,
-            lp_auto_self_ref: false,
-            temp_ref_mode: false,
-            temp_ref_range: false,
-            fine_granularity_ref_mode: FineGranularityRefMode::X1,
-            self_ref_abort: false,
-            v_ref: true,
-            geardown: false,
+            module_info: ModuleInfo::from_spd_data(
+                &spd_data[MODULE_INFO_START..384].try_into().unwrap(),
+            ),
+            t_ck_ps: t_ckavg_min_ps,
+            ..GeneralConfig::default()
+        };
+
+        // LRDIMMs specify the DRAM settings behind their data buffers
+        if let ModuleConfig::LoadReduced(module_config) = config.module_config {
+            let range = speed_range(config.speed_grade());
+            config.drive_strength = module_config.dram_drive_strength[range];
+            config.rtt_nom = module_config.dram_rtt_nom[range];
+            config.rtt_wr = module_config.dram_rtt_wr[range];
+            config.rtt_park = module_config.dram_rtt_park[range][0];
+            config.vref_dq = module_config.dram_vref_dq[0];
+            config.vref_dq_range_2 = module_config.dram_vref_dq_range_2[0];
+        }
+
+        Ok(config)
+    }
+
+    /// Encode as a 512 byte SPD image with valid CRCs, the inverse of
+    /// [from_spd_data](Self::from_spd_data). Bytes that aren't decoded (e.g. manufacturer specific
+    /// data) are left zero and the hardcoded parameters aren't stored.
+    pub fn to_spd_data(&self) -> [u8; 512] {
+        let mut spd_data = [0u8; 512];
+
+        // 0
+        let spd_bytes_total: u8 = match self.spd_bytes_total {
+            256 => 0b001,
+            _ => 0b010,
+        };
+        spd_data[0] = spd_bytes_total << 4 | (self.spd_bytes_used / 128) as u8;
+
+        // 1
+        spd_data[1] = self.spd_encoding << 4 | self.spd_additions;
+
+        // 2
+        spd_data[2] = match self.device_type {
+            DeviceType::Ddr3 => 0x0b,
+            DeviceType::Ddr4 => 0x0c,
+            DeviceType::LpDdr3 => 0x0f,
+            DeviceType::LpDdr4 => 0x10,
+        };
+
+        // 3
+        spd_data[3] = self.module_type;
+
+        // 4
+        spd_data[4] = self.bg_addr_bits << 6
+            | (self.bank_addr_bits - 2) << 4
+            | (self.capacity_megabits / 256).trailing_zeros() as u8;
+
+        // 5
+        spd_data[5] = (self.row_addr_bits - 12) << 3 | (self.col_addr_bits - 9);
+
+        // 6
+        let signal_loading = match self.signal_loading {
+            SignalLoading::Unspecified => 0b00,
+            SignalLoading::MultiLoadStack => 0b01,
+            SignalLoading::SingleLoadStack => 0b10,
+        };
+        spd_data[6] = (matches!(self.package_type, PackageType::NonMonolithic) as u8) << 7
+            | (self.die_count - 1) << 4
+            | signal_loading;
+
+        // 7
+        spd_data[7] = ((8192 / self.t_maw).trailing_zeros() as u8) << 4 | self.mac;
+
+        // 11
+        spd_data[11] = (self.vdd_12_endurant as u8) << 1 | self.vdd_12_operable as u8;
+
+        // 12
+        spd_data[12] =
+            (self.package_ranks - 1) << 3 | (self.device_width / 4).trailing_zeros() as u8;
+
+        // 13
+        spd_data[13] = self.bus_width_extension | (self.bus_width / 8).trailing_zeros() as u8;
+
+        // 14
+        spd_data[14] = (self.has_thermal_sensor as u8) << 7;
+
+        // 17: MTB = 125 ps, FTB = 1 ps (0)
+
+        // timings with FTB offsets
+        let timings = [
+            (18, 125, self.t_ckavg_min_ps),
+            (19, 124, self.t_ckavg_max_ps),
+            (24, 123, self.t_aa_min_ps),
+            (25, 122, self.t_rcd_min_ps),
+            (26, 121, self.t_rp_min_ps),
+            (29, 120, self.t_rc_min_ps),
+            (38, 119, self.t_rrd_s_min_ps),
+            (39, 118, self.t_rrd_l_min_ps),
+            (40, 117, self.t_ccd_l_min_ps),
+        ];
+        for &(byte, offset_byte, time_ps) in timings.iter() {
+            let (n_mtb, offset_ftb) = split_time_ps(self.mtb_ps, self.ftb_ps, time_ps);
+            spd_data[byte] = n_mtb as u8;
+            spd_data[offset_byte] = offset_ftb as u8;
+        }
+
+        // 20-23
+        spd_data[20..24].copy_from_slice(&self.supported_cas_latencies.to_le_bytes());
+
+        // 27: upper nibbles of t_rc (LSB in 29) and t_ras
+        let n_mtb_t_ras = self.t_ras_min_ps.div_ceil(self.mtb_ps);
+        let n_mtb_t_rc = self.t_rc_min_ps.div_ceil(self.mtb_ps);
+        spd_data[27] = ((n_mtb_t_rc >> 4) & 0xf0 | (n_mtb_t_ras >> 8) & 0x0f) as u8;
+        spd_data[28] = n_mtb_t_ras as u8;
+
+        // 30-35
+        let t_rfc = [
+            (30, self.t_rfc1_min_ps),
+            (32, self.t_rfc2_min_ps),
+            (34, self.t_rfc4_min_ps),
+        ];
+        for &(byte, time_ps) in t_rfc.iter() {
+            let n_mtb = time_ps.div_ceil(self.mtb_ps) as u16;
+            spd_data[byte..byte + 2].copy_from_slice(&n_mtb.to_le_bytes());
+        }
+
+        // 36-37
+        let n_mtb_t_faw = self.t_faw_min_ps.div_ceil(self.mtb_ps);
+        spd_data[36] = (n_mtb_t_faw >> 8) as u8 & 0xf;
+        spd_data[37] = n_mtb_t_faw as u8;
+
+        // 60-77
+        spd_data[60..78].copy_from_slice(&self.dq_map);
+
+        // 126-127
+        set_crc(&mut spd_data[..128]);
+
+        // 128-255
+        let module_spd_data = match &self.module_config {
+            ModuleConfig::Unbuffered(module_config) => module_config.to_spd_data(),
+            ModuleConfig::Registered(module_config) => module_config.to_spd_data(),
+            ModuleConfig::LoadReduced(module_config) => module_config.to_spd_data(),
+        };
+        spd_data[MODULE_CONFIG_START..256].copy_from_slice(&module_spd_data);
+
+        // 320-383
+        spd_data[MODULE_INFO_START..384].copy_from_slice(&self.module_info.to_spd_data());
+
+        spd_data
+    }
+
+    /// Registering clock driver of RDIMMs and LRDIMMs.
+    pub fn rcd(&self) -> Option<&RcdConfig> {
+        match &self.module_config {
+            ModuleConfig::Unbuffered(_) => None,
+            ModuleConfig::Registered(module_config) => Some(&module_config.rcd),
+            ModuleConfig::LoadReduced(module_config) => Some(&module_config.rcd),
+        }
+    }
+
+    /// Whether rank 1 has its address lines mirrored, on the module itself (UDIMM) or by the
+    /// RCD outputs.
+    pub fn rank_1_mirrored(&self) -> bool {
+        match &self.module_config {
+            ModuleConfig::Unbuffered(module_config) => module_config.rank_1_mirrored,
+            _ => self.rcd().map_or(false, |rcd| rcd.rank_1_mirrored),
         }
     }
 
@@ -588,11 +1147,14 @@ impl GeneralConfig {
     /// (page 4.1.2.12 – 19). Internally converts to and from `f32` to accommodate guardband.
     pub fn ps_to_nck(&self, time_ps: u32) -> u32 {
         // guardband factor of 0.01 clocks per the spec
-        ceilf(time_ps as f32 / self.t_ckavg_min_ps as f32 - 0.01) as u32
+        ceilf(time_ps as f32 / self.t_ck_ps as f32 - 0.01) as u32
     }
 
     pub fn burst_len(&self) -> u8 {
-        DDR4_BURST_LEN
+        match self.device_type {
+            DeviceType::LpDdr4 => LPDDR4_BURST_LEN,
+            _ => DDR4_BURST_LEN,
+        }
     }
 
     pub fn row_density(&self) -> u32 {
@@ -600,23 +1162,66 @@ impl GeneralConfig {
     }
 
     pub fn t_refi(&self) -> u32 {
-        DDR4_TREFI_MAX_PS
+        match self.device_type {
+            DeviceType::LpDdr4 => LPDDR4_TREFI_MAX_PS,
+            _ => DDR4_TREFI_MAX_PS,
+        }
     }
 
-    pub fn cas_latency_ns(&self) -> u32 {
-        let mut extra = 0;
-        if self.rd_dbi_en {
-            if self.max_clk_mhz() <= 933 {
-                extra = 2;
-            } else {
-                extra = 3;
-            }
+    /// Extra read latency with read DBI enabled - JESD 79-4 Table 3
+    pub fn rd_dbi_latency_nck(&self) -> u32 {
+        match (self.rd_dbi_en, self.speed_grade()) {
+            (false, _) => 0,
+            (true, 0..=2400) => 2,
+            (true, _) => 3,
+        }
+    }
+
+    /// Lowest CAS latency supported by the module that meets t_aa_min at the operating clock,
+    /// including the read DBI latency.
+    pub fn cas_latency_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::cas_latency_nck(self),
+            DeviceType::LpDdr4 => return lpddr4::read_latency_nck(self),
+            _ => {}
         }
-        self.t_aa_min_ps.div_ceil(1000) + 1 + extra
+        let min_cl = self.ps_to_nck(self.t_aa_min_ps);
+        // bit 0 of supported_cas_latencies is CL = 7
+        let cl = (min_cl.max(7)..=24)
+            .find(|cl| self.supported_cas_latencies >> (cl - 7) & 1 == 1)
+            .unwrap_or(min_cl)
+            + self.rd_dbi_latency_nck();
+        // round up to a CL that can be set in MR0
+        (cl..=32)
+            .find(|&cl| mr0_cas_latency(cl).is_some())
+            .unwrap_or(32)
     }
 
-    pub fn cas_write_latency_ns(&self) -> u32 {
-        self.t_aa_min_ps.div_ceil(1000)
+    /// Whether the 2 tCK write preamble is in use (it doesn't exist below DDR4-2400)
+    pub fn wr_preamble_2nck(&self) -> bool {
+        self.wr_preamble_2nck && self.speed_grade() >= 2400
+    }
+
+    /// CAS write latency, first set - JESD 79-4 Table 6
+    pub fn cas_write_latency_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::cas_write_latency_nck(self),
+            DeviceType::LpDdr4 => return lpddr4::write_latency_nck(self),
+            _ => {}
+        }
+        let cwl = match self.speed_grade() {
+            1600 => 9,
+            1866 => 10,
+            2133 => 11,
+            2400 => 12,
+            2666 => 14,
+            _ => 16,
+        };
+        if self.wr_preamble_2nck() {
+            cwl + 2
+        } else {
+            cwl
+        }
     }
 
     pub fn t_rfc_min_ps(&self) -> u32 {
@@ -627,24 +1232,117 @@ impl GeneralConfig {
         }
     }
 
+    /// JEDEC speed bin (MT/s) the operating clock falls in, for the JESD 79-4 latency tables.
+    pub fn speed_grade(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::speed_grade(self),
+            DeviceType::LpDdr4 => return lpddr4::speed_grade(self),
+            _ => {}
+        }
+        // t_ckavg_min of each speed bin
+        [
+            (1250, 1600),
+            (1071, 1866),
+            (938, 2133),
+            (833, 2400),
+            (750, 2666),
+            (682, 2933),
+        ]
+        .iter()
+        .find(|&&(t_ck_ps, _)| self.t_ck_ps >= t_ck_ps)
+        .map_or(3200, |&(_, rate)| rate)
+    }
+
+    /// Operating data rate (2 * clock frequency in MHz).
+    pub fn data_rate_mhz(&self) -> u32 {
+        2_000_000_u32.div_ceil(self.t_ck_ps)
+    }
+
     pub fn ctl_clock_mhz(&self) -> u32 {
-        self.max_clk_mhz() / 2
+        1_000_000 / self.t_ck_ps / 2
     }
 
     pub fn ctl_clock_period_ns(&self) -> f32 {
-        self.min_clock_period_ns() * 2.0
+        self.t_ck_ps as f32 / 1000.0 * 2.0
     }
 
     /// "greater of 4CK or 6ns" - some random micron doc I found
+    /// (DDR3: max(3 nCK, 6 ns), LPDDR4: max(5 nCK, 7.5 ns))
     pub fn t_xp_nck(&self) -> u32 {
-        self.ps_to_nck(6_000).max(4)
+        match self.device_type {
+            DeviceType::Ddr3 => self.ps_to_nck(6_000).max(3),
+            DeviceType::LpDdr4 => self.ps_to_nck(7_500).max(5),
+            _ => self.ps_to_nck(6_000).max(4),
+        }
+    }
+
+    /// Minimum CKE pulse width: max(3 nCK, 5 ns)
+    pub fn t_cke_nck(&self) -> u32 {
+        self.ps_to_nck(5_000).max(3)
+    }
+
+    /// Minimum CKE low time in self refresh: tCKE + 1 nCK
+    pub fn t_ckesr_nck(&self) -> u32 {
+        self.t_cke_nck() + 1
+    }
+
+    /// Valid clock after self refresh or power down entry: max(5 nCK, 10 ns)
+    pub fn t_cksre_nck(&self) -> u32 {
+        self.ps_to_nck(10_000).max(5)
+    }
+
+    /// Valid clock before self refresh or power down exit: max(5 nCK, 10 ns)
+    pub fn t_cksrx_nck(&self) -> u32 {
+        self.ps_to_nck(10_000).max(5)
+    }
+
+    /// Mode register set command update delay: max(24 nCK, 15 ns)
+    /// (DDR3: max(12 nCK, 15 ns), LPDDR4 tMRD: max(10 nCK, 14 ns))
+    pub fn t_mod_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => self.ps_to_nck(15_000).max(12),
+            DeviceType::LpDdr4 => self.ps_to_nck(14_000).max(10),
+            _ => self.ps_to_nck(15_000).max(24),
+        }
+    }
+
+    /// Write to read delay, different bank group: max(2 nCK, 2.5 ns)
+    pub fn t_wtr_s_nck(&self) -> u32 {
+        self.ps_to_nck(2_500).max(2)
+    }
+
+    /// LPDDR4 has no long and short calibration, both are tZQCAL (1 us)
+    pub fn t_zq_init_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => 512,
+            DeviceType::LpDdr4 => self.ps_to_nck(1_000_000),
+            _ => 1024,
+        }
+    }
+
+    pub fn t_zq_oper_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => 256,
+            DeviceType::LpDdr4 => self.ps_to_nck(1_000_000),
+            _ => 512,
+        }
+    }
+
+    /// LPDDR4: tZQLAT, max(8 nCK, 30 ns)
+    pub fn t_zq_cs_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => 64,
+            DeviceType::LpDdr4 => self.ps_to_nck(30_000).max(8),
+            _ => 128,
+        }
     }
 
     pub fn parity_latency_nck(&self) -> u32 {
         if self.parity_en {
-            if self.speed_bin_mhz() < 2400 {
+            // JESD 79-4 Table 14
+            if self.speed_grade() < 2400 {
                 4
-            } else if self.speed_bin_mhz() < 2933 {
+            } else if self.speed_grade() < 2933 {
                 5
             } else {
                 6
@@ -664,20 +1362,20 @@ impl GeneralConfig {
         // UG1087: "Note that, depending on the PHY, if using RDIMM,
         // it may be necessary to use a value of WL + 1 to compensate
         // for the extra cycle of latency through the RDIMM"
-        self.ps_to_nck(self.cas_latency_ns() * 1000)
+        self.cas_latency_nck()
             + self.additive_latency_nck()
             + self.parity_latency_nck()
-            + matches!(self.module_config, ModuleConfig::Registered(_)) as u32
+            + self.rcd().is_some() as u32
     }
 
     pub fn write_latency_nck(&self) -> u32 {
         // UG1087: "Note that, depending on the PHY, if using RDIMM,
         // it may be necessary to use a value of WL + 1 to compensate
         // for the extra cycle of latency through the RDIMM"
-        self.ps_to_nck(self.cas_write_latency_ns() * 1000)
+        self.cas_write_latency_nck()
             + self.additive_latency_nck()
             + self.parity_latency_nck()
-            + matches!(self.module_config, ModuleConfig::Registered(_)) as u32
+            + self.rcd().is_some() as u32
     }
 
     pub fn t_xs_min_ns(&self) -> u32 {
@@ -693,7 +1391,9 @@ impl GeneralConfig {
     }
 
     pub fn t_dllk_min_nck(&self) -> u32 {
-        if self.speed_bin_mhz() < 2133 {
+        if self.device_type == DeviceType::Ddr3 {
+            512
+        } else if self.speed_bin_mhz() < 2133 {
             597
         } else if self.speed_bin_mhz() < 2666 {
             768
@@ -743,6 +1443,16 @@ impl GeneralConfig {
         map
     }
 
+    /// Number of HIF address bits [hif_addr_map](Self::hif_addr_map) maps to columns, bank
+    /// groups, banks and rows. The rank bits aren't counted: ADDRMAP0 leaves the chip selects
+    /// unmapped, so only rank 0 is addressed.
+    pub fn mapped_hif_addr_bits(&self) -> u32 {
+        self.hif_addr_map()
+            .iter()
+            .filter(|&bit| (HIF_COL_OFFSET..HIF_RANK_OFFSET).contains(bit))
+            .count() as u32
+    }
+
     // these are extra dumb, but again, idk what I'm doing
     pub fn bank_addr_map(&self) -> [u32; MAX_BANK_ADDR_BITS as usize] {
         let hif_addr_map = self.hif_addr_map();
@@ -801,61 +1511,160 @@ impl GeneralConfig {
     }
 
     pub fn t_wr_min_ns(&self) -> u32 {
-        // defined as 15 ns for all speed bins in JESD 79-4
-        15
+        // defined as 15 ns for all speed bins in JESD 79-4 and JESD 79-3, 18 ns in JESD209-4
+        match self.device_type {
+            DeviceType::LpDdr4 => 18,
+            _ => 15,
+        }
+    }
+
+    /// Read to read/write to write delay, same bank group (DDR3 and LPDDR4 have a fixed tCCD)
+    pub fn t_ccd_l_nck(&self) -> u32 {
+        match self.device_type {
+            DeviceType::Ddr3 => 4,
+            DeviceType::LpDdr4 => 8,
+            _ => self.ps_to_nck(self.t_ccd_l_min_ps).max(4),
+        }
     }
 
+    /// DDR3 and DDR4 MR0 (LPDDR4 has no MR0 to write)
     pub fn mr0(&self) -> u16 {
-        // calculate MR0 value for DDR4
-        // bits A12, 6-4, and 2 calculated from CL - JESD 79-4 Table 3
-        // but 12 will always be zero for the supported speed bins (I think?)
-        let cas_latency_ns = self.cas_latency_ns() as u16;
-        let mut mr0 = 0;
-        if cas_latency_ns <= 16 {
-            mr0 |= ((cas_latency_ns - 1) % 2) << 2;
-            mr0 |= ((cas_latency_ns - 9) / 2) & 0x7 << 4;
-        } else if cas_latency_ns % 2 == 1 {
-            mr0 |= (((cas_latency_ns + 1) / 2) % 2) << 2;
-            mr0 |= ((cas_latency_ns + 2) / 6) & 0x7 << 4;
-        } else {
-            mr0 |= ((cas_latency_ns / 2 + 1) % 2) << 2;
-            mr0 |= ((cas_latency_ns - 1) / 4) & 0x7 << 4;
-        }
-        // bits A13-9 calculated from write recovery/read-to-precharge time
-        // JESD 79-4 Table 2
-        let mut t_wr_min_nck = self.ps_to_nck(self.t_wr_min_ns() * 1000) as u16;
-        // for SOME reason, 22 (11) and 24 (12) are swapped in the table
-        if t_wr_min_nck == 22 {
-            // if yes { no }
-            t_wr_min_nck = 24;
-        } else if t_wr_min_nck == 24 {
-            t_wr_min_nck = 22;
-        }
-        mr0 |= (t_wr_min_nck / 2 - 5) & 0xf << 9;
-        // bits A1-0 calculated from burst length
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::mr0(self),
+            DeviceType::LpDdr4 => return 0,
+            _ => {}
+        }
+        // A12, A6-4, A2: CL (always has an encoding, see cas_latency_nck)
+        let mut mr0 = mr0_cas_latency(self.cas_latency_nck()).unwrap_or(0);
+        // A13, A11-9: write recovery/read-to-precharge
+        mr0 |= mr0_write_recovery(self.ps_to_nck(self.t_wr_min_ns() * 1000));
+        // A1-0: burst length
         // Zynq DDRC doesn't support on-the-fly burst length (0b01)
         if self.burst_len() == 4 {
             mr0 |= 0b10;
-        } else {
-            mr0 |= 0b00;
         }
         mr0
     }
+
+    pub fn mr1(&self) -> u16 {
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::mr1(self),
+            DeviceType::LpDdr4 => return lpddr4::mr1(self),
+            _ => {}
+        }
+        // A10-8: RTT_NOM
+        let mut mr1 = (self.rtt_nom as u16) << 8;
+        // A4-3: additive latency - JESD 79-4 Table 7
+        let cas_latency = self.cas_latency_nck();
+        mr1 |= match self.additive_latency_nck() {
+            0 => 0b00,
+            al if al + 1 == cas_latency => 0b01,
+            _ => 0b10,
+        } << 3;
+        // A2-1: output driver impedance
+        mr1 |= (self.drive_strength as u16) << 1;
+        // A0: DLL enable
+        mr1 | 1
+    }
+
+    pub fn mr2(&self) -> u16 {
+        match self.device_type {
+            DeviceType::Ddr3 => return ddr3::mr2(self),
+            DeviceType::LpDdr4 => return lpddr4::mr2(self),
+            _ => {}
+        }
+        // A12: write CRC
+        let mut mr2 = (self.crc_en as u16) << 12;
+        // A11-9: RTT_WR
+        mr2 |= (self.rtt_wr as u16) << 9;
+        // A5-3: CAS write latency - JESD 79-4 Table 8
+        mr2 |= match self.cas_write_latency_nck() {
+            9 => 0b000,
+            10 => 0b001,
+            11 => 0b010,
+            12 => 0b011,
+            14 => 0b100,
+            16 => 0b101,
+            18 => 0b110,
+            _ => 0b111,
+        } << 3;
+        mr2
+    }
+
+    pub fn mr3(&self) -> u16 {
+        match self.device_type {
+            // MPR off
+            DeviceType::Ddr3 => return 0,
+            DeviceType::LpDdr4 => return lpddr4::mr3(self),
+            _ => {}
+        }
+        // A10-9: write command latency with CRC and DM - JESD 79-4 Table 10
+        let mut mr3 = match self.speed_grade() {
+            1600 => 0b00,
+            1866..=2400 => 0b01,
+            _ => 0b10,
+        } << 9;
+        // A8-6: fine granularity refresh mode (fixed)
+        mr3 |= match self.fine_granularity_ref_mode {
+            FineGranularityRefMode::X1 => 0b000,
+            FineGranularityRefMode::X2 => 0b001,
+            FineGranularityRefMode::X4 => 0b010,
+        } << 6;
+        // A3: geardown mode
+        mr3 | (self.geardown as u16) << 3
+    }
+
+    /// MR4-MR6 only exist on DDR4
+    pub fn mr4(&self) -> u16 {
+        if self.device_type != DeviceType::Ddr4 {
+            return 0;
+        }
+        // A12: write preamble, A11: read preamble, A9: self refresh abort,
+        // A3: temperature controlled refresh mode, A2: extended range
+        (self.wr_preamble_2nck() as u16) << 12
+            | (self.rd_preamble_2nck as u16) << 11
+            | (self.self_ref_abort as u16) << 9
+            | (self.temp_ref_mode as u16) << 3
+            | (self.temp_ref_range as u16) << 2
+    }
+
+    pub fn mr5(&self) -> u16 {
+        if self.device_type != DeviceType::Ddr4 {
+            return 0;
+        }
+        // A12: read DBI, A11: write DBI, A10: data mask
+        let mut mr5 = (self.rd_dbi_en as u16) << 12
+            | (self.wr_dbi_en as u16) << 11
+            | (self.dm_en as u16) << 10;
+        // A8-6: RTT_PARK
+        mr5 |= (self.rtt_park as u16) << 6;
+        // A2-0: CA parity latency - JESD 79-4 Table 14
+        mr5 |= match self.parity_latency_nck() {
+            0 => 0b000,
+            4 => 0b001,
+            5 => 0b010,
+            _ => 0b011,
+        };
+        mr5
+    }
+
+    pub fn mr6(&self) -> u16 {
+        if self.device_type != DeviceType::Ddr4 {
+            return 0;
+        }
+        // A12-10: tCCD_L - JESD 79-4 Table 15
+        let t_ccd_l = self.t_ccd_l_nck().min(8) as u16;
+        // A6: VrefDQ range, A5-0: VrefDQ value - JESD 79-4 Table 16
+        (t_ccd_l - 4) << 10 | (self.vref_dq_range_2 as u16) << 6 | (self.vref_dq & 0x3f) as u16
+    }
 }
 
 // bytes 128-255
 // global index of section start (for readability)
 const MODULE_CONFIG_START: usize = 128;
 impl UnbufferedConfig {
-    pub fn from_spd_data(spd_data: &[u8; 128]) -> Self {
-        let expected_crc = ((spd_data[127] as u16) << 8) | (spd_data[126] as u16);
-        let actual_crc = crc(&spd_data[..126]);
-        assert_eq!(
-            expected_crc, actual_crc,
-            "CRC mismatch. Expected: {:#X}, actual: {:#X}",
-            expected_crc, actual_crc
-        );
-        debug!("CRC check passed");
+    pub fn from_spd_data(spd_data: &[u8; 128]) -> Result<Self, SpdError> {
+        check_crc(spd_data, MODULE_CONFIG_START)?;
 
         // 128
         let raw_card_extension = spd_data[128 - MODULE_CONFIG_START] >> 5;
@@ -872,7 +1681,7 @@ impl UnbufferedConfig {
         // 131
         let rank_1_mirrored = spd_data[131 - MODULE_CONFIG_START] & 0x1 == 0x1;
 
-        UnbufferedConfig {
+        Ok(UnbufferedConfig {
             raw_card_extension,
             module_nominal_height,
             module_max_thickness_back,
@@ -880,6 +1689,371 @@ impl UnbufferedConfig {
             ref_raw_card_rev,
             ref_raw_card,
             rank_1_mirrored,
+        })
+    }
+
+    pub fn to_spd_data(&self) -> [u8; 128] {
+        let mut spd_data = [0u8; 128];
+        spd_data[128 - MODULE_CONFIG_START] =
+            self.raw_card_extension << 5 | self.module_nominal_height;
+        spd_data[129 - MODULE_CONFIG_START] =
+            self.module_max_thickness_back << 4 | self.module_max_thickness_front;
+        spd_data[130 - MODULE_CONFIG_START] = self.ref_raw_card_rev << 5 | self.ref_raw_card;
+        spd_data[131 - MODULE_CONFIG_START] = self.rank_1_mirrored as u8;
+        set_crc(&mut spd_data);
+        spd_data
+    }
+}
+
+impl RcdConfig {
+    pub fn from_spd_data(spd_data: &[u8; 128]) -> Result<Self, SpdError> {
+        // 131
+        let attributes = spd_data[131 - MODULE_CONFIG_START];
+        let register_type = attributes >> 4;
+        let dram_rows = match attributes >> 2 & 0x3 {
+            0b00 => 0,
+            0b01 => 1,
+            0b10 => 2,
+            _ => 4,
+        };
+        let registers = match attributes & 0x3 {
+            0b00 => 0,
+            0b01 => 1,
+            0b10 => 2,
+            _ => return Err(bad_data(131, attributes, "reserved number of registers")),
+        };
+
+        // 132
+        let heat_spreader = spd_data[132 - MODULE_CONFIG_START] & 0x80 == 0x80;
+
+        // 133-134
+        let manufacturer_id = (spd_data[134 - MODULE_CONFIG_START] as u16) << 8
+            | spd_data[133 - MODULE_CONFIG_START] as u16;
+
+        // 135
+        let revision = spd_data[135 - MODULE_CONFIG_START];
+
+        // 136
+        let rank_1_mirrored = spd_data[136 - MODULE_CONFIG_START] & 0x1 == 0x1;
+
+        // 137
+        let drive = spd_data[137 - MODULE_CONFIG_START];
+        let cs_drive = rcd_drive_strength(drive >> 6);
+        let ca_drive = rcd_drive_strength(drive >> 4);
+        let odt_drive = rcd_drive_strength(drive >> 2);
+        let cke_drive = rcd_drive_strength(drive);
+
+        // 138
+        let clock_drive = spd_data[138 - MODULE_CONFIG_START];
+        let y1_y3_drive = rcd_drive_strength(clock_drive >> 2);
+        let y0_y2_drive = rcd_drive_strength(clock_drive);
+
+        Ok(RcdConfig {
+            register_type,
+            dram_rows,
+            registers,
+            heat_spreader,
+            manufacturer_id,
+            revision,
+            rank_1_mirrored,
+            cke_drive,
+            odt_drive,
+            ca_drive,
+            cs_drive,
+            y0_y2_drive,
+            y1_y3_drive,
+        })
+    }
+
+    /// Store bytes 131-138 in the module specific section `spd_data`.
+    pub fn write_spd_data(&self, spd_data: &mut [u8; 128]) {
+        let dram_rows = match self.dram_rows {
+            1 => 0b01,
+            2 => 0b10,
+            4 => 0b11,
+            _ => 0b00,
+        };
+        spd_data[131 - MODULE_CONFIG_START] =
+            self.register_type << 4 | dram_rows << 2 | self.registers;
+        spd_data[132 - MODULE_CONFIG_START] = (self.heat_spreader as u8) << 7;
+        spd_data[133 - MODULE_CONFIG_START] = self.manufacturer_id as u8;
+        spd_data[134 - MODULE_CONFIG_START] = (self.manufacturer_id >> 8) as u8;
+        spd_data[135 - MODULE_CONFIG_START] = self.revision;
+        spd_data[136 - MODULE_CONFIG_START] = self.rank_1_mirrored as u8;
+        spd_data[137 - MODULE_CONFIG_START] = (self.cs_drive as u8) << 6
+            | (self.ca_drive as u8) << 4
+            | (self.odt_drive as u8) << 2
+            | self.cke_drive as u8;
+        spd_data[138 - MODULE_CONFIG_START] =
+            (self.y1_y3_drive as u8) << 2 | self.y0_y2_drive as u8;
+    }
+}
+
+impl RegisteredConfig {
+    pub fn from_spd_data(spd_data: &[u8; 128]) -> Result<Self, SpdError> {
+        check_crc(spd_data, MODULE_CONFIG_START)?;
+
+        // 128
+        let raw_card_extension = spd_data[128 - MODULE_CONFIG_START] >> 5;
+        let module_nominal_height = spd_data[128 - MODULE_CONFIG_START] & 0x1f;
+
+        // 129
+        let module_max_thickness_back = spd_data[129 - MODULE_CONFIG_START] >> 4;
+        let module_max_thickness_front = spd_data[129 - MODULE_CONFIG_START] & 0xf;
+
+        // 130
+        let ref_raw_card_rev = spd_data[130 - MODULE_CONFIG_START] >> 5 & 0x3;
+        let ref_raw_card = spd_data[130 - MODULE_CONFIG_START] & 0x1f;
+
+        // 131-138
+        let rcd = RcdConfig::from_spd_data(spd_data)?;
+
+        Ok(RegisteredConfig {
+            raw_card_extension,
+            module_nominal_height,
+            module_max_thickness_back,
+            module_max_thickness_front,
+            ref_raw_card_rev,
+            ref_raw_card,
+            rcd,
+        })
+    }
+
+    pub fn to_spd_data(&self) -> [u8; 128] {
+        let mut spd_data = [0u8; 128];
+        spd_data[128 - MODULE_CONFIG_START] =
+            self.raw_card_extension << 5 | self.module_nominal_height;
+        spd_data[129 - MODULE_CONFIG_START] =
+            self.module_max_thickness_back << 4 | self.module_max_thickness_front;
+        spd_data[130 - MODULE_CONFIG_START] = self.ref_raw_card_rev << 5 | self.ref_raw_card;
+        self.rcd.write_spd_data(&mut spd_data);
+        set_crc(&mut spd_data);
+        spd_data
+    }
+}
+
+impl LoadReducedConfig {
+    pub fn from_spd_data(spd_data: &[u8; 128]) -> Result<Self, SpdError> {
+        check_crc(spd_data, MODULE_CONFIG_START)?;
+
+        // 128
+        let raw_card_extension = spd_data[128 - MODULE_CONFIG_START] >> 5;
+        let module_nominal_height = spd_data[128 - MODULE_CONFIG_START] & 0x1f;
+
+        // 129
+        let module_max_thickness_back = spd_data[129 - MODULE_CONFIG_START] >> 4;
+        let module_max_thickness_front = spd_data[129 - MODULE_CONFIG_START] & 0xf;
+
+        // 130
+        let ref_raw_card_rev = spd_data[130 - MODULE_CONFIG_START] >> 5 & 0x3;
+        let ref_raw_card = spd_data[130 - MODULE_CONFIG_START] & 0x1f;
+
+        // 131-138
+        let rcd = RcdConfig::from_spd_data(spd_data)?;
+
+        // 138
+        let bck_drive_strong = spd_data[138 - MODULE_CONFIG_START] & 0x40 == 0x40;
+        let bcom_drive = rcd_drive_strength(spd_data[138 - MODULE_CONFIG_START] >> 4);
+
+        // 139
+        let db_revision = spd_data[139 - MODULE_CONFIG_START];
+
+        // 140-143 & 155
+        let mut dram_vref_dq = [0u8; 4];
+        let mut dram_vref_dq_range_2 = [false; 4];
+        for rank in 0..4 {
+            dram_vref_dq[rank] = spd_data[140 + rank - MODULE_CONFIG_START] & 0x3f;
+            dram_vref_dq_range_2[rank] = spd_data[155 - MODULE_CONFIG_START] >> rank & 0x1 == 0x1;
         }
+
+        // 144 & 155
+        let db_vref_dq = spd_data[144 - MODULE_CONFIG_START];
+        let db_vref_dq_range_2 = spd_data[155 - MODULE_CONFIG_START] & 0x10 == 0x10;
+
+        let mut db_mdq_rtt = [0u8; 3];
+        let mut db_mdq_drive = [0u8; 3];
+        let mut dram_drive_strength = [DriveStrength::Rzq7; 3];
+        let mut dram_rtt_wr = [RttWr::Disabled; 3];
+        let mut dram_rtt_nom = [Rtt::Disabled; 3];
+        let mut dram_rtt_park = [[Rtt::Disabled; 2]; 3];
+        for range in 0..3 {
+            // 145-147
+            db_mdq_rtt[range] = spd_data[145 + range - MODULE_CONFIG_START] >> 4 & 0x7;
+            db_mdq_drive[range] = spd_data[145 + range - MODULE_CONFIG_START] & 0x7;
+
+            // 148
+            let drive = spd_data[148 - MODULE_CONFIG_START];
+            dram_drive_strength[range] = match drive >> (2 * range) & 0x3 {
+                0b00 => DriveStrength::Rzq7,
+                0b01 => DriveStrength::Rzq5,
+                _ => return Err(bad_data(148, drive, "reserved DRAM drive strength")),
+            };
+
+            // 149-151
+            let byte = 149 + range;
+            let odt = spd_data[byte - MODULE_CONFIG_START];
+            dram_rtt_wr[range] = match odt >> 3 & 0x7 {
+                0b000 => RttWr::Disabled,
+                0b001 => RttWr::Rzq2,
+                0b010 => RttWr::Rzq1,
+                0b011 => RttWr::HiZ,
+                0b100 => RttWr::Rzq3,
+                _ => return Err(bad_data(byte, odt, "reserved DRAM RTT_WR")),
+            };
+            dram_rtt_nom[range] = rtt(odt);
+
+            // 152-154
+            let rtt_park = spd_data[152 + range - MODULE_CONFIG_START];
+            dram_rtt_park[range] = [rtt(rtt_park), rtt(rtt_park >> 3)];
+        }
+
+        // 156
+        let db_dfe = spd_data[156 - MODULE_CONFIG_START] & 0x2 == 0x2;
+        let db_gain_adjust = spd_data[156 - MODULE_CONFIG_START] & 0x1 == 0x1;
+
+        Ok(LoadReducedConfig {
+            raw_card_extension,
+            module_nominal_height,
+            module_max_thickness_back,
+            module_max_thickness_front,
+            ref_raw_card_rev,
+            ref_raw_card,
+            rcd,
+            bck_drive_strong,
+            bcom_drive,
+            db_revision,
+            dram_vref_dq,
+            dram_vref_dq_range_2,
+            db_vref_dq,
+            db_vref_dq_range_2,
+            db_mdq_rtt,
+            db_mdq_drive,
+            dram_drive_strength,
+            dram_rtt_wr,
+            dram_rtt_nom,
+            dram_rtt_park,
+            db_dfe,
+            db_gain_adjust,
+        })
+    }
+
+    pub fn to_spd_data(&self) -> [u8; 128] {
+        let mut spd_data = [0u8; 128];
+        spd_data[128 - MODULE_CONFIG_START] =
+            self.raw_card_extension << 5 | self.module_nominal_height;
+        spd_data[129 - MODULE_CONFIG_START] =
+            self.module_max_thickness_back << 4 | self.module_max_thickness_front;
+        spd_data[130 - MODULE_CONFIG_START] = self.ref_raw_card_rev << 5 | self.ref_raw_card;
+        self.rcd.write_spd_data(&mut spd_data);
+        spd_data[138 - MODULE_CONFIG_START] |=
+            (self.bck_drive_strong as u8) << 6 | (self.bcom_drive as u8) << 4;
+        spd_data[139 - MODULE_CONFIG_START] = self.db_revision;
+        for rank in 0..4 {
+            spd_data[140 + rank - MODULE_CONFIG_START] = self.dram_vref_dq[rank];
+            spd_data[155 - MODULE_CONFIG_START] |= (self.dram_vref_dq_range_2[rank] as u8) << rank;
+        }
+        spd_data[144 - MODULE_CONFIG_START] = self.db_vref_dq;
+        spd_data[155 - MODULE_CONFIG_START] |= (self.db_vref_dq_range_2 as u8) << 4;
+        for range in 0..3 {
+            spd_data[145 + range - MODULE_CONFIG_START] =
+                self.db_mdq_rtt[range] << 4 | self.db_mdq_drive[range];
+            spd_data[148 - MODULE_CONFIG_START] |=
+                (self.dram_drive_strength[range] as u8) << (2 * range);
+            spd_data[149 + range - MODULE_CONFIG_START] =
+                (self.dram_rtt_wr[range] as u8) << 3 | self.dram_rtt_nom[range] as u8;
+            spd_data[152 + range - MODULE_CONFIG_START] =
+                (self.dram_rtt_park[range][1] as u8) << 3 | self.dram_rtt_park[range][0] as u8;
+        }
+        spd_data[156 - MODULE_CONFIG_START] = (self.db_dfe as u8) << 1 | self.db_gain_adjust as u8;
+        set_crc(&mut spd_data);
+        spd_data
     }
 }
+
+// bytes 320-383
+// global index of section start
+const MODULE_INFO_START: usize = 320;
+impl ModuleInfo {
+    /// Decode the supplier's data. It's informational only, so a bad date is reported as not
+    /// given instead of failing the whole SPD.
+    pub fn from_spd_data(spd_data: &[u8; 64]) -> Self {
+        let byte = |i: usize| spd_data[i - MODULE_INFO_START];
+
+        // 323-324
+        let year = match from_bcd(byte(323)) {
+            Some(0) | None => 0,
+            Some(year) => 2000 + year as u16,
+        };
+        let week = from_bcd(byte(324)).unwrap_or(0);
+
+        // 329-348
+        let mut part_number = [0_u8; 20];
+        part_number.copy_from_slice(&spd_data[329 - MODULE_INFO_START..349 - MODULE_INFO_START]);
+
+        ModuleInfo {
+            // 320-321
+            module_manufacturer_id: (byte(321) as u16) << 8 | byte(320) as u16,
+            // 322
+            location: byte(322),
+            year,
+            week,
+            // 325-328
+            serial_number: u32::from_be_bytes([byte(325), byte(326), byte(327), byte(328)]),
+            part_number,
+            // 349
+            revision: byte(349) as u16,
+            // 350-351
+            dram_manufacturer_id: (byte(351) as u16) << 8 | byte(350) as u16,
+            // 352
+            dram_stepping: byte(352),
+        }
+    }
+
+    pub fn to_spd_data(&self) -> [u8; 64] {
+        let mut spd_data = [0u8; 64];
+        let mut set = |i: usize, value: u8| spd_data[i - MODULE_INFO_START] = value;
+
+        // 320-321
+        set(320, self.module_manufacturer_id as u8);
+        set(321, (self.module_manufacturer_id >> 8) as u8);
+        // 322
+        set(322, self.location);
+        // 323-324
+        set(323, to_bcd(self.year.saturating_sub(2000)));
+        set(324, to_bcd(self.week.into()));
+        // 325-328
+        for (i, &b) in self.serial_number.to_be_bytes().iter().enumerate() {
+            set(325 + i, b);
+        }
+        // 349
+        set(349, self.revision as u8);
+        // 350-351
+        set(350, self.dram_manufacturer_id as u8);
+        set(351, (self.dram_manufacturer_id >> 8) as u8);
+        // 352
+        set(352, self.dram_stepping);
+
+        // 329-348
+        spd_data[329 - MODULE_INFO_START..349 - MODULE_INFO_START]
+            .copy_from_slice(&self.part_number);
+        spd_data
+    }
+
+    /// Part number without the padding
+    pub fn part_number(&self) -> &str {
+        core::str::from_utf8(&self.part_number)
+            .unwrap_or("")
+            .trim_end_matches(|c| c == ' ' || c == '\0')
+    }
+
+    pub fn module_manufacturer(&self) -> Option<&'static str> {
+        jep106::manufacturer(self.module_manufacturer_id)
+    }
+
+    pub fn dram_manufacturer(&self) -> Option<&'static str> {
+        jep106::manufacturer(self.dram_manufacturer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests;
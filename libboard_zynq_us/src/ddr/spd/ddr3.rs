@@ -0,0 +1,355 @@
+//! DDR3 SPD data decoding and mode registers
+//!
+//! JEDEC Standard No. 21-C Release 23A Annex K: Serial Presence Detect (SPD) for DDR3 SDRAM Modules
+//!
+//! The DDR3 parameters are stored in the matching [GeneralConfig] fields. DDR3 has no bank
+//! groups and a single tRRD and tRFC, which fill both the `_s`/`_l` and the `rfc1/2/4` fields.
+use super::*;
+
+/// Byte 2 of DDR3 SPDs
+pub const DEVICE_TYPE: u8 = 0x0b;
+
+// there's no t_ckavg_max byte: 3.3 ns for all speed bins with the DLL on (JESD 79-3 Table 67)
+const T_CKAVG_MAX_PS: u32 = 3_300;
+// t_ckavg_min of each speed bin
+const SPEED_GRADES: [(u32, u32); 5] = [
+    (2500, 800),
+    (1875, 1066),
+    (1500, 1333),
+    (1250, 1600),
+    (1071, 1866),
+];
+
+/// Check the CRC in bytes 126-127, which covers bytes 0-116 if bit 7 of byte 0 is set and
+/// bytes 0-125 otherwise.
+fn check_crc(spd_data: &[u8; 256]) -> Result<(), SpdError> {
+    let len = if spd_data[0] & 0x80 == 0x80 { 117 } else { 126 };
+    let expected = ((spd_data[127] as u16) << 8) | (spd_data[126] as u16);
+    let actual = crc(&spd_data[..len]);
+    if expected != actual {
+        return Err(SpdError::Crc {
+            start: 0,
+            expected,
+            actual,
+        });
+    }
+    debug!("CRC check passed");
+    Ok(())
+}
+
+pub fn from_spd_data(spd_data: &[u8; 256]) -> Result<GeneralConfig, SpdError> {
+    check_crc(spd_data)?;
+
+    // 0
+    let spd_bytes_total: u16 = match spd_data[0] >> 4 & 0x7 {
+        0b001 => 256,
+        _ => return Err(bad_data(0, spd_data[0], "SPD size")),
+    };
+    let spd_bytes_used: u16 = match spd_data[0] & 0xf {
+        0b0001 => 128,
+        0b0010 => 176,
+        0b0011 => 256,
+        _ => return Err(bad_data(0, spd_data[0], "SPD size")),
+    };
+
+    // 1
+    let spd_encoding = spd_data[1] >> 4;
+    let spd_additions = spd_data[1] & 0xf;
+
+    // 3 & 60-63
+    let module_type = spd_data[3] & 0xf;
+    let module_config = match module_type {
+        // UDIMM, SO-DIMM, Micro-DIMM, Mini-UDIMM, 72b-SO-UDIMM, 16b-SO-DIMM, 32b-SO-DIMM
+        0b0010 | 0b0011 | 0b0100 | 0b0110 | 0b1000 | 0b1100 | 0b1101 => {
+            ModuleConfig::Unbuffered(UnbufferedConfig {
+                raw_card_extension: spd_data[60] >> 5,
+                module_nominal_height: spd_data[60] & 0x1f,
+                module_max_thickness_back: spd_data[61] >> 4,
+                module_max_thickness_front: spd_data[61] & 0xf,
+                ref_raw_card_rev: spd_data[62] >> 5 & 0x3,
+                ref_raw_card: spd_data[62] & 0x1f,
+                rank_1_mirrored: spd_data[63] & 0x1 == 0x1,
+            })
+        }
+        _ => return Err(bad_data(3, spd_data[3], "module type")),
+    };
+
+    // 4
+    if spd_data[4] >> 4 & 0x7 > 0b011 || spd_data[4] & 0xf > 0b0110 {
+        return Err(bad_data(4, spd_data[4], "reserved density or bank bits"));
+    }
+    let bank_addr_bits = (spd_data[4] >> 4 & 0x7) + 3;
+    let capacity_megabits: u16 = 256 << (spd_data[4] & 0xf);
+
+    // 5
+    if spd_data[5] >> 3 & 0x7 > 0b100 || spd_data[5] & 0x7 > 0b011 {
+        return Err(bad_data(5, spd_data[5], "reserved address bits"));
+    }
+    let row_addr_bits = (spd_data[5] >> 3 & 0x7) + 12;
+    let col_addr_bits = (spd_data[5] & 0x7) + 9;
+
+    // 7
+    if spd_data[7] >> 3 & 0x7 > 0b011 || spd_data[7] & 0x7 > 0b011 {
+        return Err(bad_data(7, spd_data[7], "reserved ranks or device width"));
+    }
+    let package_ranks = (spd_data[7] >> 3 & 0x7) + 1;
+    let device_width: u8 = 4 << (spd_data[7] & 0x7);
+
+    // 8
+    if spd_data[8] >> 3 & 0x3 > 0b01 || spd_data[8] & 0x7 > 0b011 {
+        return Err(bad_data(8, spd_data[8], "reserved bus width"));
+    }
+    let bus_width_extension = (spd_data[8] >> 3 & 0x3) << 3;
+    let bus_width: u8 = 8 << (spd_data[8] & 0x7);
+
+    // 9-11: FTB (ps) and MTB (ns) as dividend/divisor
+    if spd_data[9] != 0x11 {
+        return Err(bad_data(9, spd_data[9], "unsupported timebase"));
+    }
+    if spd_data[10] != 1 || spd_data[11] != 8 {
+        return Err(bad_data(10, spd_data[10], "unsupported timebase"));
+    }
+    let mtb_ps = 125;
+    let ftb_ps = 1;
+    let time_ps = |byte: usize, offset_byte: Option<usize>| {
+        let offset_ftb = offset_byte.map_or(0, |b| spd_data[b] as i8);
+        get_time_ps(mtb_ps, ftb_ps, spd_data[byte] as u16, offset_ftb)
+    };
+
+    // 12 & 34
+    let t_ckavg_min_ps = time_ps(12, Some(34));
+
+    // 14-15: bit 0 is CL = 4
+    let supported_cas_latencies = spd_data[14] as u32 | (spd_data[15] as u32) << 8;
+
+    // 16 & 35
+    let t_aa_min_ps = time_ps(16, Some(35));
+    // 18 & 36
+    let t_rcd_min_ps = time_ps(18, Some(36));
+    // 19
+    let t_rrd_min_ps = time_ps(19, None);
+    // 20 & 37
+    let t_rp_min_ps = time_ps(20, Some(37));
+
+    // 21: upper nibbles, 22: t_ras_min LSB, 23: t_rc_min LSB, 38: t_rc offset
+    let n_mtb_t_ras = ((spd_data[21] as u16 & 0x0f) << 8) | spd_data[22] as u16;
+    let n_mtb_t_rc = ((spd_data[21] as u16 & 0xf0) << 4) | spd_data[23] as u16;
+    let t_ras_min_ps = get_time_ps(mtb_ps, ftb_ps, n_mtb_t_ras, 0);
+    let t_rc_min_ps = get_time_ps(mtb_ps, ftb_ps, n_mtb_t_rc, spd_data[38] as i8);
+
+    // 24-25
+    let n_mtb_t_rfc = (spd_data[25] as u16) << 8 | spd_data[24] as u16;
+    let t_rfc_min_ps = get_time_ps(mtb_ps, ftb_ps, n_mtb_t_rfc, 0);
+
+    // 28 (3-0): upper nibble, 29: LSB
+    let n_mtb_t_faw = (spd_data[28] as u16 & 0xf) << 8 | spd_data[29] as u16;
+    let t_faw_min_ps = get_time_ps(mtb_ps, ftb_ps, n_mtb_t_faw, 0);
+
+    // 32
+    let has_thermal_sensor = spd_data[32] & 0x80 == 0x80;
+
+    // 33
+    if spd_data[33] & 0x3 == 0x3 {
+        return Err(bad_data(33, spd_data[33], "reserved signal loading"));
+    }
+    let package_type = if spd_data[33] & 0x80 == 0x80 {
+        PackageType::NonMonolithic
+    } else {
+        PackageType::Monolithic
+    };
+    // 1, 2, 4 or 8 dies, 0 = unspecified
+    let die_count = 1 << (spd_data[33] >> 4 & 0x3);
+    let signal_loading = match spd_data[33] & 0x3 {
+        0b00 => SignalLoading::Unspecified,
+        0b01 => SignalLoading::MultiLoadStack,
+        _ => SignalLoading::SingleLoadStack,
+    };
+
+    // 41: same encoding as DDR4 byte 7
+    if spd_data[41] >> 4 & 0x3 == 0x3 || spd_data[41] & 0x7 == 0x7 || (spd_data[41] & 0xf) > 8 {
+        return Err(bad_data(41, spd_data[41], "reserved tMAW or MAC"));
+    }
+    let t_maw: u16 = 8192 >> (spd_data[41] >> 4 & 0x3);
+    let mac = spd_data[41] & 0xf;
+
+    // 117-149
+    let module_info = module_info(spd_data);
+
+    Ok(GeneralConfig {
+        spd_bytes_total,
+        spd_bytes_used,
+        spd_encoding,
+        spd_additions,
+        device_type: DeviceType::Ddr3,
+        module_type,
+        module_config,
+        bg_addr_bits: 0,
+        bank_addr_bits,
+        capacity_megabits,
+        row_addr_bits,
+        col_addr_bits,
+        package_type,
+        die_count,
+        signal_loading,
+        t_maw,
+        mac,
+        package_ranks,
+        device_width,
+        bus_width_extension,
+        bus_width,
+        has_thermal_sensor,
+        mtb_ps,
+        ftb_ps,
+        t_ckavg_min_ps,
+        t_ckavg_max_ps: T_CKAVG_MAX_PS,
+        supported_cas_latencies,
+        t_aa_min_ps,
+        t_rcd_min_ps,
+        t_rp_min_ps,
+        t_ras_min_ps,
+        t_rc_min_ps,
+        t_rfc1_min_ps: t_rfc_min_ps,
+        t_rfc2_min_ps: t_rfc_min_ps,
+        t_rfc4_min_ps: t_rfc_min_ps,
+        t_faw_min_ps,
+        t_rrd_s_min_ps: t_rrd_min_ps,
+        t_rrd_l_min_ps: t_rrd_min_ps,
+        module_info,
+        t_ck_ps: t_ckavg_min_ps,
+        ..GeneralConfig::default()
+    })
+}
+
+/// Supplier's data in bytes 117-149, laid out like DDR4 bytes 320-352 apart from the part
+/// number being 2 characters shorter, a 2 byte revision and no DRAM stepping.
+fn module_info(spd_data: &[u8; 256]) -> ModuleInfo {
+    // 120-121
+    let year = match from_bcd(spd_data[120]) {
+        Some(0) | None => 0,
+        Some(year) => 2000 + year as u16,
+    };
+    let week = from_bcd(spd_data[121]).unwrap_or(0);
+
+    // 128-145
+    let mut part_number = [b' '; 20];
+    part_number[..18].copy_from_slice(&spd_data[128..146]);
+
+    ModuleInfo {
+        // 117-118
+        module_manufacturer_id: (spd_data[118] as u16) << 8 | spd_data[117] as u16,
+        // 119
+        location: spd_data[119],
+        year,
+        week,
+        // 122-125
+        serial_number: u32::from_be_bytes([
+            spd_data[122],
+            spd_data[123],
+            spd_data[124],
+            spd_data[125],
+        ]),
+        part_number,
+        // 146-147
+        revision: (spd_data[146] as u16) << 8 | spd_data[147] as u16,
+        // 148-149
+        dram_manufacturer_id: (spd_data[149] as u16) << 8 | spd_data[148] as u16,
+        dram_stepping: 0,
+    }
+}
+
+/// JEDEC speed bin (MT/s) the operating clock falls in.
+pub fn speed_grade(config: &GeneralConfig) -> u32 {
+    SPEED_GRADES
+        .iter()
+        .find(|&&(t_ck_ps, _)| config.t_ck_ps >= t_ck_ps)
+        .map_or(2133, |&(_, rate)| rate)
+}
+
+/// MR0 bits A6-4 and A2 for `cl`, `None` if it has no encoding - JESD 79-3 Table 17.
+fn mr0_cas_latency(cl: u32) -> Option<u16> {
+    match cl {
+        5..=11 => Some(((cl - 4) as u16) << 4),
+        12..=16 => Some(((cl - 12) as u16) << 4 | 1 << 2),
+        _ => None,
+    }
+}
+
+/// Lowest CAS latency supported by the module that meets t_aa_min at the operating clock.
+pub fn cas_latency_nck(config: &GeneralConfig) -> u32 {
+    let min_cl = config.ps_to_nck(config.t_aa_min_ps);
+    // bit 0 of supported_cas_latencies is CL = 4
+    let cl = (min_cl.max(5)..=18)
+        .find(|cl| config.supported_cas_latencies >> (cl - 4) & 1 == 1)
+        .unwrap_or(min_cl);
+    (cl..=16)
+        .find(|&cl| mr0_cas_latency(cl).is_some())
+        .unwrap_or(16)
+}
+
+/// CAS write latency - JESD 79-3 Table 68ff.
+pub fn cas_write_latency_nck(config: &GeneralConfig) -> u32 {
+    match speed_grade(config) {
+        800 => 5,
+        1066 => 6,
+        1333 => 7,
+        1600 => 8,
+        1866 => 9,
+        _ => 10,
+    }
+}
+
+pub fn mr0(config: &GeneralConfig) -> u16 {
+    // A12: DLL on during precharge power down (fast exit)
+    let mut mr0 = 1 << 12;
+    // A11-9: write recovery, rounded up to the next encoding
+    mr0 |= match config.ps_to_nck(config.t_wr_min_ns() * 1000) {
+        0..=5 => 0b001,
+        6 => 0b010,
+        7 => 0b011,
+        8 => 0b100,
+        9..=10 => 0b101,
+        11..=12 => 0b110,
+        13..=14 => 0b111,
+        _ => 0b000,
+    } << 9;
+    // A6-4, A2: CL
+    mr0 |= mr0_cas_latency(config.cas_latency_nck()).unwrap_or(0);
+    // A1-0: fixed BL8
+    mr0
+}
+
+pub fn mr1(config: &GeneralConfig) -> u16 {
+    // A9, A6, A2: RTT_NOM (RZQ/12 and RZQ/8 have no DDR4 equivalent)
+    let rtt_nom: u16 = match config.rtt_nom {
+        Rtt::Rzq4 => 0b001,
+        Rtt::Rzq2 => 0b010,
+        Rtt::Rzq6 => 0b011,
+        _ => 0b000,
+    };
+    let mut mr1 = (rtt_nom & 0x4) << 7 | (rtt_nom & 0x2) << 5 | (rtt_nom & 0x1) << 2;
+    // A4-3: additive latency
+    mr1 |= match config.additive_latency_nck() {
+        0 => 0b00,
+        al if al + 1 == config.cas_latency_nck() => 0b01,
+        _ => 0b10,
+    } << 3;
+    // A5, A1: output driver impedance, RZQ/6 for anything but RZQ/7
+    if config.drive_strength == DriveStrength::Rzq7 {
+        mr1 |= 1 << 1;
+    }
+    // A0 = 0: DLL enable
+    mr1
+}
+
+pub fn mr2(config: &GeneralConfig) -> u16 {
+    // A10-9: RTT_WR (RZQ/4 has no DDR4 equivalent)
+    let rtt_wr: u16 = match config.rtt_wr {
+        RttWr::Rzq2 => 0b10,
+        _ => 0b00,
+    };
+    // A7: self refresh temperature range
+    rtt_wr << 9
+        | (config.temp_ref_range as u16) << 7
+        // A5-3: CWL
+        | ((config.cas_write_latency_nck() - 5) as u16) << 3
+}
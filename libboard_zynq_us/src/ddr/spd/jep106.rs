@@ -0,0 +1,44 @@
+//! JEDEC JEP-106 manufacturer identification codes
+//!
+//! Only the DRAM, module and register vendors likely to show up in an SPD are listed.
+
+// bank (number of 0x7f continuation codes + 1), ID code with odd parity in bit 7, name
+const MANUFACTURERS: [(u8, u8, &str); 26] = [
+    (1, 0x01, "AMD"),
+    (1, 0x04, "Fujitsu"),
+    (1, 0x1c, "Mitsubishi"),
+    (1, 0x2c, "Micron"),
+    (1, 0x49, "Xilinx"),
+    (1, 0x4f, "Transcend"),
+    (1, 0x51, "Qimonda"),
+    (1, 0x7a, "Apacer"),
+    (1, 0x89, "Intel"),
+    (1, 0x94, "Smart Modular"),
+    (1, 0x97, "Texas Instruments"),
+    (1, 0x98, "Toshiba"),
+    (1, 0xad, "SK Hynix"),
+    (1, 0xb3, "IDT"),
+    (1, 0xc1, "Infineon"),
+    (1, 0xce, "Samsung"),
+    (1, 0xd5, "ISSI"),
+    (1, 0xda, "Winbond"),
+    (1, 0xfe, "Elpida"),
+    (2, 0x98, "Kingston"),
+    (3, 0x9e, "Corsair"),
+    (4, 0x0b, "Nanya"),
+    (5, 0xcb, "ADATA"),
+    (5, 0xcd, "G.Skill"),
+    (5, 0xef, "Team Group"),
+    (7, 0x32, "Montage"),
+];
+
+/// Name of the manufacturer with the SPD encoded `id`: continuation count (with parity) in the
+/// low byte, ID code in the high byte.
+pub fn manufacturer(id: u16) -> Option<&'static str> {
+    let bank = (id as u8 & 0x7f) + 1;
+    let code = (id >> 8) as u8;
+    MANUFACTURERS
+        .iter()
+        .find(|&&(b, c, _)| b == bank && c == code)
+        .map(|&(_, _, name)| name)
+}
@@ -0,0 +1,859 @@
+//! Host tests for SPD decoding, encoding and the derived DDR3 and DDR4 parameters
+//!
+//! The images in testdata/spd are synthetic, not dumps of real EEPROMs: they were assembled byte
+//! by byte from the module and DRAM datasheets (timings, organisation, manufacturing data) with
+//! CRCs computed independently of this crate.
+use super::*;
+
+const SYNTHETIC_MTA4ATF51264HZ_2G6: &[u8; 512] =
+    include_bytes!("../../../testdata/spd/synthetic_mta4atf51264hz_2g6.bin");
+const SYNTHETIC_MTA8ATF1G64HZ_2G3: &[u8; 512] =
+    include_bytes!("../../../testdata/spd/synthetic_mta8atf1g64hz_2g3.bin");
+const SYNTHETIC_MTA16ATF2G64HZ_2G6: &[u8; 512] =
+    include_bytes!("../../../testdata/spd/synthetic_mta16atf2g64hz_2g6.bin");
+const SYNTHETIC_MTA9ASF1G72AZ_2G6: &[u8; 512] =
+    include_bytes!("../../../testdata/spd/synthetic_mta9asf1g72az_2g6.bin");
+
+const CORPUS: [&[u8; 512]; 4] = [
+    SYNTHETIC_MTA4ATF51264HZ_2G6,
+    SYNTHETIC_MTA8ATF1G64HZ_2G3,
+    SYNTHETIC_MTA16ATF2G64HZ_2G6,
+    SYNTHETIC_MTA9ASF1G72AZ_2G6,
+];
+
+fn set_crcs(spd_data: &mut [u8; 512]) {
+    set_crc(&mut spd_data[..128]);
+    set_crc(&mut spd_data[128..256]);
+}
+
+/// xorshift64, good enough to drive the fuzz tests deterministically
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u32) -> u32 {
+        (self.next() % n as u64) as u32
+    }
+
+    fn range(&mut self, lo: u32, hi: u32) -> u32 {
+        lo + self.below(hi - lo + 1)
+    }
+
+    fn bool(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+}
+
+/// SPD image with `bytes` set and every other byte 0, without CRCs
+fn spd_image(bytes: &[(usize, u8)]) -> [u8; 512] {
+    let mut spd_data = [0u8; 512];
+    for &(byte, value) in bytes.iter() {
+        spd_data[byte] = value;
+    }
+    spd_data
+}
+
+/// Single rank DDR4-2133 SO-DIMM with 8 Gb x16 devices, CL 10-16, tAA = 14.062 ns
+fn ddr4_2133_sodimm_spd() -> [u8; 512] {
+    let mut spd_data = spd_image(&[
+        (0, 0x23),
+        (1, 0x11),
+        (2, 0x0c),
+        (3, 0x03),
+        (4, 0x45),
+        (5, 0x21),
+        (7, 0x08),
+        (11, 0x03),
+        (12, 0x02),
+        (13, 0x03),
+        (14, 0x80),
+        (18, 0x08),
+        (19, 0x0d),
+        (20, 0xf8),
+        (21, 0x03),
+        (24, 0x71),
+        (25, 0x71),
+        (26, 0x71),
+        (27, 0x11),
+        (28, 0x08),
+        (29, 0x78),
+        (30, 0xf0),
+        (31, 0x0a),
+        (32, 0x20),
+        (33, 0x08),
+        (35, 0x05),
+        (37, 0xf0),
+        (38, 0x2b),
+        (39, 0x34),
+        (40, 0x2b),
+        (117, 0xec),
+        (118, 0x9c),
+        (119, 0xb5),
+        (121, 0xc1),
+        (122, 0xc1),
+        (123, 0xc1),
+        (124, 0xe7),
+        (125, 0xc2),
+    ]);
+    set_crcs(&mut spd_data);
+    spd_data
+}
+
+fn ddr4_2133_sodimm() -> GeneralConfig {
+    GeneralConfig::from_spd_data(&ddr4_2133_sodimm_spd()).unwrap()
+}
+
+/// Same module run at DDR4-1600
+fn ddr4_1600() -> GeneralConfig {
+    let mut config = ddr4_2133_sodimm();
+    config.t_ck_ps = 1250;
+    config
+}
+
+#[test]
+fn decode() {
+    let config = ddr4_2133_sodimm();
+    assert_eq!(config.t_ckavg_min_ps, 938);
+    assert_eq!(config.t_ckavg_max_ps, 1600);
+    assert_eq!(config.t_aa_min_ps, 14_062);
+    assert_eq!(config.t_ccd_l_min_ps, 5_355);
+    assert_eq!(config.module_capacity_megabytes(), 4096);
+    assert_eq!(config.data_rate_mhz(), 2133);
+    assert_eq!(config.speed_grade(), 2133);
+}
+
+#[test]
+fn crc_mismatch() {
+    let mut spd_data = ddr4_2133_sodimm_spd();
+    spd_data[18] = 0x09;
+    assert!(matches!(
+        GeneralConfig::from_spd_data(&spd_data),
+        Err(SpdError::Crc { start: 0, .. })
+    ));
+
+    let mut spd_data = ddr4_2133_sodimm_spd();
+    spd_data[131] = 0x01;
+    assert!(matches!(
+        GeneralConfig::from_spd_data(&spd_data),
+        Err(SpdError::Crc { start: 128, .. })
+    ));
+}
+
+#[test]
+fn invalid_data() {
+    let cases = [
+        (2, 0x0d),
+        (3, 0x07),
+        (4, 0xc5),
+        (5, 0x39),
+        (12, 0x22),
+        (17, 0x01),
+    ];
+    for &(byte, value) in cases.iter() {
+        let mut spd_data = ddr4_2133_sodimm_spd();
+        spd_data[byte] = value;
+        set_crcs(&mut spd_data);
+        match GeneralConfig::from_spd_data(&spd_data) {
+            Err(SpdError::InvalidData {
+                byte: b, value: v, ..
+            }) => {
+                assert_eq!((b, v), (byte, value))
+            }
+            other => panic!("byte {}: {:?}", byte, other.map(|_| ())),
+        }
+    }
+}
+
+#[test]
+fn mr0_cas_latency_table() {
+    let table = [
+        (9, 0x0000),
+        (10, 0x0004),
+        (11, 0x0010),
+        (12, 0x0014),
+        (13, 0x0020),
+        (14, 0x0024),
+        (15, 0x0030),
+        (16, 0x0034),
+        (18, 0x0040),
+        (20, 0x0044),
+        (22, 0x0050),
+        (24, 0x0054),
+        (23, 0x0060),
+        (17, 0x0064),
+        (19, 0x0070),
+        (21, 0x0074),
+        (25, 0x1000),
+        (26, 0x1004),
+        (27, 0x1010),
+        (28, 0x1014),
+        (30, 0x1024),
+        (32, 0x1034),
+    ];
+    for &(cl, bits) in table.iter() {
+        assert_eq!(mr0_cas_latency(cl), Some(bits), "CL {}", cl);
+    }
+    for &cl in [8, 29, 31, 33].iter() {
+        assert_eq!(mr0_cas_latency(cl), None, "CL {}", cl);
+    }
+}
+
+#[test]
+fn mr0_write_recovery_table() {
+    let table = [
+        (10, 0x0000),
+        (12, 0x0200),
+        (14, 0x0400),
+        (16, 0x0600),
+        (18, 0x0800),
+        (20, 0x0a00),
+        (22, 0x0e00),
+        (24, 0x0c00),
+        (26, 0x2000),
+        (28, 0x2200),
+    ];
+    for &(wr, bits) in table.iter() {
+        assert_eq!(mr0_write_recovery(wr), bits, "WR {}", wr);
+    }
+    // odd values round up
+    assert_eq!(mr0_write_recovery(15), 0x0600);
+    assert_eq!(mr0_write_recovery(21), 0x0e00);
+}
+
+#[test]
+fn cas_latencies() {
+    let mut config = ddr4_2133_sodimm();
+    assert_eq!(config.cas_latency_nck(), 15);
+    assert_eq!(config.cas_write_latency_nck(), 11);
+    // DBI adds 2 tCK up to DDR4-2400
+    config.rd_dbi_en = true;
+    assert_eq!(config.cas_latency_nck(), 17);
+    // 2 tCK write preamble isn't defined below DDR4-2400
+    config.wr_preamble_2nck = true;
+    assert_eq!(config.cas_write_latency_nck(), 11);
+
+    let config = ddr4_1600();
+    assert_eq!(config.cas_latency_nck(), 12);
+    assert_eq!(config.cas_write_latency_nck(), 9);
+}
+
+#[test]
+fn cas_write_latency_2nck_preamble() {
+    let mut config = ddr4_2133_sodimm();
+    config.wr_preamble_2nck = true;
+    for &(t_ck_ps, cwl) in [(833, 14), (750, 16), (682, 18), (625, 18)].iter() {
+        config.t_ck_ps = t_ck_ps;
+        assert_eq!(config.cas_write_latency_nck(), cwl, "tCK {} ps", t_ck_ps);
+    }
+}
+
+#[test]
+fn mode_registers_ddr4_2133() {
+    // MR0, MR1, MR3, MR4 and MR6 match the ZCU102/ZCU111 FSBL values
+    let config = ddr4_2133_sodimm();
+    assert_eq!(config.mr0(), 0x0630);
+    assert_eq!(config.mr1(), 0x0301);
+    assert_eq!(config.mr2(), 0x0010);
+    assert_eq!(config.mr3(), 0x0200);
+    assert_eq!(config.mr4(), 0x0000);
+    assert_eq!(config.mr5(), 0x04c0);
+    assert_eq!(config.mr6(), 0x0819);
+}
+
+#[test]
+fn mode_registers_ddr4_1600() {
+    let mut config = ddr4_1600();
+    config.parity_en = true;
+    // CL 12, WR 12
+    assert_eq!(config.mr0(), 0x0214);
+    // CWL 9
+    assert_eq!(config.mr2(), 0x0000);
+    assert_eq!(config.mr3(), 0x0000);
+    // parity latency 4
+    assert_eq!(config.mr5(), 0x04c1);
+    // tCCD_L 5
+    assert_eq!(config.mr6(), 0x0419);
+}
+
+#[test]
+fn mode_registers_options() {
+    let mut config = ddr4_2133_sodimm();
+    config.drive_strength = DriveStrength::Rzq5;
+    config.rtt_nom = Rtt::Disabled;
+    config.rtt_wr = RttWr::Rzq3;
+    config.rtt_park = Rtt::Rzq4;
+    config.crc_en = true;
+    config.geardown = true;
+    config.rd_dbi_en = true;
+    config.dm_en = false;
+    config.rd_preamble_2nck = true;
+    config.vref_dq_range_2 = true;
+    config.vref_dq = 0x10;
+    // CL 17
+    assert_eq!(config.mr0(), 0x0664);
+    assert_eq!(config.mr1(), 0x0003);
+    assert_eq!(config.mr2(), 0x1810);
+    assert_eq!(config.mr3(), 0x0208);
+    assert_eq!(config.mr4(), 0x0800);
+    assert_eq!(config.mr5(), 0x1040);
+    assert_eq!(config.mr6(), 0x0850);
+
+    // DDR4-2400 with 2 tCK write preamble: CWL 14
+    config.t_ck_ps = 833;
+    config.wr_preamble_2nck = true;
+    assert_eq!(config.mr2(), 0x1820);
+    assert_eq!(config.mr4(), 0x1800);
+}
+
+fn rcd_config() -> RcdConfig {
+    RcdConfig {
+        register_type: 0,
+        dram_rows: 1,
+        registers: 1,
+        heat_spreader: false,
+        // IDT
+        manufacturer_id: 0xb380,
+        revision: 0x31,
+        rank_1_mirrored: true,
+        cke_drive: RcdDriveStrength::Light,
+        odt_drive: RcdDriveStrength::Moderate,
+        ca_drive: RcdDriveStrength::Strong,
+        cs_drive: RcdDriveStrength::VeryStrong,
+        y0_y2_drive: RcdDriveStrength::Moderate,
+        y1_y3_drive: RcdDriveStrength::Strong,
+    }
+}
+
+fn registered_config() -> RegisteredConfig {
+    RegisteredConfig {
+        raw_card_extension: 0,
+        module_nominal_height: 0x11,
+        module_max_thickness_back: 1,
+        module_max_thickness_front: 1,
+        ref_raw_card_rev: 1,
+        ref_raw_card: 0x01,
+        rcd: rcd_config(),
+    }
+}
+
+/// The RDIMM of [registered_config] with data buffers
+fn load_reduced_config() -> LoadReducedConfig {
+    let registered = registered_config();
+    LoadReducedConfig {
+        raw_card_extension: registered.raw_card_extension,
+        module_nominal_height: registered.module_nominal_height,
+        module_max_thickness_back: registered.module_max_thickness_back,
+        module_max_thickness_front: registered.module_max_thickness_front,
+        ref_raw_card_rev: registered.ref_raw_card_rev,
+        ref_raw_card: registered.ref_raw_card,
+        rcd: registered.rcd,
+        bck_drive_strong: true,
+        bcom_drive: RcdDriveStrength::Moderate,
+        db_revision: 0x21,
+        dram_vref_dq: [0x1c, 0x1d, 0x1e, 0x1f],
+        dram_vref_dq_range_2: [true, false, true, false],
+        db_vref_dq: 0x22,
+        db_vref_dq_range_2: true,
+        db_mdq_rtt: [0b001, 0b010, 0b011],
+        db_mdq_drive: [0b101, 0b001, 0b010],
+        dram_drive_strength: [
+            DriveStrength::Rzq7,
+            DriveStrength::Rzq7,
+            DriveStrength::Rzq5,
+        ],
+        dram_rtt_wr: [RttWr::Disabled, RttWr::Rzq2, RttWr::Rzq3],
+        dram_rtt_nom: [Rtt::Rzq6, Rtt::Rzq4, Rtt::Rzq7],
+        dram_rtt_park: [
+            [Rtt::Disabled, Rtt::Rzq1],
+            [Rtt::Rzq5, Rtt::Rzq2],
+            [Rtt::Rzq3, Rtt::Rzq4],
+        ],
+        db_dfe: true,
+        db_gain_adjust: false,
+    }
+}
+
+#[test]
+fn corpus_crcs() {
+    for spd_data in CORPUS.iter() {
+        assert!(check_crc(&spd_data[..128], 0).is_ok());
+        assert!(check_crc(&spd_data[128..256], 128).is_ok());
+    }
+}
+
+#[test]
+fn mta4atf51264hz_2g6() {
+    // ZCU111: 4 GiB single rank SO-DIMM, 8 Gb x16, DDR4-2666 CL19
+    let config = GeneralConfig::from_spd_data(SYNTHETIC_MTA4ATF51264HZ_2G6).unwrap();
+    assert_eq!(config.spd_bytes_total, 512);
+    assert_eq!(config.spd_bytes_used, 384);
+    assert_eq!(config.device_type, DeviceType::Ddr4);
+    assert_eq!(config.module_type, 0x3);
+    assert_eq!(
+        config.module_config,
+        ModuleConfig::Unbuffered(UnbufferedConfig {
+            raw_card_extension: 0,
+            module_nominal_height: 0x0f,
+            module_max_thickness_back: 1,
+            module_max_thickness_front: 1,
+            ref_raw_card_rev: 0,
+            ref_raw_card: 2,
+            rank_1_mirrored: false,
+        })
+    );
+    assert_eq!(config.bg_addr_bits, 1);
+    assert_eq!(config.bank_addr_bits, 2);
+    assert_eq!(config.capacity_megabits, 8192);
+    assert_eq!(config.row_addr_bits, 16);
+    assert_eq!(config.col_addr_bits, 10);
+    assert_eq!(config.package_ranks, 1);
+    assert_eq!(config.device_width, 16);
+    assert_eq!(config.bus_width, 64);
+    assert_eq!(config.bus_width_extension, 0);
+    assert_eq!(config.t_ckavg_min_ps, 750);
+    assert_eq!(config.t_ckavg_max_ps, 1600);
+    // CL 10-20
+    assert_eq!(config.supported_cas_latencies, 0x3ff8);
+    assert_eq!(config.t_aa_min_ps, 13_750);
+    assert_eq!(config.t_rcd_min_ps, 13_750);
+    assert_eq!(config.t_rp_min_ps, 13_750);
+    assert_eq!(config.t_ras_min_ps, 32_000);
+    assert_eq!(config.t_rc_min_ps, 45_750);
+    assert_eq!(config.t_rfc1_min_ps, 350_000);
+    assert_eq!(config.t_rfc2_min_ps, 260_000);
+    assert_eq!(config.t_rfc4_min_ps, 160_000);
+    assert_eq!(config.t_faw_min_ps, 30_000);
+    assert_eq!(config.t_rrd_s_min_ps, 5_300);
+    assert_eq!(config.t_rrd_l_min_ps, 6_400);
+    assert_eq!(config.t_ccd_l_min_ps, 5_000);
+
+    assert_eq!(config.module_capacity_megabytes(), 4096);
+    assert_eq!(config.speed_grade(), 2666);
+    assert_eq!(config.cas_latency_nck(), 19);
+    assert_eq!(config.cas_write_latency_nck(), 14);
+    assert_eq!(config.ps_to_nck(config.t_rfc_min_ps()), 467);
+    assert_eq!(config.ps_to_nck(config.t_ccd_l_min_ps), 7);
+    // CL 19, WR 20
+    assert_eq!(config.mr0(), 0x0a70);
+    // CWL 14
+    assert_eq!(config.mr2(), 0x0020);
+    assert_eq!(config.mr3(), 0x0400);
+    assert_eq!(config.mr6(), 0x0c19);
+
+    let info = config.module_info;
+    assert_eq!(info.module_manufacturer(), Some("Micron"));
+    assert_eq!(info.dram_manufacturer(), Some("Micron"));
+    assert_eq!(info.part_number(), "4ATF51264HZ-2G6E1");
+    assert_eq!((info.year, info.week), (2019, 21));
+    assert_eq!(info.location, 0x0f);
+    assert_eq!(info.serial_number, 0x217a_3c05);
+    assert_eq!(info.revision, 0x31);
+    assert_eq!(info.dram_stepping, 0x42);
+}
+
+#[test]
+fn mta8atf1g64hz_2g3() {
+    // 8 GiB single rank SO-DIMM, 8 Gb x8, DDR4-2400 CL17
+    let config = GeneralConfig::from_spd_data(SYNTHETIC_MTA8ATF1G64HZ_2G3).unwrap();
+    assert_eq!(config.bg_addr_bits, 2);
+    assert_eq!(config.bank_groups(), 4);
+    assert_eq!(config.banks_per_group(), 4);
+    assert_eq!(config.device_width, 8);
+    assert_eq!(config.t_ckavg_min_ps, 833);
+    // CL 10-18
+    assert_eq!(config.supported_cas_latencies, 0x0ff8);
+    assert_eq!(config.t_aa_min_ps, 14_160);
+    assert_eq!(config.t_rc_min_ps, 46_160);
+    assert_eq!(config.t_faw_min_ps, 21_000);
+    assert_eq!(config.t_rrd_s_min_ps, 3_300);
+    assert_eq!(config.t_rrd_l_min_ps, 4_900);
+
+    assert_eq!(config.module_capacity_megabytes(), 8192);
+    assert_eq!(config.speed_grade(), 2400);
+    assert_eq!(config.cas_latency_nck(), 17);
+    assert_eq!(config.cas_write_latency_nck(), 12);
+    assert_eq!(config.ps_to_nck(config.t_rfc_min_ps()), 421);
+    // CL 17, WR 18
+    assert_eq!(config.mr0(), 0x0864);
+    assert_eq!(config.mr6(), 0x0819);
+}
+
+#[test]
+fn mta16atf2g64hz_2g6() {
+    // 16 GiB dual rank SO-DIMM with mirrored rank 1, 8 Gb x8, DDR4-2666
+    let config = GeneralConfig::from_spd_data(SYNTHETIC_MTA16ATF2G64HZ_2G6).unwrap();
+    assert_eq!(config.package_ranks, 2);
+    assert_eq!(config.logical_ranks(), 2);
+    assert_eq!(config.rank_addr_bits(), 1);
+    assert!(matches!(
+        config.module_config,
+        ModuleConfig::Unbuffered(UnbufferedConfig {
+            rank_1_mirrored: true,
+            ..
+        })
+    ));
+    assert_eq!(config.rank_capacity_megabytes(), 8192);
+    assert_eq!(config.module_capacity_megabytes(), 16384);
+    assert_eq!(config.cas_latency_nck(), 19);
+    assert_eq!(config.mr0(), 0x0a70);
+}
+
+#[test]
+fn mta9asf1g72az_2g6() {
+    // 8 GiB single rank ECC UDIMM, 8 Gb x8, DDR4-2666
+    let config = GeneralConfig::from_spd_data(SYNTHETIC_MTA9ASF1G72AZ_2G6).unwrap();
+    assert_eq!(config.module_type, 0x2);
+    assert_eq!(config.bus_width, 64);
+    assert_eq!(config.bus_width_extension, 8);
+    assert_eq!(config.module_capacity_megabytes(), 8192);
+    assert_eq!(config.speed_grade(), 2666);
+}
+
+#[test]
+fn corpus_round_trip() {
+    for spd_data in CORPUS.iter() {
+        let config = GeneralConfig::from_spd_data(spd_data).unwrap();
+        let mut encoded = config.to_spd_data();
+        assert_eq!(GeneralConfig::from_spd_data(&encoded).unwrap(), config);
+        // other than PPR (byte 9, not decoded), the base section is encoded back unchanged
+        encoded[9] = spd_data[9];
+        set_crc(&mut encoded[..128]);
+        for byte in 0..128 {
+            assert_eq!(encoded[byte], spd_data[byte], "byte {}", byte);
+        }
+        assert_eq!(encoded[128..256], spd_data[128..256]);
+        // manufacturing data, up to the manufacturer specific bytes
+        assert_eq!(encoded[320..353], spd_data[320..353]);
+    }
+}
+
+fn random_config(rng: &mut Rng) -> GeneralConfig {
+    let mut config = GeneralConfig::from_spd_data(SYNTHETIC_MTA4ATF51264HZ_2G6).unwrap();
+    config.spd_bytes_total = [256, 512][rng.below(2) as usize];
+    config.spd_bytes_used = 128 * rng.range(1, 4) as u16;
+    config.spd_encoding = rng.below(16) as u8;
+    config.spd_additions = rng.below(16) as u8;
+    // DDR3 SPDs have their own layout, which to_spd_data doesn't encode
+    config.device_type =
+        [DeviceType::Ddr4, DeviceType::LpDdr3, DeviceType::LpDdr4][rng.below(3) as usize];
+    config.module_type = [0x2, 0x3, 0x6, 0x9, 0xc, 0xd][rng.below(6) as usize];
+    config.module_config = ModuleConfig::Unbuffered(UnbufferedConfig {
+        raw_card_extension: rng.below(8) as u8,
+        module_nominal_height: rng.below(32) as u8,
+        module_max_thickness_back: rng.below(16) as u8,
+        module_max_thickness_front: rng.below(16) as u8,
+        ref_raw_card_rev: rng.below(4) as u8,
+        ref_raw_card: rng.below(32) as u8,
+        rank_1_mirrored: rng.bool(),
+    });
+    config.bg_addr_bits = rng.below(3) as u8;
+    config.bank_addr_bits = rng.range(2, 3) as u8;
+    config.capacity_megabits = 256 << rng.below(8);
+    config.row_addr_bits = rng.range(12, 18) as u8;
+    config.col_addr_bits = rng.range(9, 12) as u8;
+    config.package_type = if rng.bool() {
+        PackageType::Monolithic
+    } else {
+        PackageType::NonMonolithic
+    };
+    config.die_count = rng.range(1, 8) as u8;
+    config.signal_loading = [
+        SignalLoading::Unspecified,
+        SignalLoading::MultiLoadStack,
+        SignalLoading::SingleLoadStack,
+    ][rng.below(3) as usize];
+    config.t_maw = 8192 >> rng.below(3);
+    config.mac = [0, 1, 2, 3, 4, 5, 6, 8][rng.below(8) as usize];
+    config.vdd_12_endurant = rng.bool();
+    config.vdd_12_operable = rng.bool();
+    config.package_ranks = rng.range(1, 4) as u8;
+    config.device_width = 4 << rng.below(4);
+    config.bus_width_extension = 8 * rng.below(2) as u8;
+    config.bus_width = 8 << rng.below(4);
+    config.has_thermal_sensor = rng.bool();
+    // one byte of MTB plus a negative offset
+    config.t_ckavg_min_ps = rng.range(1, 255 * 125);
+    config.t_ckavg_max_ps = rng.range(1, 255 * 125);
+    config.supported_cas_latencies = rng.next() as u32;
+    config.t_aa_min_ps = rng.range(1, 255 * 125);
+    config.t_rcd_min_ps = rng.range(1, 255 * 125);
+    config.t_rp_min_ps = rng.range(1, 255 * 125);
+    config.t_rrd_s_min_ps = rng.range(1, 255 * 125);
+    config.t_rrd_l_min_ps = rng.range(1, 255 * 125);
+    config.t_ccd_l_min_ps = rng.range(1, 255 * 125);
+    // 12 bits of MTB, with a negative offset for t_rc only
+    config.t_ras_min_ps = 125 * rng.below(0x1000);
+    config.t_rc_min_ps = rng.range(1, 0xfff * 125);
+    config.t_faw_min_ps = 125 * rng.below(0x1000);
+    // 16 bits of MTB
+    config.t_rfc1_min_ps = 125 * rng.below(0x10000);
+    config.t_rfc2_min_ps = 125 * rng.below(0x10000);
+    config.t_rfc4_min_ps = 125 * rng.below(0x10000);
+    for dq in config.dq_map.iter_mut() {
+        *dq = rng.next() as u8;
+    }
+    config.t_ck_ps = config.t_ckavg_min_ps;
+    config
+}
+
+#[test]
+fn fuzz_round_trip() {
+    let mut rng = Rng(0x5eed_5eed);
+    for _ in 0..10_000 {
+        let config = random_config(&mut rng);
+        let decoded = GeneralConfig::from_spd_data(&config.to_spd_data());
+        assert_eq!(decoded.as_ref(), Ok(&config));
+    }
+}
+
+#[test]
+fn fuzz_decode() {
+    // mutated images decode or fail with an error, but never panic
+    let mut rng = Rng(0x0dd_ba11);
+    for i in 0..10_000 {
+        let mut spd_data = *CORPUS[i % CORPUS.len()];
+        for _ in 0..rng.range(1, 8) {
+            spd_data[rng.below(256) as usize] = rng.next() as u8;
+        }
+        if rng.below(4) != 0 {
+            set_crcs(&mut spd_data);
+        }
+        let _ = GeneralConfig::from_spd_data(&spd_data);
+    }
+}
+
+/// The ECC UDIMM of the corpus with its module specific bytes replaced by `module_config`
+fn buffered_module(module_config: ModuleConfig) -> GeneralConfig {
+    let mut config = GeneralConfig::from_spd_data(SYNTHETIC_MTA9ASF1G72AZ_2G6).unwrap();
+    config.module_type = match module_config {
+        ModuleConfig::Registered(_) => 0x1,
+        _ => 0x4,
+    };
+    config.module_config = module_config;
+    config
+}
+
+#[test]
+fn rdimm() {
+    let config = buffered_module(ModuleConfig::Registered(registered_config()));
+    let spd_data = config.to_spd_data();
+    assert_eq!(spd_data[133..139], [0x80, 0xb3, 0x31, 0x01, 0xe4, 0x09]);
+
+    let decoded = GeneralConfig::from_spd_data(&spd_data).unwrap();
+    assert_eq!(decoded, config);
+    assert_eq!(decoded.rcd(), Some(&rcd_config()));
+    assert!(decoded.rank_1_mirrored());
+    // one cycle through the register
+    assert_eq!(decoded.read_latency_nck(), decoded.cas_latency_nck() + 1);
+    assert_eq!(
+        decoded.write_latency_nck(),
+        decoded.cas_write_latency_nck() + 1
+    );
+}
+
+#[test]
+fn rcd_drive_strengths() {
+    // annex L.2 bytes 131-138 of a single register, single DRAM row RDIMM with an IDT RCD
+    let rcd_bytes = [0x05, 0x00, 0x80, 0xb3, 0x31, 0x01, 0xe4, 0x09];
+    let mut spd_data = [0u8; 128];
+    spd_data[131 - MODULE_CONFIG_START..139 - MODULE_CONFIG_START].copy_from_slice(&rcd_bytes);
+    let rcd = RcdConfig::from_spd_data(&spd_data).unwrap();
+    // byte 137 = 0b11_10_01_00: CS 7-6, CA 5-4, ODT 3-2, CKE 1-0
+    assert_eq!(rcd.cs_drive, RcdDriveStrength::VeryStrong);
+    assert_eq!(rcd.ca_drive, RcdDriveStrength::Strong);
+    assert_eq!(rcd.odt_drive, RcdDriveStrength::Moderate);
+    assert_eq!(rcd.cke_drive, RcdDriveStrength::Light);
+    // byte 138 = 0b10_01: Y1/Y3 3-2, Y0/Y2 1-0
+    assert_eq!(rcd.y1_y3_drive, RcdDriveStrength::Strong);
+    assert_eq!(rcd.y0_y2_drive, RcdDriveStrength::Moderate);
+    assert_eq!(rcd, rcd_config());
+
+    let mut encoded = [0u8; 128];
+    rcd.write_spd_data(&mut encoded);
+    assert_eq!(encoded, spd_data);
+}
+
+#[test]
+fn lrdimm() {
+    let config = buffered_module(ModuleConfig::LoadReduced(load_reduced_config()));
+    let decoded = GeneralConfig::from_spd_data(&config.to_spd_data()).unwrap();
+    assert_eq!(decoded.module_config, config.module_config);
+    assert_eq!(decoded.rcd(), Some(&rcd_config()));
+
+    // DRAM settings for DDR4-2666 come from the SPD instead of the board defaults
+    assert_eq!(speed_range(decoded.speed_grade()), 2);
+    assert_eq!(decoded.drive_strength, DriveStrength::Rzq5);
+    assert_eq!(decoded.rtt_nom, Rtt::Rzq7);
+    assert_eq!(decoded.rtt_wr, RttWr::Rzq3);
+    assert_eq!(decoded.rtt_park, Rtt::Rzq3);
+    assert_eq!(decoded.vref_dq, 0x1c);
+    assert!(decoded.vref_dq_range_2);
+}
+
+#[test]
+fn lrdimm_invalid_data() {
+    let config = buffered_module(ModuleConfig::LoadReduced(load_reduced_config()));
+    let cases = [(131, 0x03), (148, 0x20), (150, 0x28)];
+    for &(byte, value) in cases.iter() {
+        let mut spd_data = config.to_spd_data();
+        spd_data[byte] = value;
+        set_crcs(&mut spd_data);
+        match GeneralConfig::from_spd_data(&spd_data) {
+            Err(SpdError::InvalidData {
+                byte: b, value: v, ..
+            }) => {
+                assert_eq!((b, v), (byte, value))
+            }
+            other => panic!("byte {}: {:?}", byte, other.map(|_| ())),
+        }
+    }
+}
+
+/// Single rank DDR3-1600 SO-DIMM with 4 Gb x16 devices, CL 5-11, tAA = 13.75 ns
+fn ddr3_1600_sodimm_spd() -> [u8; 512] {
+    let mut spd_data = spd_image(&[
+        (0, 0x92),
+        (1, 0x13),
+        (2, 0x0b),
+        (3, 0x03),
+        (4, 0x04),
+        (5, 0x19),
+        (7, 0x02),
+        (8, 0x03),
+        (9, 0x11),
+        (10, 0x01),
+        (11, 0x08),
+        (12, 0x0a),
+        (14, 0xfe),
+        (16, 0x6e),
+        (17, 0x78),
+        (18, 0x6e),
+        (19, 0x3c),
+        (20, 0x6e),
+        (21, 0x11),
+        (22, 0x18),
+        (23, 0x86),
+        (24, 0x20),
+        (25, 0x08),
+        (28, 0x01),
+        (29, 0x40),
+        (60, 0x0f),
+        (62, 0x05),
+    ]);
+    // bit 7 of byte 0: CRC covers bytes 0-116
+    let crc = crc(&spd_data[..117]);
+    spd_data[126] = crc as u8;
+    spd_data[127] = (crc >> 8) as u8;
+    spd_data
+}
+
+#[test]
+fn ddr3_decode() {
+    let config = GeneralConfig::from_spd_data(&ddr3_1600_sodimm_spd()).unwrap();
+    assert_eq!(config.device_type, DeviceType::Ddr3);
+    assert_eq!(config.bg_addr_bits, 0);
+    assert_eq!(config.bank_addr_bits, 3);
+    assert_eq!(config.row_addr_bits, 15);
+    assert_eq!(config.col_addr_bits, 10);
+    assert_eq!(config.t_ckavg_min_ps, 1250);
+    assert_eq!(config.t_aa_min_ps, 13_750);
+    assert_eq!(config.t_ras_min_ps, 35_000);
+    assert_eq!(config.t_rc_min_ps, 48_750);
+    assert_eq!(config.t_rfc4_min_ps, 260_000);
+    assert_eq!(config.t_rrd_l_min_ps, 7_500);
+    assert_eq!(config.module_capacity_megabytes(), 2048);
+    assert_eq!(config.speed_grade(), 1600);
+
+    assert_eq!(config.cas_latency_nck(), 11);
+    assert_eq!(config.cas_write_latency_nck(), 8);
+    assert_eq!(config.t_ccd_l_nck(), 4);
+    assert_eq!(config.t_mod_nck(), 12);
+    assert_eq!(config.mr0(), 0x1c70);
+    // RTT_NOM RZQ/6, output driver RZQ/7
+    assert_eq!(config.mr1(), 0x46);
+    assert_eq!(config.mr2(), 0x18);
+    assert_eq!(
+        [config.mr3(), config.mr4(), config.mr5(), config.mr6()],
+        [0; 4]
+    );
+}
+
+#[test]
+fn ddr3_invalid_data() {
+    let mut spd_data = ddr3_1600_sodimm_spd();
+    spd_data[12] = 0x0f;
+    assert!(matches!(
+        GeneralConfig::from_spd_data(&spd_data),
+        Err(SpdError::Crc { start: 0, .. })
+    ));
+
+    // RDIMM
+    let mut spd_data = ddr3_1600_sodimm_spd();
+    spd_data[0] = 0x12;
+    spd_data[3] = 0x01;
+    let crc = crc(&spd_data[..126]);
+    spd_data[126] = crc as u8;
+    spd_data[127] = (crc >> 8) as u8;
+    match GeneralConfig::from_spd_data(&spd_data) {
+        Err(SpdError::InvalidData { byte: 3, .. }) => {}
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn module_info() {
+    let mut spd_data = *SYNTHETIC_MTA8ATF1G64HZ_2G3;
+    // not covered by a CRC
+    spd_data[320] = 0x01;
+    spd_data[321] = 0x98;
+    spd_data[323] = 0x1a;
+    spd_data[350] = 0x7f;
+    let info = GeneralConfig::from_spd_data(&spd_data).unwrap().module_info;
+    assert_eq!(info.module_manufacturer(), Some("Kingston"));
+    assert_eq!(info.dram_manufacturer(), None);
+    assert_eq!(info.part_number(), "8ATF1G64HZ-2G3B1");
+    // bad BCD
+    assert_eq!((info.year, info.week), (0, 7));
+
+    // out of range dates saturate instead of wrapping
+    let mut info = info;
+    info.year = 2300;
+    info.week = 120;
+    let info = ModuleInfo::from_spd_data(&info.to_spd_data());
+    assert_eq!((info.year, info.week), (2099, 99));
+}
+
+#[test]
+fn ddr3_module_info() {
+    let mut spd_data = ddr3_1600_sodimm_spd();
+    let supplier_data: [(usize, u8); 10] = [
+        (117, 0x80),
+        (118, 0xce),
+        (119, 0x02),
+        (120, 0x15),
+        (121, 0x32),
+        (122, 0x12),
+        (125, 0x34),
+        (147, 0x41),
+        (148, 0x80),
+        (149, 0xce),
+    ];
+    for &(byte, value) in supplier_data.iter() {
+        spd_data[byte] = value;
+    }
+    spd_data[128..146].copy_from_slice(b"M471B5273DH0-CK0  ");
+    let info = GeneralConfig::from_spd_data(&spd_data).unwrap().module_info;
+    assert_eq!(info.module_manufacturer(), Some("Samsung"));
+    assert_eq!(info.dram_manufacturer(), Some("Samsung"));
+    assert_eq!(info.part_number(), "M471B5273DH0-CK0");
+    assert_eq!((info.year, info.week), (2015, 32));
+    assert_eq!(info.location, 0x02);
+    assert_eq!(info.serial_number, 0x1200_0034);
+    assert_eq!(info.revision, 0x41);
+}
@@ -0,0 +1,294 @@
+//! PS GPIO controller
+//!
+//! Banks 0-2 drive MIO pins 0-77, which must be muxed as [Mio::Gpio](crate::mio::Mio::Gpio);
+//! banks 3-5 drive the 96 EMIO pins to the PL. See UG1085 Ch. 27.
+use core::convert::Infallible;
+
+use embedded_hal::digital::v2 as hal;
+
+use crate::interrupts::{self, irq, Trigger};
+use crate::reset::Reset;
+use crate::slcr::iou_slcr::NUM_MIO_PINS;
+
+mod regs;
+
+pub const NUM_BANKS: usize = 6;
+pub const NUM_EMIO_PINS: usize = 96;
+const NUM_MIO_BANKS: usize = 3;
+/// MIO banks have 26 pins, EMIO banks 32
+const MIO_BANK_WIDTH: usize = 26;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterruptTrigger {
+    RisingEdge,
+    FallingEdge,
+    BothEdges,
+    HighLevel,
+    LowLevel,
+}
+
+pub struct Gpio {
+    regs: &'static mut regs::RegisterBlock,
+}
+
+impl Default for Gpio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gpio {
+    /// Reset the controller; all pins become inputs with interrupts disabled
+    pub fn new() -> Self {
+        Reset::Gpio.pulse();
+        Gpio {
+            regs: regs::RegisterBlock::gpio(),
+        }
+    }
+
+    pub fn mio_pin(&mut self, index: usize) -> Pin {
+        assert!(index < NUM_MIO_PINS);
+        Pin::new(index / MIO_BANK_WIDTH, (index % MIO_BANK_WIDTH) as u8)
+    }
+
+    pub fn emio_pin(&mut self, index: usize) -> Pin {
+        assert!(index < NUM_EMIO_PINS);
+        Pin::new(NUM_MIO_BANKS + index / 32, (index % 32) as u8)
+    }
+
+    /// Set the outputs selected by `mask` in `bank` to `value` without affecting other pins
+    pub fn write_masked(&mut self, bank: usize, mask: u32, value: u32) {
+        write_masked(self.regs, bank, mask, value);
+    }
+
+    /// Input levels of all pins in `bank`
+    pub fn read(&self, bank: usize) -> u32 {
+        self.regs.data_ro[bank].read()
+    }
+
+    /// Route the GPIO interrupt to the CPUs in `cpu_targets` at `priority`
+    pub fn enable_interrupt(&mut self, priority: u8, cpu_targets: u8) {
+        interrupts::enable_spi(irq::GPIO, Trigger::Level, priority, cpu_targets);
+    }
+
+    pub fn disable_interrupt(&mut self) {
+        interrupts::disable_spi(irq::GPIO);
+    }
+
+    /// Pins in `bank` with an unmasked interrupt pending
+    pub fn pending_interrupts(&self, bank: usize) -> u32 {
+        let bank = &self.regs.bank[bank];
+        bank.int_stat.read() & !bank.int_mask.read()
+    }
+
+    /// Acknowledge the interrupts of the pins in `mask`. Level interrupts stay pending
+    /// while the level persists.
+    pub fn clear_interrupts(&mut self, bank: usize, mask: u32) {
+        unsafe { self.regs.bank[bank].int_stat.write(mask) }
+    }
+}
+
+fn write_masked(regs: &mut regs::RegisterBlock, bank: usize, mask: u32, value: u32) {
+    let mask_data = &mut regs.mask_data[bank];
+    let (lower, upper) = (mask & 0xFFFF, mask >> 16);
+    unsafe {
+        if lower != 0 {
+            mask_data
+                .lsw
+                .write((!lower & 0xFFFF) << 16 | value & 0xFFFF);
+        }
+        if upper != 0 {
+            mask_data.msw.write((!upper & 0xFFFF) << 16 | value >> 16);
+        }
+    }
+}
+
+/// An unconfigured GPIO pin
+pub struct Pin {
+    bank: usize,
+    bit: u8,
+}
+
+impl Pin {
+    fn new(bank: usize, bit: u8) -> Self {
+        Pin { bank, bit }
+    }
+
+    fn mask(&self) -> u32 {
+        1 << self.bit
+    }
+
+    fn regs(&self) -> &'static mut regs::RegisterBlock {
+        regs::RegisterBlock::gpio()
+    }
+
+    fn set_bit(reg: &mut volatile_register::RW<u32>, mask: u32, value: bool) {
+        unsafe {
+            reg.modify(|r| if value { r | mask } else { r & !mask });
+        }
+    }
+
+    pub fn into_input(self) -> InputPin {
+        let mask = self.mask();
+        let bank = &mut self.regs().bank[self.bank];
+        Self::set_bit(&mut bank.oen, mask, false);
+        Self::set_bit(&mut bank.dirm, mask, false);
+        InputPin { pin: self }
+    }
+
+    /// Drive the pin, starting at `high`
+    pub fn into_output(self, high: bool) -> OutputPin {
+        let mask = self.mask();
+        write_masked(self.regs(), self.bank, mask, if high { mask } else { 0 });
+        let bank = &mut self.regs().bank[self.bank];
+        Self::set_bit(&mut bank.dirm, mask, true);
+        Self::set_bit(&mut bank.oen, mask, true);
+        OutputPin { pin: self }
+    }
+
+    fn is_high(&self) -> bool {
+        self.regs().data_ro[self.bank].read() & self.mask() != 0
+    }
+}
+
+pub struct InputPin {
+    pin: Pin,
+}
+
+impl InputPin {
+    pub fn into_output(self, high: bool) -> OutputPin {
+        self.set_interrupt(None);
+        self.pin.into_output(high)
+    }
+
+    pub fn is_high(&self) -> bool {
+        self.pin.is_high()
+    }
+
+    pub fn is_low(&self) -> bool {
+        !self.pin.is_high()
+    }
+
+    /// Raise the GPIO interrupt on `trigger`, or disable it with `None`
+    pub fn set_interrupt(&self, trigger: Option<InterruptTrigger>) {
+        let mask = self.pin.mask();
+        let bank = &mut self.pin.regs().bank[self.pin.bank];
+        unsafe { bank.int_dis.write(mask) };
+        let trigger = match trigger {
+            Some(trigger) => trigger,
+            None => return,
+        };
+        let (edge, polarity, any) = match trigger {
+            InterruptTrigger::RisingEdge => (true, true, false),
+            InterruptTrigger::FallingEdge => (true, false, false),
+            InterruptTrigger::BothEdges => (true, false, true),
+            InterruptTrigger::HighLevel => (false, true, false),
+            InterruptTrigger::LowLevel => (false, false, false),
+        };
+        Pin::set_bit(&mut bank.int_type, mask, edge);
+        Pin::set_bit(&mut bank.int_polarity, mask, polarity);
+        Pin::set_bit(&mut bank.int_any, mask, any);
+        unsafe {
+            bank.int_stat.write(mask);
+            bank.int_en.write(mask);
+        }
+    }
+
+    pub fn interrupt_pending(&self) -> bool {
+        self.pin.regs().bank[self.pin.bank].int_stat.read() & self.pin.mask() != 0
+    }
+
+    pub fn clear_interrupt(&self) {
+        unsafe {
+            self.pin.regs().bank[self.pin.bank]
+                .int_stat
+                .write(self.pin.mask())
+        }
+    }
+}
+
+pub struct OutputPin {
+    pin: Pin,
+}
+
+impl OutputPin {
+    pub fn into_input(self) -> InputPin {
+        self.pin.into_input()
+    }
+
+    pub fn set(&mut self, high: bool) {
+        let mask = self.pin.mask();
+        write_masked(
+            self.pin.regs(),
+            self.pin.bank,
+            mask,
+            if high { mask } else { 0 },
+        );
+    }
+
+    /// Level being driven (as opposed to the level on the pin)
+    pub fn is_set_high(&self) -> bool {
+        self.pin.regs().data[self.pin.bank].read() & self.pin.mask() != 0
+    }
+
+    pub fn toggle(&mut self) {
+        let high = self.is_set_high();
+        self.set(!high);
+    }
+
+    /// Tri-state the output driver without changing the pin direction
+    pub fn set_output_enable(&mut self, enable: bool) {
+        let mask = self.pin.mask();
+        Pin::set_bit(&mut self.pin.regs().bank[self.pin.bank].oen, mask, enable);
+    }
+
+    /// Level on the pin
+    pub fn is_high(&self) -> bool {
+        self.pin.is_high()
+    }
+}
+
+impl hal::InputPin for InputPin {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        Ok(InputPin::is_high(self))
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(InputPin::is_low(self))
+    }
+}
+
+impl hal::OutputPin for OutputPin {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set(true);
+        Ok(())
+    }
+}
+
+impl hal::StatefulOutputPin for OutputPin {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        Ok(OutputPin::is_set_high(self))
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(!OutputPin::is_set_high(self))
+    }
+}
+
+impl hal::ToggleableOutputPin for OutputPin {
+    type Error = Infallible;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        OutputPin::toggle(self);
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! GPIO registers, see UG1087 GPIO
+use libregister::register_at;
+use volatile_register::{RO, RW, WO};
+
+use super::NUM_BANKS;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub mask_data: [MaskData; NUM_BANKS],
+    unused0: [u32; 4],
+    pub data: [RW<u32>; NUM_BANKS],
+    unused1: [u32; 2],
+    pub data_ro: [RO<u32>; NUM_BANKS],
+    unused2: [u32; 99],
+    pub bank: [BankCtrl; NUM_BANKS],
+}
+register_at!(RegisterBlock, 0xFF0A_0000, gpio);
+assert_layout!(RegisterBlock, 0xFF0A_0000, {
+    mask_data => 0xFF0A_0000,
+    data => 0xFF0A_0040,
+    data_ro => 0xFF0A_0060,
+    bank => 0xFF0A_0204,
+});
+
+/// Masked writes to the lower and upper 16 pins of a bank
+///
+/// Bits 31:16 are a mask (1 = leave pin unchanged), bits 15:0 the output values.
+#[repr(C)]
+pub struct MaskData {
+    pub lsw: RW<u32>,
+    pub msw: RW<u32>,
+}
+
+/// Per-bank configuration, one bit per pin in every register
+#[repr(C)]
+pub struct BankCtrl {
+    /// 1 = output
+    pub dirm: RW<u32>,
+    /// 1 = output driver enabled
+    pub oen: RW<u32>,
+    pub int_mask: RO<u32>,
+    pub int_en: WO<u32>,
+    pub int_dis: WO<u32>,
+    pub int_stat: RW<u32>, // WTC
+    /// 1 = edge, 0 = level
+    pub int_type: RW<u32>,
+    /// 1 = rising edge or active high
+    pub int_polarity: RW<u32>,
+    /// 1 = both edges (edge type only)
+    pub int_any: RW<u32>,
+    unused: [u32; 7],
+}
+assert_layout!(BankCtrl, 0xFF0A_0204, {
+    dirm => 0xFF0A_0204,
+    int_stat => 0xFF0A_0218,
+    int_any => 0xFF0A_0224,
+});
+const _: () = assert!(core::mem::size_of::<BankCtrl>() == 0x40);
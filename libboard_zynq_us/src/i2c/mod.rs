@@ -2,8 +2,11 @@
 use libregister::{RegisterR, RegisterRW, RegisterW};
 use log::{debug, error};
 
-use super::clocks::Clocks;
-use super::slcr::{common::Unlocked, crl_apb};
+use super::clocks::{
+    gate::{ClockGate, GatedClock},
+    Clocks,
+};
+use super::reset::Reset;
 use super::util::div_round_closest;
 
 pub mod regs;
@@ -17,9 +20,6 @@ const DATA_INTR_THRESH: u8 = 14;
 // Maximum size of master transfers in bytes
 // UG1085 says this is 255 but the FSBL says otherwise
 const MAX_TX: u8 = 252;
-// Mux address (same for both)
-#[cfg(feature = "target_zcu111")]
-pub const MUX_ADDR: u16 = 0x75;
 // Max SCL freq (DS926 Table 47)
 const MAX_SCLK_FREQ: u32 = 400_000;
 // Max divisors (note: actual divisor is register value + 1)
@@ -31,18 +31,18 @@ const CLK_DIV_BUILTIN: u32 = 22;
 pub struct I2C {
     regs: &'static mut regs::RegisterBlock,
     ref_clk: u32,
+    _clock: ClockGate,
 }
 
 impl I2C {
     /// Constructor for I2C0 controller.
     pub fn i2c0() -> Self {
-        crl_apb::RegisterBlock::unlocked(|crl_apb| {
-            crl_apb.peri_rst_ctrl.modify(|_, w| w.i2c0_rst(true));
-            crl_apb.peri_rst_ctrl.modify(|_, w| w.i2c0_rst(false));
-        });
+        let clock = ClockGate::acquire(GatedClock::I2c0);
+        Reset::I2c0.pulse();
         let mut self_ = I2C {
             regs: regs::RegisterBlock::i2c0(),
             ref_clk: Clocks::get().i2c0_ref_clk(),
+            _clock: clock,
         };
         self_.reset(false);
         self_
@@ -50,13 +50,12 @@ impl I2C {
 
     /// Constructor for I2C1 controller.
     pub fn i2c1() -> Self {
-        crl_apb::RegisterBlock::unlocked(|crl_apb| {
-            crl_apb.peri_rst_ctrl.modify(|_, w| w.i2c1_rst(true));
-            crl_apb.peri_rst_ctrl.modify(|_, w| w.i2c1_rst(false));
-        });
+        let clock = ClockGate::acquire(GatedClock::I2c1);
+        Reset::I2c1.pulse();
         let mut self_ = I2C {
             regs: regs::RegisterBlock::i2c1(),
             ref_clk: Clocks::get().i2c1_ref_clk(),
+            _clock: clock,
         };
         self_.reset(false);
         // glitch filter set to number of clock cycles that equals 50 ns (1 / 20 MHz)
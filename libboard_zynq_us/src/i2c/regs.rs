@@ -20,6 +20,20 @@ pub struct RegisterBlock {
 
 register_at!(RegisterBlock, 0xFF02_0000, i2c0);
 register_at!(RegisterBlock, 0xFF03_0000, i2c1);
+assert_layout!(RegisterBlock, 0xFF02_0000, {
+    control => 0xFF02_0000,
+    status => 0xFF02_0004,
+    addr => 0xFF02_0008,
+    data => 0xFF02_000C,
+    interrupt_status => 0xFF02_0010,
+    tx_size => 0xFF02_0014,
+    slv_mon_pause => 0xFF02_0018,
+    timeout => 0xFF02_001C,
+    interrupt_mask => 0xFF02_0020,
+    interrupt_enable => 0xFF02_0024,
+    interrupt_disable => 0xFF02_0028,
+    glitch_filter => 0xFF02_002C,
+});
 
 register!(control, Control, RW, u32);
 // div += 1
@@ -57,6 +57,30 @@ pub struct GicD {
 }
 
 register_at!(GicD, 0xF901_0000, gicd);
+assert_layout!(GicD, 0xF901_0000, {
+    ctrl => 0xF901_0000,
+    iid => 0xF901_0008,
+    group => 0xF901_0080,
+    set_enable => 0xF901_0100,
+    clear_enable => 0xF901_0180,
+    set_pending => 0xF901_0200,
+    clear_pending => 0xF901_0280,
+    set_active => 0xF901_0300,
+    clear_active => 0xF901_0380,
+    priority => 0xF901_0400,
+    target_0_7 => 0xF901_0800,
+    target_8_47 => 0xF901_0820,
+    config_sgi => 0xF901_0C00,
+    config_spi => 0xF901_0C08,
+    ppi_status => 0xF901_0D00,
+    spi_status => 0xF901_0D04,
+    sgi => 0xF901_0F00,
+    sgi_clear_pending => 0xF901_0F10,
+    sgi_set_pending => 0xF901_0F20,
+    pid4 => 0xF901_0FD0,
+    pid0 => 0xF901_0FE0,
+    cid => 0xF901_0FF0,
+});
 
 /// GIC CPU interface register5s
 #[repr(C)]
@@ -81,3 +105,13 @@ pub struct GicC {
 }
 
 register_at!(GicC, 0xF902_0000, gicc);
+assert_layout!(GicC, 0xF902_0000, {
+    ctrl => 0xF902_0000,
+    interrupt_ack => 0xF902_000C,
+    end_of_interrupt => 0xF902_0010,
+    highest_prio_pending => 0xF902_0018,
+    aliased_highest_prio_pending => 0xF902_0028,
+    active_prio => 0xF902_00D0,
+    nonsecure_active_prio => 0xF902_00E0,
+    iid => 0xF902_00FC,
+});
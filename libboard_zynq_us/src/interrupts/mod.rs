@@ -7,4 +7,77 @@
 //! > controller and is compliant to the GICv2 architecture specification. The GIC manages the
 //! > software-generated interrupts (SGI), each CPU’s private peripheral interrupts (PPI), and the
 //! > shared peripheral interrupts (SPI).
-pub mod gic400;
\ No newline at end of file
+pub mod gic400;
+
+use self::gic400::{GicC, GicD};
+
+/// Shared peripheral interrupt IDs routed to the GIC-400 (UG1085 Table 13-1)
+pub mod irq {
+    pub const GPIO: u32 = 48;
+    /// First of three consecutive IDs (counters 1-3) per TTC
+    pub const TTC0: u32 = 68;
+    pub const TTC1: u32 = 71;
+    pub const TTC2: u32 = 74;
+    pub const TTC3: u32 = 77;
+}
+
+/// Number of interrupt IDs implemented by the distributor
+pub const NUM_IRQS: u32 = 192;
+const FIRST_SPI: u32 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// Active-high level
+    Level,
+    /// Rising edge
+    Edge,
+}
+
+/// Enable the distributor and this core's CPU interface. The first time the CPU interface is
+/// enabled its priority mask is opened to let interrupts of every priority through.
+pub fn enable_gic() {
+    unsafe {
+        GicD::gicd().ctrl.modify(|r| r | 1);
+        let gicc = GicC::gicc();
+        if gicc.ctrl.read() & 1 == 0 {
+            gicc.prio_mask.write(0xFF);
+            gicc.ctrl.modify(|r| r | 1);
+        }
+    }
+}
+
+/// Route shared peripheral interrupt `id` to the CPUs in `cpu_targets` (one bit per core) and
+/// enable it in the distributor, enabling the GIC itself if needed. Lower `priority` values are
+/// more urgent.
+pub fn enable_spi(id: u32, trigger: Trigger, priority: u8, cpu_targets: u8) {
+    assert!((FIRST_SPI..NUM_IRQS).contains(&id));
+    let gicd = GicD::gicd();
+    let (word, byte) = ((id / 4) as usize, 8 * (id % 4));
+    let config_shift = 2 * (id % 16);
+    unsafe {
+        gicd.priority[word].modify(|r| r & !(0xFF << byte) | u32::from(priority) << byte);
+        gicd.target_8_47[word - 8].modify(|r| r & !(0xFF << byte) | u32::from(cpu_targets) << byte);
+        // upper bit of each 2-bit field selects edge triggering
+        gicd.config_spi[(id / 16 - 2) as usize].modify(|r| match trigger {
+            Trigger::Level => r & !(0b10 << config_shift),
+            Trigger::Edge => r | 0b10 << config_shift,
+        });
+        gicd.set_enable[(id / 32) as usize].write(1 << (id % 32));
+    }
+    enable_gic();
+}
+
+pub fn disable_spi(id: u32) {
+    assert!((FIRST_SPI..NUM_IRQS).contains(&id));
+    unsafe {
+        GicD::gicd().clear_enable[(id / 32) as usize].write(1 << (id % 32));
+    }
+}
+
+/// Clear a pending edge-triggered interrupt
+pub fn clear_pending(id: u32) {
+    assert!(id < NUM_IRQS);
+    unsafe {
+        GicD::gicd().clear_pending[(id / 32) as usize].write(1 << (id % 32));
+    }
+}
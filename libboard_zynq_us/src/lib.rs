@@ -1,14 +1,24 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![feature(more_qualified_paths)]
 #![feature(int_roundings)]
 
+#[macro_use]
+mod util;
+
+pub mod apu;
 pub mod axi_hp;
+pub mod board;
 pub mod clocks;
 pub mod ddr;
+pub mod gpio;
 pub mod i2c;
 pub mod interrupts;
 pub mod logger;
+pub mod mio;
+pub mod reset;
 pub mod slcr;
 pub mod stdio;
+pub mod sync;
+pub mod system;
+pub mod ttc;
 pub mod uart;
-mod util;
@@ -0,0 +1,407 @@
+//! Typed MIO pin multiplexing
+//!
+//! Pin functions and their L0-L3 select values follow UG1085 Table 28-3 (MIO pin assignment).
+//! A [MioConfig] is built pin by pin, checked for conflicts as it's built, and only then
+//! written to the IOU_SLCR.
+use libregister::RegisterW;
+
+use crate::slcr::iou_slcr::{
+    self, BankDriveCtrl, BankInputCtrl, BankPullCtrl, BankPullEnable, BankSlewCtrl, MioPin,
+    MioTriEnable, NUM_BANKS, NUM_MIO_PINS,
+};
+
+// MIO pins per bank CSR (banks 0-2 are MIO 0-25, 26-51, 52-77)
+const PINS_PER_BANK: usize = 26;
+// MIO pins per MIO_MST_TRI register
+const PINS_PER_TRI_REG: usize = 32;
+
+/// Peripheral functions that can be routed to MIO pins
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mio {
+    /// Not routed to a peripheral
+    Gpio,
+    /// QSPI lower: MIO 0-5 (SCLK, IO1, IO2, IO3, IO0, SS_N)
+    QspiLower,
+    /// QSPI loopback clock: MIO 6
+    QspiFeedbackClk,
+    /// QSPI upper: MIO 7-12 (SS_N, IO0, IO1, IO2, IO3, SCLK)
+    QspiUpper,
+    I2c0Scl,
+    I2c0Sda,
+    I2c1Scl,
+    I2c1Sda,
+    Uart0Rx,
+    Uart0Tx,
+    Uart1Rx,
+    Uart1Tx,
+    Can0Rx,
+    Can0Tx,
+    Can1Rx,
+    Can1Tx,
+    /// PMU GPI: any of MIO 26-31
+    PmuIn,
+    /// PMU GPO: any of MIO 32-37
+    PmuOut,
+    /// DisplayPort AUX: MIO 27-30 (AUX out, HPD, AUX OE, AUX in)
+    DpAux,
+    /// SD1 4-bit: MIO 46-51 (data 0-3, cmd, clk)
+    Sd1,
+    /// SD1 8-bit upper data lines: MIO 39-42 (data 4-7)
+    Sd1Data8,
+    /// SD1 card detect: MIO 45
+    Sd1Cd,
+    /// USB0 ULPI: MIO 52-63
+    Usb0,
+    /// USB1 ULPI: MIO 64-75
+    Usb1,
+    /// GEM0 RGMII: MIO 26-37
+    Gem0,
+    /// GEM1 RGMII: MIO 38-49
+    Gem1,
+    /// GEM2 RGMII: MIO 52-63
+    Gem2,
+    /// GEM3 RGMII: MIO 64-75
+    Gem3,
+    /// GEM3 MDIO: MIO 76-77
+    Mdio3,
+    /// TTC0 clock input: any of MIO 6-70 in steps of 8
+    Ttc0Clk,
+    /// TTC0 waveform output: any of MIO 7-71 in steps of 8
+    Ttc0Wave,
+    /// TTC1 clock input: any of MIO 4-68 in steps of 8
+    Ttc1Clk,
+    /// TTC1 waveform output: any of MIO 5-69 in steps of 8
+    Ttc1Wave,
+    /// TTC2 clock input: any of MIO 2-66 in steps of 8
+    Ttc2Clk,
+    /// TTC2 waveform output: any of MIO 3-67 in steps of 8
+    Ttc2Wave,
+    /// TTC3 clock input: any of MIO 0-64 in steps of 8
+    Ttc3Clk,
+    /// TTC3 waveform output: any of MIO 1-65 in steps of 8
+    Ttc3Wave,
+}
+
+/// Legal pin locations of a [Mio] function
+enum Pins {
+    /// Occupies every pin in the range
+    Group(u8, u8),
+    /// Occupies a single pin out of (first..=last).step_by(step)
+    Choice(u8, u8, u8),
+}
+
+/// MIO_PIN mux level selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MioSel {
+    l0: bool,
+    l1: bool,
+    l2: u8,
+    l3: u8,
+}
+
+const fn sel(l0: bool, l1: bool, l2: u8, l3: u8) -> MioSel {
+    MioSel { l0, l1, l2, l3 }
+}
+
+impl Mio {
+    fn pins(self) -> Pins {
+        match self {
+            Mio::Gpio => Pins::Choice(0, NUM_MIO_PINS as u8 - 1, 1),
+            Mio::QspiLower => Pins::Group(0, 5),
+            Mio::QspiFeedbackClk => Pins::Group(6, 6),
+            Mio::QspiUpper => Pins::Group(7, 12),
+            Mio::I2c1Scl | Mio::Uart1Tx | Mio::Can1Tx => Pins::Choice(0, 72, 4),
+            Mio::I2c1Sda | Mio::Uart1Rx | Mio::Can1Rx => Pins::Choice(1, 73, 4),
+            Mio::I2c0Scl | Mio::Uart0Rx | Mio::Can0Rx => Pins::Choice(2, 74, 4),
+            Mio::I2c0Sda | Mio::Uart0Tx | Mio::Can0Tx => Pins::Choice(3, 75, 4),
+            Mio::PmuIn => Pins::Choice(26, 31, 1),
+            Mio::PmuOut => Pins::Choice(32, 37, 1),
+            Mio::DpAux => Pins::Group(27, 30),
+            Mio::Sd1 => Pins::Group(46, 51),
+            Mio::Sd1Data8 => Pins::Group(39, 42),
+            Mio::Sd1Cd => Pins::Group(45, 45),
+            Mio::Usb0 => Pins::Group(52, 63),
+            Mio::Usb1 => Pins::Group(64, 75),
+            Mio::Gem0 => Pins::Group(26, 37),
+            Mio::Gem1 => Pins::Group(38, 49),
+            Mio::Gem2 => Pins::Group(52, 63),
+            Mio::Gem3 => Pins::Group(64, 75),
+            Mio::Mdio3 => Pins::Group(76, 77),
+            Mio::Ttc3Clk => Pins::Choice(0, 64, 8),
+            Mio::Ttc3Wave => Pins::Choice(1, 65, 8),
+            Mio::Ttc2Clk => Pins::Choice(2, 66, 8),
+            Mio::Ttc2Wave => Pins::Choice(3, 67, 8),
+            Mio::Ttc1Clk => Pins::Choice(4, 68, 8),
+            Mio::Ttc1Wave => Pins::Choice(5, 69, 8),
+            Mio::Ttc0Clk => Pins::Choice(6, 70, 8),
+            Mio::Ttc0Wave => Pins::Choice(7, 71, 8),
+        }
+    }
+
+    fn sel(self) -> MioSel {
+        match self {
+            Mio::Gpio => sel(false, false, 0, 0),
+            Mio::QspiLower | Mio::QspiFeedbackClk | Mio::QspiUpper => sel(true, false, 0, 0),
+            Mio::Gem0 | Mio::Gem1 | Mio::Gem2 | Mio::Gem3 => sel(true, false, 0, 0),
+            Mio::Usb0 | Mio::Usb1 => sel(false, true, 0, 0),
+            Mio::PmuIn | Mio::PmuOut => sel(false, false, 1, 0),
+            Mio::Sd1 | Mio::Sd1Data8 | Mio::Sd1Cd => sel(false, false, 2, 0),
+            Mio::DpAux => sel(false, false, 3, 0),
+            Mio::Can0Rx | Mio::Can0Tx | Mio::Can1Rx | Mio::Can1Tx => sel(false, false, 0, 1),
+            Mio::I2c0Scl | Mio::I2c0Sda | Mio::I2c1Scl | Mio::I2c1Sda => sel(false, false, 0, 2),
+            Mio::Ttc0Clk | Mio::Ttc0Wave | Mio::Ttc1Clk | Mio::Ttc1Wave => sel(false, false, 0, 5),
+            Mio::Ttc2Clk | Mio::Ttc2Wave | Mio::Ttc3Clk | Mio::Ttc3Wave => sel(false, false, 0, 5),
+            Mio::Uart0Rx | Mio::Uart0Tx | Mio::Uart1Rx | Mio::Uart1Tx => sel(false, false, 0, 6),
+            Mio::Mdio3 => sel(false, false, 0, 6),
+        }
+    }
+
+    /// Whether several pins may carry this function at once
+    fn shared(self) -> bool {
+        matches!(self, Mio::Gpio | Mio::PmuIn | Mio::PmuOut)
+    }
+
+    /// Input-only pins, which have their output tri-stated
+    fn is_input(self, pin: u8) -> bool {
+        match self {
+            Mio::Uart0Rx | Mio::Uart1Rx | Mio::Can0Rx | Mio::Can1Rx => true,
+            Mio::PmuIn | Mio::Sd1Cd => true,
+            Mio::Ttc0Clk | Mio::Ttc1Clk | Mio::Ttc2Clk | Mio::Ttc3Clk => true,
+            // HPD, AUX in
+            Mio::DpAux => pin == 28 || pin == 30,
+            // clk, dir, nxt
+            Mio::Usb0 | Mio::Usb1 => {
+                let offset = pin - if self == Mio::Usb0 { 52 } else { 64 };
+                offset == 0 || offset == 1 || offset == 3
+            }
+            // rx clk, rxd[0:3], rx ctl
+            Mio::Gem0 => pin >= 32,
+            Mio::Gem1 => pin >= 44,
+            Mio::Gem2 => pin >= 58,
+            Mio::Gem3 => pin >= 70,
+            _ => false,
+        }
+    }
+
+    fn is_legal(self, pin: u8) -> bool {
+        match self.pins() {
+            Pins::Group(first, last) => (first..=last).contains(&pin),
+            Pins::Choice(first, last, step) => {
+                (first..=last).contains(&pin) && (pin - first) % step == 0
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MioError {
+    /// Function can't be routed to the given pin
+    IllegalPin(Mio, u8),
+    /// Pin is already assigned to a function
+    PinConflict {
+        pin: u8,
+        assigned: Mio,
+        requested: Mio,
+    },
+    /// Single-pin function already has a pin
+    FunctionConflict(Mio),
+    /// Multi-pin function must be assigned with [MioConfig::group]
+    NotSinglePin(Mio),
+    /// Single-pin function must be assigned with [MioConfig::pin]
+    NotGroup(Mio),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pull {
+    None,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Slew {
+    Fast,
+    Slow,
+}
+
+/// Output drive strength ({DRIVE1, DRIVE0} bits of the bank CSRs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum Drive {
+    Ma2 = 0b00,
+    Ma4 = 0b01,
+    Ma8 = 0b10,
+    Ma12 = 0b11,
+}
+
+/// Electrical settings of a single pin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinSettings {
+    pub pull: Pull,
+    pub slew: Slew,
+    pub drive: Drive,
+    /// Schmitt trigger (true) or CMOS (false) input
+    pub schmitt: bool,
+}
+
+impl Default for PinSettings {
+    /// Reset values: pull-up, fast slew, 12 mA, CMOS input
+    fn default() -> Self {
+        PinSettings {
+            pull: Pull::Up,
+            slew: Slew::Fast,
+            drive: Drive::Ma12,
+            schmitt: false,
+        }
+    }
+}
+
+/// Complete MIO pin assignment; unassigned pins are GPIOs with default settings
+pub struct MioConfig {
+    pins: [Option<(Mio, PinSettings)>; NUM_MIO_PINS],
+}
+
+impl Default for MioConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MioConfig {
+    pub fn new() -> Self {
+        MioConfig {
+            pins: [None; NUM_MIO_PINS],
+        }
+    }
+
+    fn claim(&mut self, pin: u8, function: Mio, settings: PinSettings) -> Result<(), MioError> {
+        if !function.is_legal(pin) {
+            return Err(MioError::IllegalPin(function, pin));
+        }
+        if let Some((assigned, _)) = self.pins[usize::from(pin)] {
+            return Err(MioError::PinConflict {
+                pin,
+                assigned,
+                requested: function,
+            });
+        }
+        self.pins[usize::from(pin)] = Some((function, settings));
+        Ok(())
+    }
+
+    /// Route a single-pin function to `pin` with default settings
+    pub fn pin(&mut self, pin: u8, function: Mio) -> Result<&mut Self, MioError> {
+        self.pin_with(pin, function, PinSettings::default())
+    }
+
+    /// Route a single-pin function to `pin`
+    pub fn pin_with(
+        &mut self,
+        pin: u8,
+        function: Mio,
+        settings: PinSettings,
+    ) -> Result<&mut Self, MioError> {
+        match function.pins() {
+            Pins::Choice(..) => (),
+            Pins::Group(first, last) if first == last => (),
+            Pins::Group(..) => return Err(MioError::NotSinglePin(function)),
+        }
+        if !function.shared() && self.pins.iter().flatten().any(|&(f, _)| f == function) {
+            return Err(MioError::FunctionConflict(function));
+        }
+        self.claim(pin, function, settings)?;
+        Ok(self)
+    }
+
+    /// Route a function to its fixed pins with default settings
+    pub fn group(&mut self, function: Mio) -> Result<&mut Self, MioError> {
+        self.group_with(function, PinSettings::default())
+    }
+
+    /// Route a function to its fixed pins
+    pub fn group_with(
+        &mut self,
+        function: Mio,
+        settings: PinSettings,
+    ) -> Result<&mut Self, MioError> {
+        let (first, last) = match function.pins() {
+            Pins::Group(first, last) => (first, last),
+            Pins::Choice(..) => return Err(MioError::NotGroup(function)),
+        };
+        // check every pin before claiming any, so a failed call leaves the config untouched
+        for pin in first..=last {
+            if let Some((assigned, _)) = self.pins[usize::from(pin)] {
+                return Err(MioError::PinConflict {
+                    pin,
+                    assigned,
+                    requested: function,
+                });
+            }
+        }
+        for pin in first..=last {
+            self.claim(pin, function, settings)?;
+        }
+        Ok(self)
+    }
+
+    /// Function currently assigned to `pin`
+    pub fn function(&self, pin: u8) -> Mio {
+        self.pins[usize::from(pin)].map_or(Mio::Gpio, |(function, _)| function)
+    }
+
+    /// Write the configuration to the IOU_SLCR
+    pub fn apply(&self, iou_slcr: &mut iou_slcr::RegisterBlock) {
+        let mut drive = [[0u32; 2]; NUM_BANKS];
+        let mut schmitt = [0u32; NUM_BANKS];
+        let mut pullup = [0u32; NUM_BANKS];
+        let mut pull_enable = [0u32; NUM_BANKS];
+        let mut slow_slew = [0u32; NUM_BANKS];
+        let mut tri_enable = [0u32; NUM_BANKS];
+
+        for pin in 0..NUM_MIO_PINS {
+            let (function, settings) =
+                self.pins[pin].unwrap_or((Mio::Gpio, PinSettings::default()));
+            let sel = function.sel();
+            iou_slcr.mio_pin[pin].write(
+                MioPin::zeroed()
+                    .l0_sel(sel.l0)
+                    .l1_sel(sel.l1)
+                    .l2_sel(sel.l2)
+                    .l3_sel(sel.l3),
+            );
+
+            let (bank, bit) = (pin / PINS_PER_BANK, pin % PINS_PER_BANK);
+            let drive_bits = settings.drive as u32;
+            drive[bank][0] |= (drive_bits & 1) << bit;
+            drive[bank][1] |= (drive_bits >> 1) << bit;
+            schmitt[bank] |= u32::from(settings.schmitt) << bit;
+            pullup[bank] |= u32::from(settings.pull == Pull::Up) << bit;
+            pull_enable[bank] |= u32::from(settings.pull != Pull::None) << bit;
+            slow_slew[bank] |= u32::from(settings.slew == Slew::Slow) << bit;
+
+            let input = function.is_input(pin as u8);
+            tri_enable[pin / PINS_PER_TRI_REG] |= u32::from(input) << (pin % PINS_PER_TRI_REG);
+        }
+
+        for bank in 0..NUM_BANKS {
+            let bank_csr = &mut iou_slcr.bank_csr[bank];
+            for (drive_ctrl, &drive) in bank_csr.bank_drive_ctrl.iter_mut().zip(&drive[bank]) {
+                drive_ctrl.write(BankDriveCtrl::zeroed().drive(drive));
+            }
+            bank_csr
+                .bank_input_ctrl
+                .write(BankInputCtrl::zeroed().schmitt(schmitt[bank]));
+            bank_csr
+                .bank_pull_ctrl
+                .write(BankPullCtrl::zeroed().pullup(pullup[bank]));
+            bank_csr
+                .bank_pull_enable
+                .write(BankPullEnable::zeroed().pull_enable(pull_enable[bank]));
+            bank_csr
+                .bank_slew_ctrl
+                .write(BankSlewCtrl::zeroed().slow_slew(slow_slew[bank]));
+
+            iou_slcr.mio_tri_enable[bank].write(MioTriEnable::zeroed().enable(tri_enable[bank]));
+        }
+    }
+}
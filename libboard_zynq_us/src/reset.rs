@@ -0,0 +1,142 @@
+//! Block resets in CRL_APB and CRF_APB
+use libregister::{RegisterR, RegisterRW};
+
+use crate::slcr::{common::Unlocked, crf_apb, crl_apb};
+
+/// Default hold time of [Reset::pulse]
+pub const RESET_HOLD_NS: u32 = 1_000;
+
+macro_rules! resets {
+    ($($(#[$meta: meta])* $variant: ident => $block: ident.$reg: ident.$bit: ident,)*) => {
+        /// A reset bit in one of the CRL_APB or CRF_APB reset control registers
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Reset {
+            $($(#[$meta])* $variant,)*
+        }
+
+        impl Reset {
+            fn set(self, asserted: bool) {
+                match self {
+                    $(Reset::$variant => $block::RegisterBlock::unlocked(|slcr| {
+                        slcr.$reg.modify(|_, w| w.$bit(asserted))
+                    }),)*
+                }
+            }
+
+            /// Whether the block is currently held in reset
+            pub fn is_asserted(self) -> bool {
+                match self {
+                    $(Reset::$variant => $block::RegisterBlock::$block().$reg.read().$bit(),)*
+                }
+            }
+        }
+    };
+}
+
+resets! {
+    // PERI_RST_CTRL
+    Timestamp => crl_apb.peri_rst_ctrl.timestamp_rst,
+    IouCc => crl_apb.peri_rst_ctrl.iou_cc_rst,
+    Gpio => crl_apb.peri_rst_ctrl.gpio_rst,
+    LpdDma => crl_apb.peri_rst_ctrl.lpd_dma_rst,
+    Nand => crl_apb.peri_rst_ctrl.nand_rst,
+    Swdt => crl_apb.peri_rst_ctrl.swdt_rst,
+    Ttc3 => crl_apb.peri_rst_ctrl.ttc3_rst,
+    Ttc2 => crl_apb.peri_rst_ctrl.ttc2_rst,
+    Ttc1 => crl_apb.peri_rst_ctrl.ttc1_rst,
+    Ttc0 => crl_apb.peri_rst_ctrl.ttc0_rst,
+    I2c1 => crl_apb.peri_rst_ctrl.i2c1_rst,
+    I2c0 => crl_apb.peri_rst_ctrl.i2c0_rst,
+    Can1 => crl_apb.peri_rst_ctrl.can1_rst,
+    Can0 => crl_apb.peri_rst_ctrl.can0_rst,
+    Sdio1 => crl_apb.peri_rst_ctrl.sdio1_rst,
+    Sdio0 => crl_apb.peri_rst_ctrl.sdio0_rst,
+    Spi1 => crl_apb.peri_rst_ctrl.spi1_rst,
+    Spi0 => crl_apb.peri_rst_ctrl.spi0_rst,
+    Uart1 => crl_apb.peri_rst_ctrl.uart1_rst,
+    Uart0 => crl_apb.peri_rst_ctrl.uart0_rst,
+    Qspi => crl_apb.peri_rst_ctrl.qspi_rst,
+    // GEM_RST_CTRL
+    Gem3 => crl_apb.gem_rst_ctrl.gem3_rst,
+    Gem2 => crl_apb.gem_rst_ctrl.gem2_rst,
+    Gem1 => crl_apb.gem_rst_ctrl.gem1_rst,
+    Gem0 => crl_apb.gem_rst_ctrl.gem0_rst,
+    // RST_LPD_TOP
+    /// Entire full-power domain, including CRF_APB
+    Fpd => crl_apb.rst_lpd_top.fpd_rst,
+    LpdSwdt => crl_apb.rst_lpd_top.lpd_swdt_rst,
+    SAxiLpd => crl_apb.rst_lpd_top.s_axi_lpd_rst,
+    Sysmon => crl_apb.rst_lpd_top.sysmon_rst,
+    Rtc => crl_apb.rst_lpd_top.rtc_rst,
+    LpdApm => crl_apb.rst_lpd_top.apm_rst,
+    Ipi => crl_apb.rst_lpd_top.ipi_rst,
+    Usb1Apb => crl_apb.rst_lpd_top.usb1_apb_rst,
+    Usb0Apb => crl_apb.rst_lpd_top.usb0_apb_rst,
+    Usb1Hiber => crl_apb.rst_lpd_top.usb1_hiber_rst,
+    Usb0Hiber => crl_apb.rst_lpd_top.usb0_hiber_rst,
+    Usb1Core => crl_apb.rst_lpd_top.usb1_core_rst,
+    Usb0Core => crl_apb.rst_lpd_top.usb0_core_rst,
+    RpuPge => crl_apb.rst_lpd_top.rpu_pge_rst,
+    Ocm => crl_apb.rst_lpd_top.ocm_rst,
+    RpuAmba => crl_apb.rst_lpd_top.rpu_amba_rst,
+    RpuCore1 => crl_apb.rst_lpd_top.rpu_core1_rst,
+    RpuCore0 => crl_apb.rst_lpd_top.rpu_core0_rst,
+    // RST_FPD_TOP
+    PcieCfg => crf_apb.rst_fpd_top.pcie_cfg_reset,
+    PcieBridge => crf_apb.rst_fpd_top.pcie_bridge_reset,
+    PcieCtrl => crf_apb.rst_fpd_top.pcie_ctrl_reset,
+    Dp => crf_apb.rst_fpd_top.dp_reset,
+    FpdSwdt => crf_apb.rst_fpd_top.swdt_reset,
+    SAxiHpc3Fpd => crf_apb.rst_fpd_top.s_axi_hpc_3_fpd_reset,
+    SAxiHpc2Fpd => crf_apb.rst_fpd_top.s_axi_hpc_2_fpd_reset,
+    SAxiHp1Fpd => crf_apb.rst_fpd_top.s_axi_hp_1_fpd_reset,
+    SAxiHp0Fpd => crf_apb.rst_fpd_top.s_axi_hp_0_fpd_reset,
+    SAxiHpc1Fpd => crf_apb.rst_fpd_top.s_axi_hpc_1_fpd_reset,
+    SAxiHpc0Fpd => crf_apb.rst_fpd_top.s_axi_hpc_0_fpd_reset,
+    FpdDma => crf_apb.rst_fpd_top.fpd_dma_reset,
+    GpuPp1 => crf_apb.rst_fpd_top.gpu_pp1_reset,
+    GpuPp0 => crf_apb.rst_fpd_top.gpu_pp0_reset,
+    Gpu => crf_apb.rst_fpd_top.gpu_reset,
+    Gt => crf_apb.rst_fpd_top.gt_reset,
+    Sata => crf_apb.rst_fpd_top.sata_reset,
+    // RST_FPD_APU
+    Apu3Por => crf_apb.rst_fpd_apu.apu3_por,
+    Apu2Por => crf_apb.rst_fpd_apu.apu2_por,
+    Apu1Por => crf_apb.rst_fpd_apu.apu1_por,
+    Apu0Por => crf_apb.rst_fpd_apu.apu0_por,
+    ApuL2 => crf_apb.rst_fpd_apu.apu_l2_reset,
+    Apu3 => crf_apb.rst_fpd_apu.apu3_reset,
+    Apu2 => crf_apb.rst_fpd_apu.apu2_reset,
+    Apu1 => crf_apb.rst_fpd_apu.apu1_reset,
+    Apu0 => crf_apb.rst_fpd_apu.apu0_reset,
+    // RST_DDR_SS
+    Ddr => crf_apb.rst_ddr_ss.ddr_reset,
+    DdrApm => crf_apb.rst_ddr_ss.apm_reset,
+}
+
+impl Reset {
+    /// Hold the block in reset
+    pub fn assert(self) {
+        self.set(true);
+    }
+
+    /// Release the block from reset
+    pub fn deassert(self) {
+        self.set(false);
+    }
+
+    /// Assert and release after [RESET_HOLD_NS]
+    pub fn pulse(self) {
+        self.pulse_for(RESET_HOLD_NS);
+    }
+
+    /// Assert and release after at least `hold_ns`
+    pub fn pulse_for(self, hold_ns: u32) {
+        self.assert();
+        // each register read takes more than 1 ns
+        for _ in 0..hold_ns {
+            let _ = self.is_asserted();
+        }
+        self.deassert();
+    }
+}
@@ -0,0 +1,63 @@
+///! APU control (cluster configuration, reset vectors and power control)
+use libregister::{register, register_at, register_bit, register_bits};
+use volatile_register::RW;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub err_ctrl: RW<u32>,
+    unused0: [u32; 3],
+    pub isr: RW<u32>, // todo: WTC LSB
+    pub imr: RW<u32>,
+    pub ien: RW<u32>,
+    pub ids: RW<u32>,
+    pub config_0: Config0,
+    pub config_1: RW<u32>,
+    unused1: [u32; 6],
+    pub rvbaraddr: [RvBarAddr; 4],
+    pub ace_ctrl: RW<u32>,
+    unused2: [u32; 7],
+    pub snoop_ctrl: RW<u32>,
+    unused3: [u32; 3],
+    pub pwrctl: PwrCtl,
+    pub pwrstat: PwrStat,
+}
+register_at!(RegisterBlock, 0xFD5C_0000, apu);
+assert_layout!(RegisterBlock, 0xFD5C_0000, {
+    isr => 0xFD5C_0010,
+    config_0 => 0xFD5C_0020,
+    rvbaraddr => 0xFD5C_0040,
+    ace_ctrl => 0xFD5C_0060,
+    snoop_ctrl => 0xFD5C_0080,
+    pwrctl => 0xFD5C_0090,
+    pwrstat => 0xFD5C_0094,
+});
+
+/// Reset vector base address of one core (RVBARADDRnL/H)
+#[repr(C)]
+pub struct RvBarAddr {
+    /// Bits [31:2] of the address
+    pub lo: RW<u32>,
+    /// Bits [39:32] of the address
+    pub hi: RW<u32>,
+}
+
+// One bit per core in each field
+register!(config_0, Config0, RW, u32);
+// Enable T32 exception handling
+register_bits!(config_0, cfgte, u8, 24, 27);
+// Big-endian exception handling
+register_bits!(config_0, cfgend, u8, 16, 19);
+// High exception vectors (AArch32)
+register_bits!(config_0, vinithi, u8, 8, 11);
+// AArch64 (1) or AArch32 (0) at reset
+register_bits!(config_0, aa64naa32, u8, 0, 3);
+
+register!(pwrctl, PwrCtl, RW, u32);
+register_bit!(pwrctl, clrexmonreq, 17);
+register_bit!(pwrctl, l2flushreq, 16);
+register_bits!(pwrctl, cpupwrdwnreq, u8, 0, 3);
+
+register!(pwrstat, PwrStat, RO, u32);
+register_bit!(pwrstat, clrexmonack, 17, RO);
+register_bit!(pwrstat, l2flush_done, 16, RO);
+register_bits!(pwrstat, dbgnopwrdwn, u8, 0, 3, RO);
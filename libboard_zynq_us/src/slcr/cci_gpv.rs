@@ -0,0 +1,57 @@
+///! CCI-400 cache coherent interconnect programmer's view
+use libregister::{register, register_at, register_bit};
+use volatile_register::RW;
+
+pub const NUM_SLAVE_INTERFACES: usize = 5;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub control_override: RW<u32>,
+    pub speculation_control: RW<u32>,
+    pub secure_access: RW<u32>,
+    pub status: Status,
+    pub imprecise_error: RW<u32>,
+    unused0: [u32; 59],
+    pub performance_monitor_control: RW<u32>,
+    unused1: [u32; 959],
+    pub slave: [SlaveInterface; NUM_SLAVE_INTERFACES],
+}
+register_at!(RegisterBlock, 0xFD6E_0000, cci_gpv);
+assert_layout!(RegisterBlock, 0xFD6E_0000, {
+    status => 0xFD6E_000C,
+    imprecise_error => 0xFD6E_0010,
+    performance_monitor_control => 0xFD6E_0100,
+    slave => 0xFD6E_1000,
+});
+assert_layout!(SlaveInterface, 0xFD6E_1000, {
+    read_qos_override => 0xFD6E_1100,
+    qos_control => 0xFD6E_110C,
+    target_latency => 0xFD6E_1130,
+    qos_range => 0xFD6E_1138,
+});
+const _: () = assert!(core::mem::size_of::<SlaveInterface>() == 0x1000);
+
+#[repr(C)]
+pub struct SlaveInterface {
+    pub snoop_control: SnoopControl,
+    pub shareable_override: RW<u32>,
+    unused0: [u32; 62],
+    pub read_qos_override: RW<u32>,
+    pub write_qos_override: RW<u32>,
+    unused1: [u32; 1],
+    pub qos_control: RW<u32>,
+    pub max_ot: RW<u32>,
+    unused2: [u32; 7],
+    pub target_latency: RW<u32>,
+    pub latency_regulation: RW<u32>,
+    pub qos_range: RW<u32>,
+    unused3: [u32; 945],
+}
+
+register!(status, Status, RO, u32);
+// wait for this to clear after changing snoop_control
+register_bit!(status, change_pending, 0, RO);
+
+register!(snoop_control, SnoopControl, RW, u32);
+register_bit!(snoop_control, enable_dvm, 1);
+register_bit!(snoop_control, enable_snoop, 0);
@@ -1,15 +1,143 @@
 ///! Type definitions for re-use across SLCR blocks
-use libregister::{register, register_bit, register_bits};
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use libregister::{register, register_bit, register_bits, RegisterR, RegisterW};
 
-pub trait Unlocked {
-    fn unlocked<F: FnMut(&mut Self) -> R, R>(f: F) -> R;
+use crate::sync::BakeryLock;
+
+/// SLCR blocks whose registers may sit behind a write-protect register
+pub trait Unlocked: Sized + 'static {
+    fn regs() -> &'static mut Self;
+
+    /// Write-protect register, `None` if the block has none
+    fn wprot(&mut self) -> Option<&mut WProt>;
+
+    /// Bookkeeping shared by all guards of this block
+    fn wprot_state() -> &'static WProtState;
+
+    /// Unlock the block until the returned guard is dropped
+    fn unlock() -> UnlockGuard<Self> {
+        let regs = Self::regs();
+        if let Some(wprot) = regs.wprot() {
+            Self::wprot_state().acquire(wprot);
+        }
+        UnlockGuard { regs }
+    }
+
+    fn unlocked<F: FnMut(&mut Self) -> R, R>(mut f: F) -> R {
+        let mut guard = Self::unlock();
+        f(&mut guard)
+    }
+}
+
+/// Write access to an SLCR block
+///
+/// The first live guard of a block records whether it was write-protected and unlocks it, the
+/// last one to be dropped restores that state. Guards may be nested and held on several cores.
+pub struct UnlockGuard<T: Unlocked> {
+    regs: &'static mut T,
+}
+
+impl<T: Unlocked> Deref for UnlockGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.regs
+    }
+}
+
+impl<T: Unlocked> DerefMut for UnlockGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.regs
+    }
+}
+
+impl<T: Unlocked> Drop for UnlockGuard<T> {
+    fn drop(&mut self) {
+        if let Some(wprot) = self.regs.wprot() {
+            T::wprot_state().release(wprot);
+        }
+    }
+}
+
+/// Number of live [UnlockGuard]s of a block and its write-protect state before the first one
+pub struct WProtState {
+    // keeps the depth count and the WPROT register consistent across cores
+    lock: BakeryLock,
+    depth: AtomicU32,
+    was_locked: AtomicBool,
+}
+
+impl Default for WProtState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WProtState {
+    pub const fn new() -> Self {
+        WProtState {
+            lock: BakeryLock::new(),
+            depth: AtomicU32::new(0),
+            was_locked: AtomicBool::new(false),
+        }
+    }
+
+    fn acquire(&self, wprot: &mut WProt) {
+        self.lock.lock(|| {
+            let depth = self.depth.load(Ordering::Relaxed);
+            if depth == 0 {
+                let locked = wprot.read().active();
+                self.was_locked.store(locked, Ordering::Relaxed);
+                if locked {
+                    wprot.write(WProt::zeroed().active(false));
+                }
+            }
+            self.depth.store(depth + 1, Ordering::Relaxed);
+        })
+    }
+
+    fn release(&self, wprot: &mut WProt) {
+        self.lock.lock(|| {
+            let depth = self.depth.load(Ordering::Relaxed) - 1;
+            self.depth.store(depth, Ordering::Relaxed);
+            if depth == 0 && self.was_locked.load(Ordering::Relaxed) {
+                wprot.write(WProt::zeroed().active(true));
+            }
+        })
+    }
 }
 
 register!(wprot, WProt, RW, u32);
 register_bit!(wprot, active, 0);
 
+/// PLL reference clock selection (PRE_SRC and POST_SRC fields of the PLL_CTRL registers)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum PllSource {
+    PsRefClk = 0b000,
+    VideoRefClk = 0b100,
+    AltRefClk = 0b101,
+    AuxRefClk = 0b110,
+    GtRefClk = 0b111,
+}
+
+impl From<u8> for PllSource {
+    /// Decode a PRE_SRC/POST_SRC field, values 0b001-0b011 also select PS_REF_CLK.
+    fn from(bits: u8) -> Self {
+        match bits & 0b111 {
+            0b000..=0b011 => PllSource::PsRefClk,
+            0b100 => PllSource::VideoRefClk,
+            0b101 => PllSource::AltRefClk,
+            0b110 => PllSource::AuxRefClk,
+            _ => PllSource::GtRefClk,
+        }
+    }
+}
+
 register!(pll_ctrl, PllCtrl, RW, u32);
+// source used in bypass mode, see PllSource
 register_bits!(pll_ctrl, pll_post_src, u8, 24, 26);
+// source of the PLL reference input, see PllSource
 register_bits!(pll_ctrl, pll_pre_src, u8, 20, 22);
 register_bit!(pll_ctrl, pll_div2, 16);
 register_bits!(pll_ctrl, pll_fdiv, u8, 8, 14);
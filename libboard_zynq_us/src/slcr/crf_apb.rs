@@ -1,10 +1,8 @@
 ///! FPD clock and reset control
-use libregister::{
-    register, register_at, register_bit, register_bits, register_bits_typed, RegisterW,
-};
+use libregister::{register, register_at, register_bit, register_bits, register_bits_typed};
 use volatile_register::{RO, RW, WO};
 
-use super::common::{PllCfg, PllCtrl, PllFracCfg, Unlocked, WProt};
+use super::common::{PllCfg, PllCtrl, PllFracCfg, Unlocked, WProt, WProtState};
 
 #[repr(u8)]
 pub enum ApuClkSource {
@@ -62,14 +60,49 @@ pub struct RegisterBlock {
     pub rst_ddr_ss: RstDdrSS,
 }
 register_at!(RegisterBlock, 0xFD1A_0000, crf_apb);
+assert_layout!(RegisterBlock, 0xFD1A_0000, {
+    err_ctrl => 0xFD1A_0000,
+    ir_disable => 0xFD1A_0010,
+    crf_wprot => 0xFD1A_001C,
+    apu_pll_ctrl => 0xFD1A_0020,
+    ddr_pll_ctrl => 0xFD1A_002C,
+    video_pll_ctrl => 0xFD1A_0038,
+    video_pll_frac_cfg => 0xFD1A_0040,
+    pll_status => 0xFD1A_0044,
+    apu_pll_to_lpd_ctrl => 0xFD1A_0048,
+    video_pll_to_lpd_ctrl => 0xFD1A_0050,
+    apu_clk_ctrl => 0xFD1A_0060,
+    dbg_fpd_clk_ctrl => 0xFD1A_0068,
+    dp_video_clk_ctrl => 0xFD1A_0070,
+    dp_audio_clk_ctrl => 0xFD1A_0074,
+    dp_sys_clk_ctrl => 0xFD1A_007C,
+    ddr_clk_ctrl => 0xFD1A_0080,
+    gpu_clk_ctrl => 0xFD1A_0084,
+    sata_clk_ctrl => 0xFD1A_00A0,
+    pcie_clk_ctrl => 0xFD1A_00B4,
+    fpd_dma_clk_ctrl => 0xFD1A_00B8,
+    dp_dma_clk_ctrl => 0xFD1A_00BC,
+    topsw_main_clk_ctrl => 0xFD1A_00C0,
+    topsw_lsbus_clk_ctrl => 0xFD1A_00C4,
+    dbg_tstmp_clk_ctrl => 0xFD1A_00F8,
+    rst_fpd_top => 0xFD1A_0100,
+    rst_fpd_apu => 0xFD1A_0104,
+    rst_ddr_ss => 0xFD1A_0108,
+});
+
+static WPROT_STATE: WProtState = WProtState::new();
 
 impl Unlocked for RegisterBlock {
-    fn unlocked<F: FnMut(&mut Self) -> R, R>(mut f: F) -> R {
-        let mut self_ = Self::crf_apb();
-        self_.crf_wprot.write(WProt::zeroed().active(false));
-        let r = f(&mut self_);
-        self_.crf_wprot.write(WProt::zeroed().active(true));
-        r
+    fn regs() -> &'static mut Self {
+        Self::crf_apb()
+    }
+
+    fn wprot(&mut self) -> Option<&mut WProt> {
+        Some(&mut self.crf_wprot)
+    }
+
+    fn wprot_state() -> &'static WProtState {
+        &WPROT_STATE
     }
 }
 
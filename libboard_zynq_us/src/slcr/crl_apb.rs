@@ -1,12 +1,13 @@
 use libregister::{
-    register, register_at, register_bit, register_bits, register_bits_typed, RegisterRW, RegisterW,
+    register, register_at, register_bit, register_bits, register_bits_typed, RegisterRW,
 };
 ///! LPD clock and reset control
 use volatile_register::{RO, RW, WO};
 
-use super::common::{PllCfg, PllCtrl, PllFracCfg, Unlocked, WProt};
+use super::common::{PllCfg, PllCtrl, PllFracCfg, Unlocked, WProt, WProtState};
 
 /// Clock source selection for IO-type devices
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum IoClkSource {
     IoPll = 0b00,
@@ -15,6 +16,7 @@ pub enum IoClkSource {
 }
 
 /// Clock source selection for RPU and related (e.g. LPD interconnect) devices
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum RpuClkSource {
     RpuPll = 0b00,
@@ -93,51 +95,20 @@ pub struct RegisterBlock {
     unused9: [u32; 1],
     pub safety_chk: RW<u32>,
     unused10: [u32; 3],
-    pub clkmon_status: RW<u32>,
-    pub clkmon_mask: RO<u32>,
-    pub clkmon_enable: WO<u32>,
-    pub clkmon_disable: WO<u32>,
-    pub clkmon_trigger: WO<u32>,
+    pub clkmon_status: ClkmonStatus,
+    pub clkmon_mask: ClkmonMask,
+    pub clkmon_enable: ClkmonEnable,
+    pub clkmon_disable: ClkmonDisable,
+    pub clkmon_trigger: ClkmonTrigger,
     unused11: [u32; 3],
-    pub chkr0_clka_upper: RW<u32>,
-    pub chkr0_clka_lower: RW<u32>,
-    pub chkr0_clkb_cnt: RW<u32>,
-    pub chkr0_ctrl: RW<u32>,
-    pub chkr1_clka_upper: RW<u32>,
-    pub chkr1_clka_lower: RW<u32>,
-    pub chkr1_clkb_cnt: RW<u32>,
-    pub chkr1_ctrl: RW<u32>,
-    pub chkr2_clka_upper: RW<u32>,
-    pub chkr2_clka_lower: RW<u32>,
-    pub chkr2_clkb_cnt: RW<u32>,
-    pub chkr2_ctrl: RW<u32>,
-    pub chkr3_clka_upper: RW<u32>,
-    pub chkr3_clka_lower: RW<u32>,
-    pub chkr3_clkb_cnt: RW<u32>,
-    pub chkr3_ctrl: RW<u32>,
-    pub chkr4_clka_upper: RW<u32>,
-    pub chkr4_clka_lower: RW<u32>,
-    pub chkr4_clkb_cnt: RW<u32>,
-    pub chkr4_ctrl: RW<u32>,
-    pub chkr5_clka_upper: RW<u32>,
-    pub chkr5_clka_lower: RW<u32>,
-    pub chkr5_clkb_cnt: RW<u32>,
-    pub chkr5_ctrl: RW<u32>,
-    pub chkr6_clka_upper: RW<u32>,
-    pub chkr6_clka_lower: RW<u32>,
-    pub chkr6_clkb_cnt: RW<u32>,
-    pub chkr6_ctrl: RW<u32>,
-    pub chkr7_clka_upper: RW<u32>,
-    pub chkr7_clka_lower: RW<u32>,
-    pub chkr7_clkb_cnt: RW<u32>,
-    pub chkr7_ctrl: RW<u32>,
+    pub chkr: [Chkr; 8],
     unused12: [u32; 8],
-    pub boot_mode_user: RW<u32>,
+    pub boot_mode_user: BootModeUser,
     pub boot_mode: BootMode,
     unused13: [u32; 4],
     pub reset_ctrl: ResetCtrl,
     pub blockonly_rst: RW<u32>, // todo: WTC LSB
-    pub reset_reason: RW<u32>,  // todo: WTC 0:6
+    pub reset_reason: ResetReason,
     unused14: [u32; 3],
     pub gem_rst_ctrl: GemRstCtrl,
     unused15: [u32; 1],
@@ -156,14 +127,81 @@ pub struct RegisterBlock {
     pub bank3_status: RO<u32>,
 }
 register_at!(RegisterBlock, 0xFF5E_0000, crl_apb);
+assert_layout!(RegisterBlock, 0xFF5E_0000, {
+    err_ctrl => 0xFF5E_0000,
+    ir_disable => 0xFF5E_0010,
+    crl_wprot => 0xFF5E_001C,
+    io_pll_ctrl => 0xFF5E_0020,
+    io_pll_frac_cfg => 0xFF5E_0028,
+    rpu_pll_ctrl => 0xFF5E_0030,
+    rpu_pll_frac_cfg => 0xFF5E_0038,
+    pll_status => 0xFF5E_0040,
+    rpu_pll_to_fpd_ctrl => 0xFF5E_0048,
+    usb3_clk_ctrl => 0xFF5E_004C,
+    gem0_clk_ctrl => 0xFF5E_0050,
+    usb0_bus_clk_ctrl => 0xFF5E_0060,
+    qspi_clk_ctrl => 0xFF5E_0068,
+    uart0_clk_ctrl => 0xFF5E_0074,
+    can1_clk_ctrl => 0xFF5E_0088,
+    rpu_clk_ctrl => 0xFF5E_0090,
+    iou_switch_clk_ctrl => 0xFF5E_009C,
+    pcap_clk_ctrl => 0xFF5E_00A4,
+    lpd_dma_clk_ctrl => 0xFF5E_00B8,
+    pl0_clk_ctrl => 0xFF5E_00C0,
+    pl0_thr_ctrl => 0xFF5E_00D0,
+    pl3_thr_ctrl => 0xFF5E_00E8,
+    pl3_thr_cnt => 0xFF5E_00FC,
+    gem_tsu_clk_ctrl => 0xFF5E_0100,
+    ps_sysmon_clk_ctrl => 0xFF5E_0108,
+    i2c0_clk_ctrl => 0xFF5E_0120,
+    timestamp_clk_ctrl => 0xFF5E_0128,
+    safety_chk => 0xFF5E_0130,
+    clkmon_status => 0xFF5E_0140,
+    clkmon_trigger => 0xFF5E_0150,
+    chkr => 0xFF5E_0160,
+    boot_mode_user => 0xFF5E_0200,
+    boot_mode => 0xFF5E_0204,
+    reset_ctrl => 0xFF5E_0218,
+    reset_reason => 0xFF5E_0220,
+    gem_rst_ctrl => 0xFF5E_0230,
+    peri_rst_ctrl => 0xFF5E_0238,
+    rst_lpd_top => 0xFF5E_023C,
+    rst_lpd_dbg => 0xFF5E_0240,
+    boot_pin_ctrl => 0xFF5E_0250,
+    bank3_drive0 => 0xFF5E_0270,
+    bank3_status => 0xFF5E_0288,
+});
+assert_layout!(Chkr, 0xFF5E_0160, {
+    clka_upper => 0xFF5E_0160,
+    clka_lower => 0xFF5E_0164,
+    clkb_cnt => 0xFF5E_0168,
+    ctrl => 0xFF5E_016C,
+});
+const _: () = assert!(core::mem::size_of::<Chkr>() == 0x10);
+
+/// Clock checker: counts clock A for CLKB_CNT cycles of clock B and flags a count outside
+/// [CLKA_LOWER, CLKA_UPPER]
+#[repr(C)]
+pub struct Chkr {
+    pub clka_upper: RW<u32>,
+    pub clka_lower: RW<u32>,
+    pub clkb_cnt: RW<u32>,
+    pub ctrl: ChkrCtrl,
+}
+
+static WPROT_STATE: WProtState = WProtState::new();
 
 impl Unlocked for RegisterBlock {
-    fn unlocked<F: FnMut(&mut Self) -> R, R>(mut f: F) -> R {
-        let mut self_ = Self::crl_apb();
-        self_.crl_wprot.write(WProt::zeroed().active(false));
-        let r = f(&mut self_);
-        self_.crl_wprot.write(WProt::zeroed().active(true));
-        r
+    fn regs() -> &'static mut Self {
+        Self::crl_apb()
+    }
+
+    fn wprot(&mut self) -> Option<&mut WProt> {
+        Some(&mut self.crl_wprot)
+    }
+
+    fn wprot_state() -> &'static WProtState {
+        &WPROT_STATE
     }
 }
 
@@ -257,10 +295,60 @@ register_bit!(pl_thr_ctrl, cnt_rst, 0);
 register!(pl_thr_cnt, PlThrCnt, RW, u32);
 register_bits!(pl_thr_cnt, last_cnt, u16, 0, 15);
 
+/// Clock checker clock A (monitored clock) selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ChkrClkASource {
+    RpuPll = 0b000,
+    ApuPll = 0b001,
+    DdrPll = 0b010,
+    VideoPll = 0b011,
+    IoPll = 0b100,
+}
+
+/// Clock checker clock B (reference clock) selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ChkrClkBSource {
+    PsRefClk = 0b0,
+    AltRefClk = 0b1,
+}
+
+register!(chkr_ctrl, ChkrCtrl, RW, u32);
+register_bit!(chkr_ctrl, start_single_shot, 6);
+register_bit!(chkr_ctrl, start_continuous, 5);
+register_bits_typed!(chkr_ctrl, clkb_mux_ctrl, u8, ChkrClkBSource, 4, 4);
+register_bits_typed!(chkr_ctrl, clka_mux_ctrl, u8, ChkrClkASource, 1, 3);
+register_bit!(chkr_ctrl, enable, 0);
+
+// One bit per checker in each field
+macro_rules! clkmon_reg {
+    ($mod_name: ident, $struct_name: ident, $access: ident) => {
+        register!($mod_name, $struct_name, $access, u32);
+        register_bits!($mod_name, cnta_overflow, u8, 16, 23);
+        register_bits!($mod_name, clka_max, u8, 8, 15);
+        register_bits!($mod_name, clka_min, u8, 0, 7);
+    };
+}
+
+// todo: status bits are WTC
+clkmon_reg!(clkmon_status, ClkmonStatus, RW);
+clkmon_reg!(clkmon_mask, ClkmonMask, RO);
+clkmon_reg!(clkmon_enable, ClkmonEnable, WO);
+clkmon_reg!(clkmon_disable, ClkmonDisable, WO);
+clkmon_reg!(clkmon_trigger, ClkmonTrigger, WO);
+
 register!(dll_clk_ctrl, DllClkCtrl, RW, u32);
 register_bits!(dll_clk_ctrl, srcsel, u8, 0, 2);
 
 // boot mode pin values read after POR and "triplicated for security"
+register!(boot_mode_user, BootModeUser, RW, u32);
+// boot mode used after the next soft reset if use_alt is set
+register_bits!(boot_mode_user, alt_boot_mode, u8, 12, 15);
+register_bit!(boot_mode_user, use_alt, 8);
+// boot mode in effect
+register_bits!(boot_mode_user, boot_mode, u8, 0, 3, RO);
+
 register!(boot_mode, BootMode, RO, u32);
 register_bits!(boot_mode, boot_mode2, u8, 8, 11);
 register_bits!(boot_mode, boot_mode1, u8, 4, 7);
@@ -269,6 +357,15 @@ register_bits!(boot_mode, boot_mode0, u8, 0, 3);
 register!(reset_ctrl, ResetCtrl, RW, u32);
 register_bit!(reset_ctrl, soft_reset, 4);
 
+register!(reset_reason, ResetReason, RW, u32);
+register_bit!(reset_reason, debug_sys, 6, WTC);
+register_bit!(reset_reason, soft, 5, WTC);
+register_bit!(reset_reason, srst, 4, WTC);
+register_bit!(reset_reason, psonly_reset_req, 3, WTC);
+register_bit!(reset_reason, pmu_sys_reset, 2, WTC);
+register_bit!(reset_reason, internal, 1, WTC);
+register_bit!(reset_reason, external, 0, WTC);
+
 register!(gem_rst_ctrl, GemRstCtrl, RW, u32);
 register_bit!(gem_rst_ctrl, gem3_rst, 3);
 register_bit!(gem_rst_ctrl, gem2_rst, 2);
@@ -0,0 +1,24 @@
+///! FPD NIC-400 interconnect programmer's view
+use libregister::register_at;
+use volatile_register::{RO, WO};
+
+// todo: per-interface QoS and issuing capability registers
+#[repr(C)]
+pub struct RegisterBlock {
+    pub remap: WO<u32>,
+    unused0: [u32; 2035],
+    pub pid4: RO<u32>,
+    pub pid5: RO<u32>,
+    pub pid6: RO<u32>,
+    pub pid7: RO<u32>,
+    pub pid0: RO<u32>,
+    pub pid1: RO<u32>,
+    pub pid2: RO<u32>,
+    pub pid3: RO<u32>,
+    pub cid: [RO<u32>; 4],
+}
+register_at!(RegisterBlock, 0xFD70_0000, fpd_gpv);
+assert_layout!(RegisterBlock, 0xFD70_0000, {
+    pid4 => 0xFD70_1FD0,
+    cid => 0xFD70_1FF0,
+});
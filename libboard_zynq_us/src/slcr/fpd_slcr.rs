@@ -0,0 +1,65 @@
+///! FPD SLCR (FPD interconnect and PL interface configuration)
+use libregister::{register, register_at, register_bits_typed};
+use volatile_register::{RO, RW, WO};
+
+use super::common::{Unlocked, WProt, WProtState};
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub wprot0: WProt,
+    pub ctrl: RW<u32>,
+    pub isr: RW<u32>, // todo: WTC LSB
+    pub imr: RO<u32>,
+    pub ier: WO<u32>,
+    pub idr: WO<u32>,
+    pub itr: WO<u32>,
+    unused0: [u32; 57],
+    pub wdt_clk_sel: RW<u32>,
+    pub int_fpd: RW<u32>,
+    unused1: [u32; 62],
+    pub gpu: RW<u32>,
+    unused2: [u32; 63],
+    pub gdma_cfg: RW<u32>,
+    unused3: [u32; 4927],
+    pub afi_fs: FpdAfiFs,
+}
+register_at!(RegisterBlock, 0xFD61_0000, fpd_slcr);
+assert_layout!(RegisterBlock, 0xFD61_0000, {
+    itr => 0xFD61_0018,
+    wdt_clk_sel => 0xFD61_0100,
+    int_fpd => 0xFD61_0104,
+    gpu => 0xFD61_0200,
+    gdma_cfg => 0xFD61_0300,
+    afi_fs => 0xFD61_5000,
+});
+
+static WPROT_STATE: WProtState = WProtState::new();
+
+impl Unlocked for RegisterBlock {
+    fn regs() -> &'static mut Self {
+        Self::fpd_slcr()
+    }
+
+    fn wprot(&mut self) -> Option<&mut WProt> {
+        Some(&mut self.wprot0)
+    }
+
+    fn wprot_state() -> &'static WProtState {
+        &WPROT_STATE
+    }
+}
+
+/// Data width of a PS-PL AXI master interface
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum AfiWidth {
+    W32 = 0b00,
+    W64 = 0b01,
+    W128 = 0b10,
+}
+
+register!(fpd_afi_fs, FpdAfiFs, RW, u32);
+// M_AXI_HPM1_FPD
+register_bits_typed!(fpd_afi_fs, dw_ss1_sel, u8, AfiWidth, 10, 11);
+// M_AXI_HPM0_FPD
+register_bits_typed!(fpd_afi_fs, dw_ss0_sel, u8, AfiWidth, 8, 9);
@@ -0,0 +1,37 @@
+///! FPD secure SLCR (TrustZone attributes of FPD masters)
+use libregister::{register, register_at, register_bit, register_bits};
+use volatile_register::{RO, RW, WO};
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub ctrl: RW<u32>,
+    pub isr: RW<u32>, // todo: WTC LSB
+    pub imr: RO<u32>,
+    pub ier: WO<u32>,
+    pub idr: WO<u32>,
+    pub itr: WO<u32>,
+    unused0: [u32; 2],
+    pub slcr_sata: RW<u32>,
+    unused1: [u32; 3],
+    pub slcr_pcie: RW<u32>,
+    unused2: [u32; 3],
+    pub slcr_dpdma: SlcrDpdma,
+    unused3: [u32; 3],
+    pub slcr_gdma: SlcrGdma,
+}
+register_at!(RegisterBlock, 0xFD69_0000, fpd_slcr_secure);
+assert_layout!(RegisterBlock, 0xFD69_0000, {
+    itr => 0xFD69_0014,
+    slcr_sata => 0xFD69_0020,
+    slcr_pcie => 0xFD69_0030,
+    slcr_dpdma => 0xFD69_0040,
+    slcr_gdma => 0xFD69_0050,
+});
+
+// 0 = secure, 1 = non-secure
+register!(slcr_dpdma, SlcrDpdma, RW, u32);
+register_bit!(slcr_dpdma, tz, 0);
+
+// one bit per channel
+register!(slcr_gdma, SlcrGdma, RW, u32);
+register_bits!(slcr_gdma, tz, u8, 0, 7);
@@ -0,0 +1,28 @@
+///! IOU system timestamp counter (secure), drives the generic timer count
+use libregister::{register, register_at, register_bit};
+use volatile_register::RW;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub counter_control: CounterControl,
+    pub counter_status: CounterStatus,
+    pub current_counter_value_lower: RW<u32>,
+    pub current_counter_value_upper: RW<u32>,
+    unused0: [u32; 4],
+    /// Counter frequency in Hz
+    pub base_frequency_id: RW<u32>,
+}
+register_at!(RegisterBlock, 0xFF26_0000, iou_scntrs);
+assert_layout!(RegisterBlock, 0xFF26_0000, {
+    counter_control => 0xFF26_0000,
+    current_counter_value_lower => 0xFF26_0008,
+    base_frequency_id => 0xFF26_0020,
+});
+
+register!(counter_control, CounterControl, RW, u32);
+// halt on debug
+register_bit!(counter_control, hdbg, 1);
+register_bit!(counter_control, en, 0);
+
+register!(counter_status, CounterStatus, RO, u32);
+register_bit!(counter_status, dbgh, 1, RO);
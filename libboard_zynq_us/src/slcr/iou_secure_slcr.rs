@@ -0,0 +1,14 @@
+///! IOU secure SLCR (AXI protection attributes of the IOU masters)
+use libregister::register_at;
+use volatile_register::RW;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub iou_axi_wprtcn: RW<u32>,
+    pub iou_axi_rprtcn: RW<u32>,
+}
+register_at!(RegisterBlock, 0xFF24_0000, iou_secure_slcr);
+assert_layout!(RegisterBlock, 0xFF24_0000, {
+    iou_axi_wprtcn => 0xFF24_0000,
+    iou_axi_rprtcn => 0xFF24_0004,
+});
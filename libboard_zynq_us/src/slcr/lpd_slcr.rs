@@ -0,0 +1,61 @@
+///! LPD SLCR (LPD interconnect, coherency and PL interface configuration)
+use libregister::{register, register_at, register_bits_typed};
+use volatile_register::{RO, RW, WO};
+
+use super::common::{Unlocked, WProt, WProtState};
+use super::fpd_slcr::AfiWidth;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub wprot0: WProt,
+    pub ctrl: RW<u32>,
+    pub isr: RW<u32>, // todo: WTC LSB
+    pub imr: RO<u32>,
+    pub ier: WO<u32>,
+    pub idr: WO<u32>,
+    pub itr: WO<u32>,
+    unused0: [u32; 1],
+    /// Survive all resets except POR
+    pub persistent: [RW<u32>; 8],
+    pub safety_chk: [RW<u32>; 4],
+    unused1: [u32; 9196],
+    pub afi_fs: LpdAfiFs,
+    unused2: [u32; 1023],
+    pub lpd_cci: RW<u32>,
+    pub lpd_cci_addrmap: RW<u32>,
+    pub lpd_cci_qvnprealloc: RW<u32>,
+    unused3: [u32; 5],
+    pub lpd_smmu: RW<u32>,
+    unused4: [u32; 7],
+    pub lpd_apu: RW<u32>,
+}
+register_at!(RegisterBlock, 0xFF41_0000, lpd_slcr);
+assert_layout!(RegisterBlock, 0xFF41_0000, {
+    itr => 0xFF41_0018,
+    persistent => 0xFF41_0020,
+    safety_chk => 0xFF41_0040,
+    afi_fs => 0xFF41_9000,
+    lpd_cci => 0xFF41_A000,
+    lpd_smmu => 0xFF41_A020,
+    lpd_apu => 0xFF41_A040,
+});
+
+static WPROT_STATE: WProtState = WProtState::new();
+
+impl Unlocked for RegisterBlock {
+    fn regs() -> &'static mut Self {
+        Self::lpd_slcr()
+    }
+
+    fn wprot(&mut self) -> Option<&mut WProt> {
+        Some(&mut self.wprot0)
+    }
+
+    fn wprot_state() -> &'static WProtState {
+        &WPROT_STATE
+    }
+}
+
+register!(lpd_afi_fs, LpdAfiFs, RW, u32);
+// M_AXI_HPM0_LPD
+register_bits_typed!(lpd_afi_fs, dw_ss2_sel, u8, AfiWidth, 8, 9);
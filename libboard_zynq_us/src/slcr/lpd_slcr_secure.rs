@@ -0,0 +1,42 @@
+///! LPD secure SLCR (TrustZone attributes of LPD masters)
+use libregister::{register, register_at, register_bit, register_bits};
+use volatile_register::{RO, RW, WO};
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub ctrl: RW<u32>,
+    pub isr: RW<u32>, // todo: WTC LSB
+    pub imr: RO<u32>,
+    pub ier: WO<u32>,
+    pub idr: WO<u32>,
+    pub itr: WO<u32>,
+    unused0: [u32; 2],
+    pub slcr_rpu: SlcrRpu,
+    unused1: [u32; 3],
+    pub slcr_adma: SlcrAdma,
+    unused2: [u32; 3],
+    pub safety_chk: RW<u32>,
+    unused3: [u32; 3],
+    pub slcr_usb: SlcrUsb,
+}
+register_at!(RegisterBlock, 0xFF4B_0000, lpd_slcr_secure);
+assert_layout!(RegisterBlock, 0xFF4B_0000, {
+    itr => 0xFF4B_0014,
+    slcr_rpu => 0xFF4B_0020,
+    slcr_adma => 0xFF4B_0030,
+    safety_chk => 0xFF4B_0040,
+    slcr_usb => 0xFF4B_0050,
+});
+
+// 0 = secure, 1 = non-secure
+register!(slcr_rpu, SlcrRpu, RW, u32);
+register_bit!(slcr_rpu, tz_r5_1, 1);
+register_bit!(slcr_rpu, tz_r5_0, 0);
+
+// one bit per channel
+register!(slcr_adma, SlcrAdma, RW, u32);
+register_bits!(slcr_adma, tz, u8, 0, 7);
+
+register!(slcr_usb, SlcrUsb, RW, u32);
+register_bit!(slcr_usb, tz_usb3_1, 1);
+register_bit!(slcr_usb, tz_usb3_0, 0);
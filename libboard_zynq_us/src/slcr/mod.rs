@@ -1,15 +1,16 @@
 ///! Register definitions for UltraScale+ System Level Control
+pub mod apu;
+pub mod cci_gpv;
 pub mod common;
 pub mod crf_apb;
-// APU
-// FPD_SLCR
-// FPD_SLCR_SECURE
-pub mod iou_slcr;
-// IOU_SECURE_SLCR
-// IOU_SCNTRS
-// LPD_SLCR
-// LPD_SLCR_SECURE
 pub mod crl_apb;
-// RPU
-// CCI_GPV
-// FPD_GPV
+pub mod fpd_gpv;
+pub mod fpd_slcr;
+pub mod fpd_slcr_secure;
+pub mod iou_scntrs;
+pub mod iou_secure_slcr;
+pub mod iou_slcr;
+pub mod lpd_slcr;
+pub mod lpd_slcr_secure;
+pub mod pmu_global;
+pub mod rpu;
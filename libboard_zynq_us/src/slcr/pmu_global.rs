@@ -0,0 +1,22 @@
+///! PMU global registers (power state of the PS islands)
+use libregister::{register, register_at, register_bits};
+use volatile_register::RW;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub global_cntl: RW<u32>,
+    pub ps_cntl: RW<u32>,
+    pub apu_pwr_status_init: RW<u32>,
+    unused0: [u32; 61],
+    pub pwr_state: PwrState,
+}
+register_at!(RegisterBlock, 0xFFD8_0000, pmu_global);
+assert_layout!(RegisterBlock, 0xFFD8_0000, {
+    global_cntl => 0xFFD8_0000,
+    apu_pwr_status_init => 0xFFD8_0008,
+    pwr_state => 0xFFD8_0100,
+});
+
+register!(pwr_state, PwrState, RO, u32);
+// 1 = powered up, one bit per APU core
+register_bits!(pwr_state, acpu, u8, 0, 3, RO);
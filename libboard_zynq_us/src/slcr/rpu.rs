@@ -0,0 +1,49 @@
+///! RPU control (lock-step/split mode, TCMs and per-core configuration)
+use libregister::{register, register_at, register_bit};
+use volatile_register::{RO, RW};
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub rpu_glbl_cntl: RpuGlblCntl,
+    pub rpu_glbl_status: RO<u32>,
+    pub rpu_err_cntl: RW<u32>,
+    unused0: [u32; 61],
+    pub rpu_core: [RpuCore; 2],
+}
+register_at!(RegisterBlock, 0xFF9A_0000, rpu);
+assert_layout!(RegisterBlock, 0xFF9A_0000, {
+    rpu_glbl_cntl => 0xFF9A_0000,
+    rpu_err_cntl => 0xFF9A_0008,
+    rpu_core => 0xFF9A_0100,
+});
+// RPU1_CFG at 0x200
+const _: () = assert!(core::mem::size_of::<RpuCore>() == 0x100);
+
+#[repr(C)]
+pub struct RpuCore {
+    pub cfg: RpuCfg,
+    pub status: RO<u32>,
+    unused: [u32; 62],
+}
+
+register!(rpu_glbl_cntl, RpuGlblCntl, RW, u32);
+register_bit!(rpu_glbl_cntl, gic_axprot, 10);
+register_bit!(rpu_glbl_cntl, tcm_clk_cntl, 8);
+register_bit!(rpu_glbl_cntl, tcm_wait, 7);
+// combine TCMs in lock-step mode
+register_bit!(rpu_glbl_cntl, tcm_comb, 6);
+register_bit!(rpu_glbl_cntl, teinit, 5);
+register_bit!(rpu_glbl_cntl, slclamp, 4);
+// 0 = lock-step, 1 = split
+register_bit!(rpu_glbl_cntl, slsplit, 3);
+register_bit!(rpu_glbl_cntl, dbgnoclkstop, 2);
+register_bit!(rpu_glbl_cntl, cfgie, 1);
+register_bit!(rpu_glbl_cntl, cfgee, 0);
+
+register!(rpu_cfg, RpuCfg, RW, u32);
+register_bit!(rpu_cfg, cfgnmfi, 3);
+// high (0xFFFF_0000) or low (0x0) exception vectors
+register_bit!(rpu_cfg, vinithi, 2);
+register_bit!(rpu_cfg, coherent, 1);
+// 0 = halted
+register_bit!(rpu_cfg, ncpuhalt, 0);
@@ -1,5 +1,6 @@
 use core::ops::{Deref, DerefMut};
 
+use crate::board::{Board, Target, UartPort};
 use crate::uart::Uart;
 
 const UART_RATE: u32 = 115_200;
@@ -43,8 +44,10 @@ impl DerefMut for LazyUart {
     fn deref_mut(&mut self) -> &mut Uart {
         match self {
             LazyUart::Uninitialized => {
-                #[cfg(feature = "target_zcu111")]
-                let uart = Uart::uart0(UART_RATE);
+                let uart = match Target::CONSOLE_UART {
+                    UartPort::Uart0 => Uart::uart0(UART_RATE),
+                    UartPort::Uart1 => Uart::uart1(UART_RATE),
+                };
                 *self = LazyUart::Initialized(uart);
                 self
             }
@@ -0,0 +1,98 @@
+//! Mutual exclusion between the APU cores
+//!
+//! Exclusive loads and stores may fail indefinitely while the MMU is off and all memory is
+//! Device memory, so atomic read-modify-write operations (and locks built on them) can't be used
+//! before the MMU is set up. [BakeryLock] only needs ordered loads and stores, which work on any
+//! memory type.
+#[cfg(target_arch = "aarch64")]
+use core::arch::asm;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::apu::{Core, NUM_CORES};
+
+// initial value of the per-core lock state
+#[allow(clippy::declare_interior_mutable_const)]
+const NOT_CHOOSING: AtomicBool = AtomicBool::new(false);
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_TICKET: AtomicU32 = AtomicU32::new(0);
+
+/// Lamport's bakery lock for the four A53 cores
+///
+/// A core takes a ticket one higher than every ticket it sees and waits for all cores holding
+/// lower tickets (or equal tickets and lower core numbers). Interrupts are masked while the
+/// lock is held, so a handler on the same core can't deadlock against it.
+pub struct BakeryLock {
+    choosing: [AtomicBool; NUM_CORES],
+    ticket: [AtomicU32; NUM_CORES],
+}
+
+impl Default for BakeryLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BakeryLock {
+    pub const fn new() -> Self {
+        BakeryLock {
+            choosing: [NOT_CHOOSING; NUM_CORES],
+            ticket: [NO_TICKET; NUM_CORES],
+        }
+    }
+
+    /// Run `f` with the lock held and interrupts masked
+    pub fn lock<F: FnOnce() -> R, R>(&self, f: F) -> R {
+        let daif = mask_interrupts();
+        let me = Core::current() as usize;
+
+        self.choosing[me].store(true, Ordering::SeqCst);
+        let ticket = 1 + self
+            .ticket
+            .iter()
+            .map(|ticket| ticket.load(Ordering::SeqCst))
+            .max()
+            .unwrap_or(0);
+        self.ticket[me].store(ticket, Ordering::SeqCst);
+        self.choosing[me].store(false, Ordering::SeqCst);
+
+        for other in (0..NUM_CORES).filter(|&other| other != me) {
+            while self.choosing[other].load(Ordering::SeqCst) {
+                core::hint::spin_loop();
+            }
+            loop {
+                let other_ticket = self.ticket[other].load(Ordering::SeqCst);
+                if other_ticket == 0 || (other_ticket, other) > (ticket, me) {
+                    break;
+                }
+                core::hint::spin_loop();
+            }
+        }
+
+        let r = f();
+        self.ticket[me].store(0, Ordering::SeqCst);
+        restore_interrupts(daif);
+        r
+    }
+}
+
+/// Mask IRQ and FIQ, returning the previous DAIF value
+#[cfg(target_arch = "aarch64")]
+fn mask_interrupts() -> u64 {
+    let daif: u64;
+    unsafe { asm!("mrs {}, DAIF", "msr DAIFSet, #0b0011", out(reg) daif) };
+    daif
+}
+
+#[cfg(target_arch = "aarch64")]
+fn restore_interrupts(daif: u64) {
+    unsafe { asm!("msr DAIF, {}", in(reg) daif) };
+}
+
+// host builds (unit tests) have no interrupts to mask
+#[cfg(not(target_arch = "aarch64"))]
+fn mask_interrupts() -> u64 {
+    0
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn restore_interrupts(_daif: u64) {}
@@ -0,0 +1,125 @@
+//! System reset, reset reason and boot mode
+use libregister::{RegisterR, RegisterRW, RegisterW};
+
+use crate::slcr::{common::Unlocked, crl_apb};
+
+/// Cause of the last reset, see UG1085 Ch. 38
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResetReason {
+    /// External (PS_POR_B) or internal power-on reset
+    Por,
+    /// External system reset (PS_SRST_B)
+    Srst,
+    /// System reset initiated by the PMU, e.g. on a watchdog timeout
+    Watchdog,
+    /// System reset requested by a debugger
+    Debugger,
+    /// System reset from [soft_reset]
+    Software,
+    /// PS-only reset
+    PsOnly,
+}
+
+/// Boot mode pin settings (UG1085 Table 11-1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum BootMode {
+    PsJtag = 0x0,
+    Qspi24 = 0x1,
+    Qspi32 = 0x2,
+    Sd0 = 0x3,
+    Nand = 0x4,
+    Sd1 = 0x5,
+    Emmc = 0x6,
+    Usb0 = 0x7,
+    PjtagMio0 = 0x8,
+    PjtagMio1 = 0x9,
+    /// SD1 with level shifter
+    Sd1Ls = 0xE,
+}
+
+impl BootMode {
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x0 => Some(BootMode::PsJtag),
+            0x1 => Some(BootMode::Qspi24),
+            0x2 => Some(BootMode::Qspi32),
+            0x3 => Some(BootMode::Sd0),
+            0x4 => Some(BootMode::Nand),
+            0x5 => Some(BootMode::Sd1),
+            0x6 => Some(BootMode::Emmc),
+            0x7 => Some(BootMode::Usb0),
+            0x8 => Some(BootMode::PjtagMio0),
+            0x9 => Some(BootMode::PjtagMio1),
+            0xE => Some(BootMode::Sd1Ls),
+            _ => None,
+        }
+    }
+}
+
+/// Reset the whole system (PS and PL)
+pub fn soft_reset() -> ! {
+    crl_apb::RegisterBlock::unlocked(|crl_apb| {
+        crl_apb.reset_ctrl.modify(|_, w| w.soft_reset(true))
+    });
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+/// Read and clear the reset reason
+///
+/// Reason bits accumulate until cleared, so more than one may be set; the one with the widest
+/// scope is reported. Returns `None` if they have already been cleared.
+pub fn reset_reason() -> Option<ResetReason> {
+    let reason = crl_apb::RegisterBlock::crl_apb().reset_reason.read();
+    let decoded = if reason.external() || reason.internal() {
+        Some(ResetReason::Por)
+    } else if reason.srst() {
+        Some(ResetReason::Srst)
+    } else if reason.pmu_sys_reset() {
+        Some(ResetReason::Watchdog)
+    } else if reason.debug_sys() {
+        Some(ResetReason::Debugger)
+    } else if reason.soft() {
+        Some(ResetReason::Software)
+    } else if reason.psonly_reset_req() {
+        Some(ResetReason::PsOnly)
+    } else {
+        None
+    };
+    crl_apb::RegisterBlock::unlocked(|crl_apb| {
+        crl_apb.reset_reason.write(
+            crl_apb::ResetReason::zeroed()
+                .debug_sys()
+                .soft()
+                .srst()
+                .psonly_reset_req()
+                .pmu_sys_reset()
+                .internal()
+                .external(),
+        )
+    });
+    decoded
+}
+
+/// Boot mode in effect, `None` for a reserved setting
+pub fn boot_mode() -> Option<BootMode> {
+    BootMode::from_bits(
+        crl_apb::RegisterBlock::crl_apb()
+            .boot_mode_user
+            .read()
+            .boot_mode(),
+    )
+}
+
+/// Boot from `mode` after the next soft reset instead of the boot mode pins,
+/// or go back to the pins with `None`
+pub fn set_next_boot_mode(mode: Option<BootMode>) {
+    crl_apb::RegisterBlock::unlocked(|crl_apb| {
+        crl_apb.boot_mode_user.modify(|_, w| match mode {
+            Some(mode) => w.alt_boot_mode(mode as u8).use_alt(true),
+            None => w.alt_boot_mode(0).use_alt(false),
+        })
+    });
+}
@@ -0,0 +1,364 @@
+//! Triple timer counters (TTC0-3), each with three 32-bit counters
+//!
+//! Counters run from the TTC reference clock selected in IOU_SLCR (LPD_LSBUS by default) or
+//! from their external clock input. The waveform output changes level on match 1, which is used
+//! for PWM. Every counter's clock input and waveform output reach the PL through EMIO; those of
+//! the first counter of each TTC can also be routed to MIO. See UG1085 Ch. 14.
+use libregister::{RegisterR, RegisterRW, RegisterW};
+
+use crate::board::{Board, Target};
+use crate::clocks::Clocks;
+use crate::interrupts::{self, irq, Trigger};
+use crate::mio::{Mio, MioConfig, MioError};
+use crate::reset::Reset;
+use crate::slcr::{
+    common::Unlocked,
+    iou_slcr::{self, TtcClkSource},
+};
+
+mod regs;
+
+pub const NUM_COUNTERS: usize = 3;
+// prescaler divides by 2^(ps_v + 1)
+const PRESCALE_MAX: u8 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TtcError {
+    /// The frequency (Hz) can't be reached from the reference clock
+    FreqOutOfRange(u32),
+    /// Duty cycle above 100 %
+    InvalidDuty(u8),
+    /// Only the first counter of each TTC has MIO pins
+    NoMioPins,
+    Mio(MioError),
+}
+
+impl From<MioError> for TtcError {
+    fn from(error: MioError) -> Self {
+        TtcError::Mio(error)
+    }
+}
+
+/// Counter interrupt sources
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Interrupts {
+    pub interval: bool,
+    pub match1: bool,
+    pub match2: bool,
+    pub match3: bool,
+    pub overflow: bool,
+    pub event_overflow: bool,
+}
+
+pub struct Ttc {
+    index: usize,
+}
+
+impl Ttc {
+    pub fn ttc0() -> Self {
+        Reset::Ttc0.pulse();
+        Ttc { index: 0 }
+    }
+
+    pub fn ttc1() -> Self {
+        Reset::Ttc1.pulse();
+        Ttc { index: 1 }
+    }
+
+    pub fn ttc2() -> Self {
+        Reset::Ttc2.pulse();
+        Ttc { index: 2 }
+    }
+
+    pub fn ttc3() -> Self {
+        Reset::Ttc3.pulse();
+        Ttc { index: 3 }
+    }
+
+    /// Select the reference clock of all three counters
+    pub fn set_clock_source(&mut self, source: TtcClkSource) {
+        iou_slcr::RegisterBlock::unlocked(|iou_slcr| {
+            iou_slcr.iou_ttc_apb_clk.modify(|_, w| match self.index {
+                0 => w.ttc0_sel(source),
+                1 => w.ttc1_sel(source),
+                2 => w.ttc2_sel(source),
+                _ => w.ttc3_sel(source),
+            })
+        });
+    }
+
+    pub fn split(self) -> (Counter, Counter, Counter) {
+        (
+            Counter::new(self.index, 0),
+            Counter::new(self.index, 1),
+            Counter::new(self.index, 2),
+        )
+    }
+}
+
+pub struct Counter {
+    ttc: usize,
+    index: usize,
+}
+
+impl Counter {
+    fn new(ttc: usize, index: usize) -> Self {
+        Counter { ttc, index }
+    }
+
+    fn regs(&self) -> &'static mut regs::RegisterBlock {
+        match self.ttc {
+            0 => regs::RegisterBlock::ttc0(),
+            1 => regs::RegisterBlock::ttc1(),
+            2 => regs::RegisterBlock::ttc2(),
+            _ => regs::RegisterBlock::ttc3(),
+        }
+    }
+
+    fn irq(&self) -> u32 {
+        let first = match self.ttc {
+            0 => irq::TTC0,
+            1 => irq::TTC1,
+            2 => irq::TTC2,
+            _ => irq::TTC3,
+        };
+        first + self.index as u32
+    }
+
+    /// MIO functions of the clock input and the waveform output
+    fn mio_functions(&self) -> Result<(Mio, Mio), TtcError> {
+        if self.index != 0 {
+            return Err(TtcError::NoMioPins);
+        }
+        Ok(match self.ttc {
+            0 => (Mio::Ttc0Clk, Mio::Ttc0Wave),
+            1 => (Mio::Ttc1Clk, Mio::Ttc1Wave),
+            2 => (Mio::Ttc2Clk, Mio::Ttc2Wave),
+            _ => (Mio::Ttc3Clk, Mio::Ttc3Wave),
+        })
+    }
+
+    /// Route the waveform output (e.g. for [start_pwm](Self::start_pwm)) to MIO `pin` in
+    /// `config`, which takes effect once the config is applied
+    pub fn route_wave_out(&self, config: &mut MioConfig, pin: u8) -> Result<(), TtcError> {
+        config.pin(pin, self.mio_functions()?.1)?;
+        Ok(())
+    }
+
+    /// Route MIO `pin` to the external clock input in `config`, which takes effect once the
+    /// config is applied
+    pub fn route_clk_in(&self, config: &mut MioConfig, pin: u8) -> Result<(), TtcError> {
+        config.pin(pin, self.mio_functions()?.0)?;
+        Ok(())
+    }
+
+    /// Reference clock frequency before the prescaler
+    pub fn ref_clk(&self) -> u32 {
+        let sel = iou_slcr::RegisterBlock::iou_slcr().iou_ttc_apb_clk.read();
+        let source = match self.ttc {
+            0 => sel.ttc0_sel(),
+            1 => sel.ttc1_sel(),
+            2 => sel.ttc2_sel(),
+            _ => sel.ttc3_sel(),
+        };
+        match source {
+            TtcClkSource::LpdLsbus => Clocks::get().lpd_lsbus_clk(),
+            TtcClkSource::PsRefClk => Target::REF_CLOCKS.ps_ref_clk,
+            TtcClkSource::Rpu => Clocks::get().rpu_clk(),
+        }
+    }
+
+    /// Divide the reference clock by 2^(`prescale` + 1), or not at all with `None`
+    pub fn set_prescaler(&mut self, prescale: Option<u8>) {
+        let clock_control = regs::ClockControl::zeroed();
+        self.regs().clock_control[self.index].write(match prescale {
+            Some(prescale) => {
+                assert!(prescale <= PRESCALE_MAX);
+                clock_control.ps_v(prescale).ps_en(true)
+            }
+            None => clock_control,
+        });
+    }
+
+    /// Pick the smallest prescaler that fits one period of `freq` into the counter and
+    /// return the period in counter ticks
+    fn set_period(&mut self, freq: u32) -> Result<u32, TtcError> {
+        let ref_clk = u64::from(self.ref_clk());
+        let freq_64 = u64::from(freq.max(1));
+        let mut prescale = None;
+        loop {
+            let divisor = prescale.map_or(1, |ps: u8| 2u64 << ps);
+            let ticks = ref_clk / divisor / freq_64;
+            if freq == 0 || ticks < 2 {
+                return Err(TtcError::FreqOutOfRange(freq));
+            }
+            if ticks <= u64::from(u32::MAX) {
+                self.set_prescaler(prescale);
+                unsafe { self.regs().interval_counter[self.index].write(ticks as u32 - 1) };
+                return Ok(ticks as u32);
+            }
+            prescale = match prescale {
+                None => Some(0),
+                Some(PRESCALE_MAX) => return Err(TtcError::FreqOutOfRange(freq)),
+                Some(ps) => Some(ps + 1),
+            };
+        }
+    }
+
+    /// Counter clock after the prescaler
+    fn counter_clk(&self) -> u32 {
+        let clock_control = self.regs().clock_control[self.index].read();
+        if clock_control.ps_en() {
+            self.ref_clk() >> (clock_control.ps_v() + 1)
+        } else {
+            self.ref_clk()
+        }
+    }
+
+    /// Count up to the interval and restart, raising the interval interrupt at `freq` (Hz).
+    /// Returns the frequency actually set.
+    pub fn start_interval(&mut self, freq: u32) -> Result<u32, TtcError> {
+        self.stop();
+        let ticks = self.set_period(freq)?;
+        self.regs().counter_control[self.index].write(
+            regs::CounterControl::zeroed()
+                .wave_en_n(true)
+                .int(true)
+                .rst(true),
+        );
+        Ok(self.counter_clk() / ticks)
+    }
+
+    /// Free-running count over the full 32-bit range, raising the overflow interrupt at
+    /// each wrap
+    pub fn start_overflow(&mut self, prescale: Option<u8>) {
+        self.stop();
+        self.set_prescaler(prescale);
+        self.regs().counter_control[self.index]
+            .write(regs::CounterControl::zeroed().wave_en_n(true).rst(true));
+    }
+
+    /// Output a PWM waveform at `freq` (Hz), high for `duty_percent` of each period.
+    /// Returns the frequency actually set.
+    pub fn start_pwm(&mut self, freq: u32, duty_percent: u8) -> Result<u32, TtcError> {
+        if duty_percent > 100 {
+            return Err(TtcError::InvalidDuty(duty_percent));
+        }
+        self.stop();
+        let ticks = self.set_period(freq)?;
+        self.set_match(ticks, duty_percent);
+        // wave_pol = 0: high until match 1, low after
+        self.regs().counter_control[self.index].write(
+            regs::CounterControl::zeroed()
+                .wave_en_n(false)
+                .match_en(true)
+                .int(true)
+                .rst(true),
+        );
+        Ok(self.counter_clk() / ticks)
+    }
+
+    /// Change the duty cycle of a running PWM output
+    pub fn set_duty(&mut self, duty_percent: u8) -> Result<(), TtcError> {
+        if duty_percent > 100 {
+            return Err(TtcError::InvalidDuty(duty_percent));
+        }
+        let ticks = self.regs().interval_counter[self.index].read() + 1;
+        self.set_match(ticks, duty_percent);
+        Ok(())
+    }
+
+    fn set_match(&mut self, ticks: u32, duty_percent: u8) {
+        let high = (u64::from(ticks) * u64::from(duty_percent) / 100) as u32;
+        unsafe { self.regs().match_1[self.index].write(high) };
+    }
+
+    /// Set match register 1-3 for the match interrupts; also enables match mode
+    pub fn set_match_value(&mut self, register: usize, value: u32) {
+        let regs = self.regs();
+        let reg = match register {
+            1 => &mut regs.match_1[self.index],
+            2 => &mut regs.match_2[self.index],
+            3 => &mut regs.match_3[self.index],
+            _ => panic!("TTC match registers are numbered 1-3"),
+        };
+        unsafe { reg.write(value) };
+        regs.counter_control[self.index].modify(|_, w| w.match_en(true));
+    }
+
+    /// Count edges of the external clock input instead of the reference clock
+    pub fn start_event_count(&mut self, falling_edge: bool) {
+        self.stop();
+        self.regs().clock_control[self.index]
+            .write(regs::ClockControl::zeroed().c_src(true).ex_e(falling_edge));
+        self.regs().counter_control[self.index]
+            .write(regs::CounterControl::zeroed().wave_en_n(true).rst(true));
+    }
+
+    /// Time high (or `low`) pulses on the external clock input in reference clock cycles,
+    /// read back with [pulse_width](Self::pulse_width)
+    pub fn start_capture(&mut self, low: bool) {
+        self.regs().event_control_timer[self.index]
+            .write(regs::EventControlTimer::zeroed().e_lo(low).e_en(true));
+    }
+
+    pub fn stop_capture(&mut self) {
+        self.regs().event_control_timer[self.index].write(regs::EventControlTimer::zeroed());
+    }
+
+    /// Width of the last captured pulse in reference clock cycles
+    pub fn pulse_width(&self) -> u32 {
+        self.regs().event_register[self.index].read()
+    }
+
+    pub fn value(&self) -> u32 {
+        self.regs().counter_value[self.index].read()
+    }
+
+    pub fn stop(&mut self) {
+        self.regs().counter_control[self.index].modify(|_, w| w.dis(true));
+    }
+
+    pub fn resume(&mut self) {
+        self.regs().counter_control[self.index].modify(|_, w| w.dis(false));
+    }
+
+    /// Restart from zero
+    pub fn restart(&mut self) {
+        self.regs().counter_control[self.index].modify(|_, w| w.rst(true));
+    }
+
+    pub fn set_interrupts(&mut self, interrupts: Interrupts) {
+        self.regs().interrupt_enable[self.index].write(
+            regs::InterruptEnable::zeroed()
+                .ev(interrupts.event_overflow)
+                .ov(interrupts.overflow)
+                .m3(interrupts.match3)
+                .m2(interrupts.match2)
+                .m1(interrupts.match1)
+                .iv(interrupts.interval),
+        );
+    }
+
+    /// Read and clear the interrupt flags
+    pub fn take_interrupts(&mut self) -> Interrupts {
+        let status = self.regs().interrupt_register[self.index].read();
+        Interrupts {
+            interval: status.iv(),
+            match1: status.m1(),
+            match2: status.m2(),
+            match3: status.m3(),
+            overflow: status.ov(),
+            event_overflow: status.ev(),
+        }
+    }
+
+    /// Route this counter's interrupt to the CPUs in `cpu_targets` at `priority`
+    pub fn enable_gic_interrupt(&mut self, priority: u8, cpu_targets: u8) {
+        interrupts::enable_spi(self.irq(), Trigger::Level, priority, cpu_targets);
+    }
+
+    pub fn disable_gic_interrupt(&mut self) {
+        interrupts::disable_spi(self.irq());
+    }
+}
@@ -0,0 +1,83 @@
+//! TTC registers, see UG1087 TTC
+use libregister::{register, register_at, register_bit, register_bits};
+use volatile_register::{RO, RW};
+
+use super::NUM_COUNTERS;
+
+#[repr(C)]
+pub struct RegisterBlock {
+    pub clock_control: [ClockControl; NUM_COUNTERS],
+    pub counter_control: [CounterControl; NUM_COUNTERS],
+    pub counter_value: [RO<u32>; NUM_COUNTERS],
+    pub interval_counter: [RW<u32>; NUM_COUNTERS],
+    pub match_1: [RW<u32>; NUM_COUNTERS],
+    pub match_2: [RW<u32>; NUM_COUNTERS],
+    pub match_3: [RW<u32>; NUM_COUNTERS],
+    /// Cleared on read
+    pub interrupt_register: [InterruptRegister; NUM_COUNTERS],
+    pub interrupt_enable: [InterruptEnable; NUM_COUNTERS],
+    pub event_control_timer: [EventControlTimer; NUM_COUNTERS],
+    /// Width of the last external pulse in LPD_LSBUS clock cycles
+    pub event_register: [RO<u32>; NUM_COUNTERS],
+}
+register_at!(RegisterBlock, 0xFF11_0000, ttc0);
+register_at!(RegisterBlock, 0xFF12_0000, ttc1);
+register_at!(RegisterBlock, 0xFF13_0000, ttc2);
+register_at!(RegisterBlock, 0xFF14_0000, ttc3);
+assert_layout!(RegisterBlock, 0xFF11_0000, {
+    clock_control => 0xFF11_0000,
+    counter_control => 0xFF11_000C,
+    counter_value => 0xFF11_0018,
+    interval_counter => 0xFF11_0024,
+    match_1 => 0xFF11_0030,
+    match_2 => 0xFF11_003C,
+    match_3 => 0xFF11_0048,
+    interrupt_register => 0xFF11_0054,
+    interrupt_enable => 0xFF11_0060,
+    event_control_timer => 0xFF11_006C,
+    event_register => 0xFF11_0078,
+});
+
+register!(clock_control, ClockControl, RW, u32);
+// external clock edge: 0 = rising, 1 = falling
+register_bit!(clock_control, ex_e, 6);
+// 0 = reference clock, 1 = external clock input
+register_bit!(clock_control, c_src, 5);
+// divide by 2^(ps_v + 1)
+register_bits!(clock_control, ps_v, u8, 1, 4);
+register_bit!(clock_control, ps_en, 0);
+
+register!(counter_control, CounterControl, RW, u32);
+// 0 = waveform goes low on match 1
+register_bit!(counter_control, wave_pol, 6);
+// active low
+register_bit!(counter_control, wave_en_n, 5);
+// self-clearing
+register_bit!(counter_control, rst, 4);
+register_bit!(counter_control, match_en, 3);
+register_bit!(counter_control, decr, 2);
+// 0 = overflow mode, 1 = interval mode
+register_bit!(counter_control, int, 1);
+register_bit!(counter_control, dis, 0);
+
+macro_rules! interrupt_reg {
+    ($mod_name: ident, $struct_name: ident, $access: ident) => {
+        register!($mod_name, $struct_name, $access, u32);
+        register_bit!($mod_name, ev, 5);
+        register_bit!($mod_name, ov, 4);
+        register_bit!($mod_name, m3, 3);
+        register_bit!($mod_name, m2, 2);
+        register_bit!($mod_name, m1, 1);
+        register_bit!($mod_name, iv, 0);
+    };
+}
+
+interrupt_reg!(interrupt_register, InterruptRegister, RO);
+interrupt_reg!(interrupt_enable, InterruptEnable, RW);
+
+register!(event_control_timer, EventControlTimer, RW, u32);
+// 0 = stop at overflow, 1 = wrap around
+register_bit!(event_control_timer, e_ov, 2);
+// 0 = time high level, 1 = time low level
+register_bit!(event_control_timer, e_lo, 1);
+register_bit!(event_control_timer, e_en, 0);
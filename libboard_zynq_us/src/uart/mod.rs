@@ -9,45 +9,41 @@ use libregister::{RegisterR, RegisterRW, RegisterW};
 
 use self::regs::{BaudRateDiv, BaudRateGen};
 
-use super::clocks::Clocks;
-use super::slcr::{common::Unlocked, crl_apb};
+use super::clocks::{
+    gate::{ClockGate, GatedClock},
+    Clocks,
+};
+use super::reset::Reset;
 
 // mod baud_rate_gen;
 mod regs;
 
 pub struct Uart {
     regs: &'static mut regs::RegisterBlock,
+    _clock: ClockGate,
 }
 
 impl Uart {
-    #[cfg(feature = "target_zcu111")]
     pub fn uart0(baudrate: u32) -> Self {
-        crl_apb::RegisterBlock::unlocked(|slcr| {
-            // reset uart
-            slcr.peri_rst_ctrl.modify(|_, w| w.uart0_rst(true));
-            slcr.peri_rst_ctrl.modify(|_, w| w.uart0_rst(false));
-            slcr.uart0_clk_ctrl.modify(|_, w| w.clkact(true));
-        });
+        let clock = ClockGate::acquire(GatedClock::Uart0);
+        Reset::Uart0.pulse();
 
         let mut self_ = Uart {
             regs: regs::RegisterBlock::uart0(),
+            _clock: clock,
         };
         let ref_clk = Clocks::get().uart0_ref_clk();
         self_.configure(baudrate, ref_clk);
         self_
     }
 
-    #[cfg(not(feature = "target_zcu111"))]
     pub fn uart1(baudrate: u32) -> Self {
-        crl_apb::RegisterBlock::unlocked(|slcr| {
-            // reset uart
-            slcr.peri_rst_ctrl.modify(|_, w| w.uart1_rst(true));
-            slcr.peri_rst_ctrl.modify(|_, w| w.uart1_rst(false));
-            slcr.uart1_clk_ctrl.modify(|_, w| w.clkact(true));
-        });
+        let clock = ClockGate::acquire(GatedClock::Uart1);
+        Reset::Uart1.pulse();
 
         let mut self_ = Uart {
             regs: regs::RegisterBlock::uart1(),
+            _clock: clock,
         };
         let ref_clk = Clocks::get().uart1_ref_clk();
         self_.configure(baudrate, ref_clk);
@@ -66,6 +66,22 @@ pub struct RegisterBlock {
 }
 register_at!(RegisterBlock, 0xFF000000, uart0);
 register_at!(RegisterBlock, 0xFF010000, uart1);
+assert_layout!(RegisterBlock, 0xFF00_0000, {
+    control => 0xFF00_0000,
+    mode => 0xFF00_0004,
+    interrupt_enable => 0xFF00_0008,
+    interrupt_mask => 0xFF00_0010,
+    channel_interrupt_status => 0xFF00_0014,
+    baud_rate_gen => 0xFF00_0018,
+    rcvr_fifo_trigger_level => 0xFF00_0020,
+    modem_sts => 0xFF00_0028,
+    channel_sts => 0xFF00_002C,
+    tx_rx_fifo => 0xFF00_0030,
+    baud_rate_divider => 0xFF00_0034,
+    flow_delay => 0xFF00_0038,
+    tx_fifo_trigger_level => 0xFF00_0044,
+    rx_fifo_byte_status => 0xFF00_0048,
+});
 
 register!(control, Control, RW, u32);
 register_bit!(control, rxrst, 0);
@@ -3,3 +3,35 @@
 pub fn div_round_closest(q: u32, d: u32) -> u32 {
     (q + (d / 2)) / d
 }
+
+/// Byte offset of `$field` within `$ty`, usable in const context
+macro_rules! field_offset {
+    ($ty: ty, $field: ident) => {{
+        let uninit = core::mem::MaybeUninit::<$ty>::uninit();
+        let base = uninit.as_ptr();
+        // only computes addresses within the (uninitialized) allocation, nothing is read
+        #[allow(unused_unsafe)]
+        unsafe {
+            (core::ptr::addr_of!((*base).$field) as *const u8).offset_from(base as *const u8)
+                as usize
+        }
+    }};
+}
+
+/// Check at compile time that register block fields sit at their UG1087 addresses
+///
+/// `$base` is the block's base address; fields are listed with their absolute address so the
+/// list can be compared against the register reference directly.
+macro_rules! assert_layout {
+    ($ty: ty, $base: expr, { $($field: ident => $addr: expr,)* }) => {
+        $(
+            const _: () = assert!(
+                field_offset!($ty, $field) == $addr - $base,
+                concat!(
+                    stringify!($ty), "::", stringify!($field),
+                    " is not at ", stringify!($addr)
+                )
+            );
+        )*
+    };
+}
@@ -10,12 +10,13 @@
 
 use core::arch::asm;
 
-use log::info;
+use log::{info, warn};
 use r0::zero_bss;
 
 use libboard_zynq_us::{
     clocks, ddr, logger, print, println,
-    slcr::{common::Unlocked, crf_apb, crl_apb, iou_slcr},
+    slcr::{common::Unlocked, iou_slcr},
+    system,
 };
 use libcortex_a53::{asm, cache};
 
@@ -83,16 +84,41 @@ fn enable_fpu() {
 
 fn main() {
     // setup MIO pins
-    iou_slcr::RegisterBlock::unlocked(|slcr| slcr.mio_init());
+    iou_slcr::RegisterBlock::unlocked(|slcr| slcr.mio_init()).unwrap();
 
     // Initialize PLLs, dividers, source selects, etc.
-    clocks::Clocks::init();
+    clocks::Clocks::init().unwrap();
     logger::init().unwrap();
     log::set_max_level(log::LevelFilter::Debug);
     info!("Clock initialization complete.");
+    info!(
+        "Boot mode: {:?}, reset reason: {:?}",
+        system::boot_mode(),
+        system::reset_reason()
+    );
+    match clocks::monitor::verify_clocks() {
+        Ok(()) => info!("Clock verification passed."),
+        Err(e) => warn!("Clock verification failed: {:?}", e),
+    }
+
+    match ddr::init(&clocks::Clocks::get()) {
+        Ok(mut ddr) => {
+            info!("DDR initialization complete.");
+            for region in ddr.memory_map().regions() {
+                info!(
+                    "DDR: {:#011X}-{:#011X} ({} MB)",
+                    region.base,
+                    region.end() - 1,
+                    region.size / (1024 * 1024)
+                );
+            }
+            ddr.memtest();
+        }
+        Err(e) => warn!("DDR initialization failed: {:?}", e),
+    }
 
-    let ddr_config = ddr::spd::read_spd_eeprom();
-    info!("SPD EEPROM read done.\nConfig: {:?}", ddr_config);
+    // only the UART behind the logger is still in use
+    clocks::gate::gate_unused();
 
     loop {}
 }
@@ -145,23 +171,3 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
     }
     loop {}
 }
-
-fn test_addrs() {
-    let crf_apb = crf_apb::RegisterBlock::crf_apb();
-    let mut start = &crf_apb.err_ctrl as *const _ as u32;
-    let mut end = &crf_apb.rst_ddr_ss as *const _ as u32;
-    assert_eq!(start, 0xFD1A_0000);
-    assert_eq!(end, 0xFD1A_0108);
-
-    let crl_apb = crl_apb::RegisterBlock::crl_apb();
-    start = &crl_apb.err_ctrl as *const _ as u32;
-    end = &crl_apb.bank3_status as *const _ as u32;
-    assert_eq!(start, 0xFF5E_0000);
-    assert_eq!(end, 0xFF5E_0288);
-
-    let iou_slcr = iou_slcr::RegisterBlock::iou_slcr();
-    start = &iou_slcr.mio_pin[0] as *const _ as u32;
-    end = &iou_slcr.itr as *const _ as u32;
-    assert_eq!(start, 0xFF18_0000);
-    assert_eq!(end, 0xFF18_0710);
-}